@@ -1,9 +1,10 @@
+use serde::{Serialize, Deserialize};
 
 
 
 
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Card {
     rank: String,
     suit: String
@@ -17,6 +18,9 @@ impl PartialEq for Card {
 }
 
 impl Card {
+    pub const SUITS: [&'static str; 4] = ["Spades", "Hearts", "Diamonds", "Clubs"];
+    pub const RANKS: [&'static str; 13] = ["2", "3", "4", "5", "6", "7", "8", "9", "10", "Jack", "Queen", "King", "Ace"];
+
     pub fn new(rank: String, suit: String) -> Self {
         Card {
             rank,
@@ -33,4 +37,37 @@ impl Card {
         let suit:&str = &self.suit;
         suit
     }
-}
\ No newline at end of file
+
+    // One of each card, in suit then rank order
+    pub fn get_standard_deck() -> Vec<Card> {
+        Self::SUITS.iter()
+            .flat_map(|suit| Self::RANKS.iter().map(move |rank| Card::new(String::from(*rank), String::from(*suit))))
+            .collect()
+    }
+
+    // Short names like "Ah", "Td" or "10s", as used to stack a deck
+    pub fn from_short_name(short_name: &str) -> Option<Card> {
+        let short_name = short_name.trim();
+        let suit_letter = short_name.chars().last()?;
+        let rank_name = &short_name[..short_name.len() - suit_letter.len_utf8()];
+        let suit = Self::SUITS.iter().find(|suit| suit.starts_with(suit_letter.to_ascii_uppercase()))?;
+        let rank = match rank_name.to_ascii_uppercase().as_str() {
+            "T" | "10" => "10",
+            "J" => "Jack",
+            "Q" => "Queen",
+            "K" => "King",
+            "A" => "Ace",
+            number => Self::RANKS[..8].iter().find(|rank| **rank == number)?,
+        };
+        Some(Card::new(String::from(rank), String::from(*suit)))
+    }
+
+    pub fn get_short_name(&self) -> String {
+        let rank = match self.rank.as_str() {
+            "10" => "T",
+            rank => &rank[..1],
+        };
+        let suit = self.suit.chars().next().unwrap_or('?').to_ascii_lowercase();
+        format!("{}{}", rank, suit)
+    }
+}
@@ -1,4 +1,8 @@
-#[derive(Debug, Clone)]
+use serde::{Serialize, Deserialize};
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum PlayerAction {
     Check,
     Bet,
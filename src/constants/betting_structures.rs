@@ -0,0 +1,17 @@
+use serde::{Serialize, Deserialize};
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum BettingStructure {
+    #[default]
+    NoLimit,
+    // Bets and raises are capped at the size of the pot
+    PotLimit,
+    // Bets and raises are one big blind early and two on the turn and river, four bets a street
+    FixedLimit
+}
+
+impl BettingStructure {
+    pub const FIXED_LIMIT_BET_CAP: u32 = 4;
+}
@@ -0,0 +1,14 @@
+use serde::{Serialize, Deserialize};
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BlindType {
+    SmallBlind,
+    BigBlind,
+    Ante,
+    BigBlindAnte,
+    Straddle,
+    MissedBigBlind,
+    DeadSmallBlind
+}
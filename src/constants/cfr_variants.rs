@@ -0,0 +1,23 @@
+use serde::{Serialize, Deserialize};
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum CfrVariant {
+    // Walks the whole game tree every iteration
+    #[default]
+    Vanilla,
+    // Floors regrets at zero, alternates players and weights later iterations more in the average
+    CfrPlus,
+    // Monte Carlo CFR that samples one deal per iteration and walks every action
+    ChanceSampling,
+    // Monte Carlo CFR that samples deals and the opponent's actions
+    ExternalSampling
+}
+
+impl CfrVariant {
+    // Full tree walks need every chance outcome listed, the sampling variants do not
+    pub fn needs_chance_outcomes(&self) -> bool {
+        matches!(self, CfrVariant::Vanilla | CfrVariant::CfrPlus)
+    }
+}
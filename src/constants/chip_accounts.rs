@@ -0,0 +1,12 @@
+use serde::{Serialize, Deserialize};
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ChipAccount {
+    // Chips brought to or taken away from the table
+    Cashier,
+    Player(u32),
+    Pot,
+    House
+}
@@ -0,0 +1,9 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameErrorKind {
+    // The table was not configured or prepared correctly
+    Setup,
+    // A player tried something the rules do not allow
+    IllegalAction,
+    // The engine reached a state it should never be in
+    Internal
+}
@@ -0,0 +1,9 @@
+use serde::{Serialize, Deserialize};
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum GameVariant {
+    #[default]
+    TexasHoldem
+}
@@ -0,0 +1,32 @@
+use serde::{Serialize, Deserialize};
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum HandPhase {
+    Initial,
+    PreFlop,
+    Flop,
+    Turn,
+    River,
+    Showdown,
+    HandOver
+}
+
+impl HandPhase {
+    pub fn is_betting_round(&self) -> bool {
+        matches!(self, HandPhase::PreFlop | HandPhase::Flop | HandPhase::Turn | HandPhase::River)
+    }
+
+    // The street that follows this one and how many community cards it opens
+    pub fn get_next_street(&self) -> (HandPhase, usize) {
+        match self {
+            HandPhase::Initial => (HandPhase::PreFlop, 0),
+            HandPhase::PreFlop => (HandPhase::Flop, 3),
+            HandPhase::Flop => (HandPhase::Turn, 1),
+            HandPhase::Turn => (HandPhase::River, 1),
+            HandPhase::River => (HandPhase::Showdown, 0),
+            HandPhase::Showdown | HandPhase::HandOver => (HandPhase::HandOver, 0),
+        }
+    }
+}
@@ -1,5 +1,23 @@
 mod allowed_actions;
-mod player_versions;
+mod straddle_types;
+mod hand_phases;
+mod blind_types;
+mod error_kinds;
+mod chip_accounts;
+mod transfer_reasons;
+mod betting_structures;
+mod game_variants;
+mod table_positions;
+mod cfr_variants;
 
 pub use allowed_actions::PlayerAction;
-pub use player_versions::PlayerVersion;
\ No newline at end of file
+pub use straddle_types::StraddleType;
+pub use hand_phases::HandPhase;
+pub use blind_types::BlindType;
+pub use error_kinds::GameErrorKind;
+pub use chip_accounts::ChipAccount;
+pub use transfer_reasons::TransferReason;
+pub use betting_structures::BettingStructure;
+pub use game_variants::GameVariant;
+pub use table_positions::TablePosition;
+pub use cfr_variants::CfrVariant;
@@ -1,4 +0,0 @@
-#[derive(Debug, Clone)]
-pub enum PlayerVersion {
-    AutoPlayer
-}
\ No newline at end of file
@@ -0,0 +1,9 @@
+use serde::{Serialize, Deserialize};
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum StraddleType {
+    UnderTheGun,
+    Button
+}
@@ -0,0 +1,13 @@
+use serde::{Serialize, Deserialize};
+
+
+
+// Where a seat acts from before the flop, relative to the button
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TablePosition {
+    Blinds,
+    Early,
+    Middle,
+    // The cutoff and the button
+    Late
+}
@@ -0,0 +1,17 @@
+use crate::constants::BlindType;
+use serde::{Serialize, Deserialize};
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TransferReason {
+    BuyIn,
+    CashOut,
+    Blind(BlindType),
+    Bet,
+    Call,
+    Raise,
+    Refund,
+    Award,
+    Rake
+}
@@ -1,22 +1,70 @@
 
 use super::card::Card;
 use super::player::Player;
-use crate::features::StandardAutoPlayer;
+use crate::features::{StandardAutoPlayer, DealerToken};
 use rand::Rng;
 use rand::seq::SliceRandom;
-use rand::rngs::OsRng;
+use rand::rngs::{OsRng, StdRng};
+use rand::SeedableRng;
+use serde::{Serialize, Deserialize};
+use std::fmt;
 
 
-#[derive(Debug, Clone)]
+/// Only the number of cards left can be read from outside the crate:
+///
+/// ```
+/// let mut dealer = poker::Dealer::new(String::from("auto"));
+/// dealer.generate_cards(1);
+/// dealer.shuffle_cards();
+/// assert_eq!(dealer.get_cards_left(), 52);
+/// ```
+///
+/// The order of the cards to come cannot:
+///
+/// ```compile_fail
+/// let mut dealer = poker::Dealer::new(String::from("auto"));
+/// dealer.generate_cards(1);
+/// let upcoming_cards = dealer.get_deck();
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Dealer {
     dealer_id: String,
     dealer_type: String,
     deck_id: Option<String>,
+    // The deck order and stacked decks are left out of serde so they can't be read from a
+    // serialized dealer; snapshots carry them instead
+    #[serde(skip)]
     deck: Option<Vec<Card>>,
+    deck_count: usize,
+    // Shuffles are reproducible when seeded; each shuffle advances the seed
+    rng_seed: Option<u64>,
+    shuffle_count: u64,
+    // Card orders waiting for the next shuffles, each one stacking a single deck
+    #[serde(skip)]
+    stacked_decks: Vec<Vec<Card>>,
+    // Never serialized, so a token can't be forged from bytes
+    #[serde(skip, default = "DealerToken::new")]
+    token: DealerToken,
     //assigned_game: Option<String>
 }
 
 
+// Only the number of cards left is shown, never their order
+impl fmt::Debug for Dealer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dealer")
+            .field("dealer_id", &self.dealer_id)
+            .field("dealer_type", &self.dealer_type)
+            .field("deck_id", &self.deck_id)
+            .field("deck", &self.deck.as_ref().map(|deck| deck.len()))
+            .field("deck_count", &self.deck_count)
+            .field("rng_seed", &self.rng_seed)
+            .field("shuffle_count", &self.shuffle_count)
+            .field("stacked_decks", &self.stacked_decks.len())
+            .finish()
+    }
+}
+
 impl Dealer {
     pub fn new(dealer_type: String) -> Self {
         let dealer_id:String = String::from("dlr-")+&Self::generate_id(9);
@@ -25,6 +73,11 @@ impl Dealer {
             dealer_type,
             deck_id: None,
             deck: None,
+            deck_count: 0,
+            rng_seed: None,
+            shuffle_count: 0,
+            stacked_decks: Vec::new(),
+            token: DealerToken::new(),
             //assigned_game: None
         }
     }
@@ -48,9 +101,8 @@ impl Dealer {
     fn get_a_card(&mut self) -> Option<Card> {
         match &mut self.deck {
             Some(deck) => {
-                if deck.len() > 0 {
-                    let newly_taken_card = deck.pop();
-                    newly_taken_card
+                if !deck.is_empty() {
+                    deck.pop()
                 } else {
                     println!("Deck is Empty!");
                     None
@@ -63,20 +115,32 @@ impl Dealer {
         }
     }
 
-    pub fn post_blinds(&self, player: &mut Player, amount: u32) -> u32 {
-        player.request_funds(amount, String::from("Blinds"), &self.get_dealer_id())
+    pub(crate) fn post_blinds(&self, player: &mut Player, amount: u32) -> u32 {
+        player.request_funds(amount, String::from("Blinds"), &self.token)
+    }
+
+    pub(crate) fn post_ante(&self, player: &mut Player, amount: u32) -> u32 {
+        player.request_funds(amount, String::from("Ante"), &self.token)
+    }
+
+    pub(crate) fn post_dead_blind(&self, player: &mut Player, amount: u32) -> u32 {
+        player.request_funds(amount, String::from("Dead Blind"), &self.token)
+    }
+
+    pub(crate) fn pay_player(&self, player: &mut Player, amount: u32) {
+        player.set_coins(player.get_coins() + amount);
     }
 
-    pub fn player_bet(&self, player: &mut Player, amount: u32) -> u32 {
-        player.request_funds(amount, String::from("Bet"), &self.get_dealer_id())
+    pub(crate) fn player_bet(&self, player: &mut Player, amount: u32) -> u32 {
+        player.request_funds(amount, String::from("Bet"), &self.token)
     }
 
-    pub fn player_raise(&self, player: &mut Player, amount: u32) -> u32 {
-        player.request_funds(amount, String::from("Raise"), &self.get_dealer_id())
+    pub(crate) fn player_raise(&self, player: &mut Player, amount: u32) -> u32 {
+        player.request_funds(amount, String::from("Raise"), &self.token)
     }
 
-    pub fn player_call(&self, player: &mut Player, amount: u32) -> u32 {
-        player.request_funds(amount, String::from("Raise"), &self.get_dealer_id())
+    pub(crate) fn player_call(&self, player: &mut Player, amount: u32) -> u32 {
+        player.request_funds(amount, String::from("Call"), &self.token)
     }
 
     pub fn get_dealer_id(&self) -> &str {
@@ -90,14 +154,13 @@ impl Dealer {
     }
 
     pub fn get_deck_id(&self) -> &str{
-        let deck_id:&str = &self.deck_id.as_ref()
-            .map(String::as_str).unwrap_or_default();
+        let deck_id:&str = self.deck_id.as_deref().unwrap_or_default();
         deck_id
     }
 
     pub fn generate_cards(&mut self, num_decks: usize) -> bool{
         let current_deck:Vec<Card> = match &self.deck {
-            Some(deck) => deck.iter().cloned().collect(),
+            Some(deck) => deck.to_vec(),
             None => Vec::new(),
         };
         match current_deck.len() {
@@ -138,6 +201,7 @@ impl Dealer {
                 let cards = generate_cards_recursive(num_decks, &suits, &ranks);
                 self.deck_id = Some(deck_id);
                 self.deck = Some(cards);
+                self.deck_count = num_decks;
                 true
             }
             _ => false
@@ -145,16 +209,41 @@ impl Dealer {
 
     }
 
-    pub fn get_deck(&self) -> &Option<Vec<Card>> {
+    pub(crate) fn set_rng_seed(&mut self, rng_seed: u64) {
+        self.rng_seed = Some(rng_seed);
+        self.shuffle_count = 0;
+    }
+
+    pub fn get_deck_count(&self) -> usize {
+        self.deck_count
+    }
+
+    pub(crate) fn collect_cards(&mut self) {
+        self.deck_id = None;
+        self.deck = None;
+    }
+
+    // The cards still to come, in order, so only the engine may look
+    pub(crate) fn get_deck(&self) -> &Option<Vec<Card>> {
         let current_deck:&Option<Vec<Card>> = &self.deck;
         current_deck
     }
 
+    pub fn get_cards_left(&self) -> usize {
+        self.deck.as_ref().map(|deck| deck.len()).unwrap_or(0)
+    }
+
     pub fn shuffle_cards(&mut self) {
-        let mut rng = OsRng::default();
         match &mut self.deck {
             Some(deck) => {
-                deck.shuffle(&mut rng);
+                match self.rng_seed {
+                    Some(rng_seed) => {
+                        let mut rng = StdRng::seed_from_u64(rng_seed.wrapping_add(self.shuffle_count));
+                        deck.shuffle(&mut rng);
+                    },
+                    None => deck.shuffle(&mut OsRng),
+                }
+                self.shuffle_count += 1;
             },
             _ => {
                 println!("Dealer has no Deck to shuffle!");
@@ -162,11 +251,52 @@ impl Dealer {
         };
     }
     
-    pub fn deal_player(&mut self, player: &mut Option<&mut Player>) {
+    // Moves the given cards to the top of the deck so they are dealt in the order listed,
+    // leaving the rest of the deck as it was
+    pub(crate) fn arrange_deck(&mut self, stacked_cards: &[Card]) -> bool {
+        let mut deck = match self.deck.clone() {
+            Some(deck) => deck,
+            None => return false,
+        };
+        for card in stacked_cards {
+            match deck.iter().position(|deck_card| deck_card == card) {
+                Some(card_index) => { deck.remove(card_index); },
+                None => return false,
+            }
+        }
+        deck.extend(stacked_cards.iter().rev().cloned());
+        self.deck = Some(deck);
+        true
+    }
+
+    pub(crate) fn get_stacked_decks(&self) -> &Vec<Vec<Card>> {
+        &self.stacked_decks
+    }
+
+    // Puts back a deck order and stacked decks taken from a snapshot
+    pub(crate) fn set_cards(&mut self, deck: Option<Vec<Card>>, stacked_decks: Vec<Vec<Card>>) {
+        self.deck = deck;
+        self.stacked_decks = stacked_decks;
+    }
+
+    pub(crate) fn queue_stacked_deck(&mut self, stacked_cards: Vec<Card>) {
+        self.stacked_decks.push(stacked_cards);
+    }
+
+    pub(crate) fn take_stacked_deck(&mut self) -> Option<Vec<Card>> {
+        match self.stacked_decks.is_empty() {
+            true => None,
+            false => Some(self.stacked_decks.remove(0)),
+        }
+    }
+
+    pub(crate) fn deal_player(&mut self, player: &mut Option<&mut Player>) {
         if let Some(_deck) = &self.deck {
             if let Some(player) = player {
-                player.receive_card(self.get_a_card());
-                player.receive_card(self.get_a_card());
+                let first_card = self.get_a_card();
+                player.receive_card(first_card, &self.token);
+                let second_card = self.get_a_card();
+                player.receive_card(second_card, &self.token);
             } else {
                 println!("No players to deal!");
             }
@@ -176,7 +306,7 @@ impl Dealer {
         
     }
 
-    pub fn open_cards(&mut self, number_of_cards: usize) -> Option<Vec<Card>> {
+    pub(crate) fn open_cards(&mut self, number_of_cards: usize) -> Option<Vec<Card>> {
         if let Some(_deck) = &self.deck {
            let mut cards_for_community = Vec::new();
             for _idx in 0..number_of_cards {
@@ -190,12 +320,8 @@ impl Dealer {
         
     }
 
-    pub fn request_player_hand(&mut self, player: &Player) -> Option<Vec<Card>> {
-        if let Some(retrieved_hand_cards) = &player.clone().request_hand_cards(self.get_dealer_id()).cloned() {
-            Some(retrieved_hand_cards.clone())
-        } else {
-            None
-        }
+    pub(crate) fn request_player_hand(&mut self, player: &Player) -> Option<Vec<Card>> {
+        Some(player.clone().request_hand_cards(&self.token).clone())
     }
 
 }
\ No newline at end of file
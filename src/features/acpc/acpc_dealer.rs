@@ -0,0 +1,68 @@
+use crate::constants::BettingStructure;
+use crate::features::{TableConfig, GameError, GameObserver, AcpcAgent};
+use crate::poker_game::PokerGame;
+
+
+
+// Plays a match on a table config with ACPC clients in the seats whose agent is "acpc".
+// Building the table waits for each of those clients to connect.
+pub struct AcpcDealer {
+    table_config: TableConfig,
+    // Every hand starts from the same stacks, as in ACPC matches
+    reset_stacks: bool,
+    observers: Vec<Box<dyn GameObserver>>
+}
+
+impl AcpcDealer {
+    pub fn new(table_config: TableConfig) -> Result<Self, GameError> {
+        let invalid = |reason: &str| Err(GameError::InvalidConfig { reason: format!("ACPC match: {}", reason) });
+
+        if table_config.betting_structure == BettingStructure::PotLimit {
+            return invalid("ACPC games are limit or no-limit");
+        }
+        let stakes = &table_config.stakes;
+        if stakes.ante > 0 || stakes.big_blind_ante > 0 || stakes.straddle.is_some() {
+            return invalid("ACPC games have blinds only, no antes or straddles");
+        }
+        if !table_config.players.iter().any(|seat_config| seat_config.agent == AcpcAgent::AGENT_NAME) {
+            return invalid("no seat is played by an acpc agent");
+        }
+        Ok(AcpcDealer { table_config, reset_stacks: true, observers: Vec::new() })
+    }
+
+    pub fn with_reset_stacks(mut self, reset_stacks: bool) -> Self {
+        self.reset_stacks = reset_stacks;
+        self
+    }
+
+    pub fn subscribe(&mut self, observer: Box<dyn GameObserver>) {
+        self.observers.push(observer);
+    }
+
+    // The ports the acpc seats listen on, in seat config order
+    pub fn get_ports(&self) -> Vec<u64> {
+        self.table_config.players.iter()
+            .filter(|seat_config| seat_config.agent == AcpcAgent::AGENT_NAME)
+            .filter_map(|seat_config| seat_config.agent_params.get("port").and_then(|port| port.as_u64()))
+            .collect()
+    }
+
+    // Stacks are put back to the largest starting stack in the config before every hand.
+    // Players' notes are saved at the end when the config has a stats directory.
+    pub fn play_match(self, number_of_hands: u32) -> Result<PokerGame, GameError> {
+        let starting_stack = self.table_config.players.iter().map(|seat_config| seat_config.coins).max().unwrap_or_default();
+        let mut game = self.table_config.build()?;
+        for observer in self.observers {
+            game.subscribe(observer);
+        }
+        for _ in 0..number_of_hands {
+            if self.reset_stacks {
+                game.reset_stacks(starting_stack)?;
+            }
+            game.start_next_hand()?;
+            game.play()?;
+        }
+        game.save_stats()?;
+        Ok(game)
+    }
+}
@@ -0,0 +1,159 @@
+use crate::card::Card;
+use crate::constants::{PlayerAction, BettingStructure};
+use crate::features::{PlayerView, GameEvent, ActionResult};
+use std::collections::HashMap;
+use std::fmt;
+
+
+
+// One line of the Annual Computer Poker Competition protocol as one seat sees it:
+// MATCHSTATE:<position>:<hand number>:<betting>:<cards>
+#[derive(Debug, Clone, PartialEq)]
+pub struct AcpcMatchState {
+    pub position: usize,
+    pub hand_number: u32,
+    pub betting: String,
+    pub cards: String
+}
+
+impl AcpcMatchState {
+    pub const PREFIX: &'static str = "MATCHSTATE";
+    const STREET_CARDS: [usize; 3] = [3, 1, 1];
+
+    pub fn from_view(player_view: &PlayerView, reverse_blinds: bool) -> Option<Self> {
+        let position_order = Self::get_position_order(player_view, reverse_blinds);
+        let position = position_order.iter().position(|&player_id| player_id == player_view.player_id)?;
+
+        let mut shown_cards: HashMap<u32, Vec<Card>> = HashMap::new();
+        for event in player_view.action_history.iter() {
+            if let GameEvent::Showdown { hands } = event {
+                for (player_id, hole_cards, _) in hands.iter() {
+                    shown_cards.insert(*player_id, hole_cards.clone());
+                }
+            }
+        }
+        let hole_cards = position_order.iter()
+            .map(|&player_id| match player_id == player_view.player_id {
+                true => Self::get_card_names(&player_view.hole_cards),
+                false => shown_cards.get(&player_id).map(|hole_cards| Self::get_card_names(hole_cards)).unwrap_or_default(),
+            })
+            .collect::<Vec<String>>()
+            .join("|");
+        let mut cards = hole_cards;
+        let mut board_start = 0;
+        for street_cards in Self::STREET_CARDS {
+            if player_view.board.len() < board_start + street_cards {
+                break;
+            }
+            cards.push('/');
+            cards.push_str(&Self::get_card_names(&player_view.board[board_start..board_start + street_cards]));
+            board_start += street_cards;
+        }
+
+        Some(AcpcMatchState {
+            position,
+            // ACPC counts hands from zero
+            hand_number: player_view.hand_number.saturating_sub(1),
+            betting: Self::get_betting(player_view),
+            cards
+        })
+    }
+
+    // Player ids in ACPC position order, starting at the small blind. Heads-up games with
+    // reversed blinds, as played in the competition, put the big blind first instead.
+    pub fn get_position_order(player_view: &PlayerView, reverse_blinds: bool) -> Vec<u32> {
+        let mut seat_views: Vec<_> = player_view.seats.iter().filter(|seat_view| !seat_view.sitting_out).collect();
+        seat_views.sort_by_key(|seat_view| seat_view.seat);
+        let small_blind_index = seat_views.iter().position(|seat_view| seat_view.seat == player_view.small_blind_seat).unwrap_or(0);
+        seat_views.rotate_left(small_blind_index);
+        if reverse_blinds && seat_views.len() == 2 {
+            seat_views.reverse();
+        }
+        seat_views.iter().map(|seat_view| seat_view.player_id).collect()
+    }
+
+    // Streets are split by "/". Limit raises are a bare "r", no-limit ones "r" followed by
+    // everything the player has put in this hand once the raise is made.
+    pub fn get_betting(player_view: &PlayerView) -> String {
+        let no_limit = player_view.betting_structure != BettingStructure::FixedLimit;
+        let mut put_in: HashMap<u32, u32> = HashMap::new();
+        let mut rounds: Vec<String> = vec![String::new()];
+        for event in player_view.action_history.iter() {
+            match event {
+                GameEvent::HandStarted { .. } => {
+                    put_in.clear();
+                    rounds = vec![String::new()];
+                },
+                GameEvent::BlindsPosted { player_id, amount, .. } => *put_in.entry(*player_id).or_insert(0) += amount,
+                GameEvent::StreetDealt { .. } => rounds.push(String::new()),
+                GameEvent::PlayerActed { player_id, action, amount, .. } => {
+                    let total_put_in = put_in.entry(*player_id).or_insert(0);
+                    *total_put_in += amount;
+                    let symbol = match action {
+                        PlayerAction::Fold => String::from("f"),
+                        PlayerAction::Check | PlayerAction::Call => String::from("c"),
+                        PlayerAction::Bet | PlayerAction::Raise if no_limit => format!("r{}", total_put_in),
+                        PlayerAction::Bet | PlayerAction::Raise => String::from("r"),
+                    };
+                    if let Some(round) = rounds.last_mut() {
+                        round.push_str(&symbol);
+                    }
+                },
+                _ => {},
+            }
+        }
+        rounds.join("/")
+    }
+
+    fn get_card_names(cards: &[Card]) -> String {
+        cards.iter().map(|card| card.get_short_name()).collect()
+    }
+
+    // The action a client added to the end of this state, like "r250" in "MATCHSTATE:0:3:cr:Kd7c|:r250"
+    pub fn get_response_action<'a>(&self, response: &'a str) -> Option<&'a str> {
+        response.trim_end().strip_prefix(&format!("{}:", self))
+    }
+
+    // Turns an ACPC action into a legal one the way the ACPC dealer fixes actions: a fold with
+    // nothing to call checks, an impossible raise calls and raise sizes move inside the limits
+    pub fn get_action_result(acpc_action: &str, player_view: &PlayerView) -> Result<ActionResult, String> {
+        let passive_action = if player_view.is_legal(PlayerAction::Check) { PlayerAction::Check } else { PlayerAction::Call };
+        let passive_result = ActionResult { action: passive_action, success: true, amount: None };
+        let mut characters = acpc_action.trim().chars();
+        match characters.next() {
+            Some('f') if player_view.is_legal(PlayerAction::Fold) && !player_view.is_legal(PlayerAction::Check) => {
+                Ok(ActionResult { action: PlayerAction::Fold, success: true, amount: None })
+            },
+            Some('f') | Some('c') | Some('k') => Ok(passive_result),
+            Some('r') => {
+                let size_text = characters.as_str();
+                let total_put_in = match size_text.is_empty() {
+                    true => None,
+                    false => Some(size_text.parse::<u32>().map_err(|_| format!("raise size {:?} is not a number", size_text))?),
+                };
+                let action = match [PlayerAction::Raise, PlayerAction::Bet].into_iter().find(|&action| player_view.is_legal(action)) {
+                    Some(action) => action,
+                    None => return Ok(passive_result),
+                };
+                let minimum = if action == PlayerAction::Bet { player_view.minimum_bet } else { player_view.minimum_raise_to };
+                let amount = match (player_view.betting_structure, total_put_in) {
+                    (BettingStructure::FixedLimit, _) | (_, None) => minimum,
+                    (_, Some(total_put_in)) => {
+                        // Sizes count the whole hand, bets here count only this street
+                        let put_in_before_street = player_view.get_own_seat()
+                            .map_or(0, |seat_view| seat_view.total_bet - seat_view.current_bet);
+                        total_put_in.saturating_sub(put_in_before_street).clamp(minimum, player_view.maximum_bet_to.max(minimum))
+                    },
+                };
+                Ok(ActionResult { action, success: true, amount: Some(amount) })
+            },
+            _ => Err(format!("{:?} is not an ACPC action", acpc_action)),
+        }
+    }
+}
+
+impl fmt::Display for AcpcMatchState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}:{}:{}:{}", Self::PREFIX, self.position, self.hand_number, self.betting, self.cards)
+    }
+}
@@ -0,0 +1,5 @@
+mod acpc_match_state;
+mod acpc_dealer;
+
+pub use acpc_match_state::AcpcMatchState;
+pub use acpc_dealer::AcpcDealer;
@@ -0,0 +1,165 @@
+use crate::features::{PlayerView, ActionResult, GameError, AcpcMatchState, SubprocessAgent};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+
+
+// A seat played by an ACPC client connected over TCP. The client gets a MATCHSTATE line when
+// it is to act and another when the hand is over, and answers the first with its action
+// within the time the table's limits leave it. Late, unreadable and disconnected clients
+// fold and the problem is reported to the table.
+pub struct AcpcAgent {
+    connection: TcpStream,
+    replies: Receiver<String>,
+    client_address: String,
+    reverse_blinds: bool,
+    disconnected: bool,
+    problems: Vec<String>,
+    reported_problem_count: usize
+}
+
+impl AcpcAgent {
+    pub const AGENT_NAME: &'static str = "acpc";
+    pub const PROTOCOL_VERSION: &'static str = "VERSION:2.";
+
+    // Waits on localhost for one client to connect and say which protocol version it speaks
+    pub fn listen(port: u16, reverse_blinds: bool) -> Result<Self, GameError> {
+        let listener = TcpListener::bind(("127.0.0.1", port))
+            .map_err(|error| GameError::InvalidConfig { reason: format!("could not listen on port {}: {}", port, error) })?;
+        let local_address = listener.local_addr().map(|address| address.to_string()).unwrap_or_default();
+        let (connection, client_address) = listener.accept()
+            .map_err(|error| GameError::InvalidConfig { reason: format!("no ACPC client connected on {}: {}", local_address, error) })?;
+        AcpcAgent::from_connection(connection, client_address.to_string(), reverse_blinds)
+    }
+
+    pub fn from_connection(connection: TcpStream, client_address: String, reverse_blinds: bool) -> Result<Self, GameError> {
+        let reader = connection.try_clone()
+            .map_err(|error| GameError::InvalidConfig { reason: format!("ACPC client {}: {}", client_address, error) })?;
+        // Lines are read on their own thread so a silent client can be timed out
+        let (reply_sender, replies) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(reader).lines() {
+                match line {
+                    Ok(line) => if reply_sender.send(line).is_err() { break },
+                    Err(_) => break,
+                }
+            }
+        });
+
+        match replies.recv() {
+            Ok(version) if version.starts_with(Self::PROTOCOL_VERSION) => {},
+            Ok(version) => return Err(GameError::InvalidConfig { reason: format!("ACPC client {} sent {:?} instead of VERSION:2.0.0", client_address, version) }),
+            Err(_) => return Err(GameError::InvalidConfig { reason: format!("ACPC client {} did not send its protocol version", client_address) }),
+        }
+        Ok(AcpcAgent { connection, replies, client_address, reverse_blinds, disconnected: false, problems: Vec::new(), reported_problem_count: 0 })
+    }
+
+    // Reads "port" and the optional "reverse_blinds" from a seat's agent params. Heads-up
+    // blinds are reversed unless told otherwise, as in the competition games.
+    pub fn from_params(agent_params: &serde_json::Value) -> Result<Self, GameError> {
+        let port = agent_params.get("port")
+            .and_then(|port| port.as_u64())
+            .and_then(|port| u16::try_from(port).ok())
+            .ok_or(GameError::InvalidConfig { reason: String::from("acpc agent needs a port to listen on") })?;
+        let reverse_blinds = agent_params.get("reverse_blinds").and_then(|reverse_blinds| reverse_blinds.as_bool()).unwrap_or(true);
+        AcpcAgent::listen(port, reverse_blinds)
+    }
+
+    pub fn get_client_address(&self) -> &str {
+        &self.client_address
+    }
+
+    pub fn get_problems(&self) -> &Vec<String> {
+        &self.problems
+    }
+
+    // The problems logged since this was last called
+    pub(crate) fn take_new_problems(&mut self) -> Vec<String> {
+        let new_problems = self.problems[self.reported_problem_count..].to_vec();
+        self.reported_problem_count = self.problems.len();
+        new_problems
+    }
+
+    pub fn is_disconnected(&self) -> bool {
+        self.disconnected
+    }
+
+    fn log_problem(&mut self, player_view: &PlayerView, problem: String) {
+        let problem = format!("ACPC client {} for player {} in hand {}: {}", self.client_address, player_view.player_id, player_view.hand_number, problem);
+        self.problems.push(problem);
+    }
+
+    fn mark_disconnected(&mut self, player_view: &PlayerView, problem: String) {
+        if !self.disconnected {
+            self.disconnected = true;
+            self.log_problem(player_view, format!("{}, folding its hands from now on", problem));
+        }
+    }
+
+    fn send_state(&mut self, player_view: &PlayerView, match_state: &AcpcMatchState) -> bool {
+        let sent = write!(self.connection, "{}\r\n", match_state).and_then(|_| self.connection.flush());
+        if let Err(error) = sent {
+            self.mark_disconnected(player_view, format!("could not be sent the match state ({})", error));
+            return false;
+        }
+        true
+    }
+
+    pub(crate) fn request_action(&mut self, player_view: &PlayerView) -> ActionResult {
+        if self.disconnected {
+            return SubprocessAgent::get_fold_action(player_view);
+        }
+        let match_state = match AcpcMatchState::from_view(player_view, self.reverse_blinds) {
+            Some(match_state) => match_state,
+            None => {
+                self.log_problem(player_view, String::from("the seat has no ACPC position"));
+                return SubprocessAgent::get_fold_action(player_view);
+            },
+        };
+
+        // Replies that came in too late for earlier states are thrown away
+        while self.replies.try_recv().is_ok() {}
+        if !self.send_state(player_view, &match_state) {
+            return SubprocessAgent::get_fold_action(player_view);
+        }
+
+        let deadline = player_view.time_left_ms.and_then(|time_left_ms| Instant::now().checked_add(Duration::from_millis(time_left_ms)));
+        loop {
+            match SubprocessAgent::receive_line(&self.replies, deadline) {
+                Ok(response) => {
+                    // Comments, blank lines and answers to other states are skipped
+                    let acpc_action = match match_state.get_response_action(&response) {
+                        Some(acpc_action) => acpc_action,
+                        None => continue,
+                    };
+                    return match AcpcMatchState::get_action_result(acpc_action, player_view) {
+                        Ok(action_result) => action_result,
+                        Err(problem) => {
+                            self.log_problem(player_view, problem);
+                            SubprocessAgent::get_fold_action(player_view)
+                        },
+                    };
+                },
+                Err(RecvTimeoutError::Timeout) => {
+                    self.log_problem(player_view, format!("did not reply within {}ms", player_view.time_left_ms.unwrap_or_default()));
+                    return SubprocessAgent::get_fold_action(player_view);
+                },
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.mark_disconnected(player_view, String::from("disconnected"));
+                    return SubprocessAgent::get_fold_action(player_view);
+                },
+            }
+        }
+    }
+
+    pub(crate) fn send_final_state(&mut self, player_view: &PlayerView) {
+        if self.disconnected {
+            return;
+        }
+        if let Some(match_state) = AcpcMatchState::from_view(player_view, self.reverse_blinds) {
+            self.send_state(player_view, &match_state);
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use crate::features::{PlayerAgent, RandomAgent, HumanAgent, RuleBasedAgent, EquityAgent, EquitySettings, Personality, CfrAgent, ScriptedAgent, SubprocessAgent, AcpcAgent, GameError};
+use std::collections::HashMap;
+
+
+
+// Builds an agent from the parameters given for its seat in the table config
+pub type AgentFactory = Box<dyn Fn(&serde_json::Value) -> Result<Box<dyn PlayerAgent>, GameError>>;
+
+pub struct AgentRegistry {
+    factories: HashMap<String, AgentFactory>
+}
+
+impl AgentRegistry {
+    pub fn new() -> Self {
+        AgentRegistry { factories: HashMap::new() }
+    }
+
+    // The agents that ship with the engine. The bots all take an optional personality in their params.
+    pub fn with_defaults() -> Self {
+        let mut agent_registry = AgentRegistry::new();
+        agent_registry.register(RandomAgent::AGENT_NAME, Box::new(|agent_params| {
+            let random_agent = RandomAgent::new();
+            Ok(match Personality::from_params(agent_params)? {
+                Some(personality) => Box::new(random_agent.with_personality(personality)),
+                None => Box::new(random_agent),
+            })
+        }));
+        agent_registry.register(RuleBasedAgent::AGENT_NAME, Box::new(|agent_params| {
+            let rule_based_agent = RuleBasedAgent::new();
+            Ok(match Personality::from_params(agent_params)? {
+                Some(personality) => Box::new(rule_based_agent.with_personality(personality)),
+                None => Box::new(rule_based_agent),
+            })
+        }));
+        agent_registry.register(EquityAgent::AGENT_NAME, Box::new(|agent_params| {
+            let equity_agent = EquityAgent::new(EquitySettings::from_params(agent_params)?);
+            Ok(match Personality::from_params(agent_params)? {
+                Some(personality) => Box::new(equity_agent.with_personality(personality)),
+                None => Box::new(equity_agent),
+            })
+        }));
+        agent_registry.register(CfrAgent::AGENT_NAME, Box::new(|agent_params| Ok(Box::new(CfrAgent::from_params(agent_params)?))));
+        agent_registry.register(ScriptedAgent::AGENT_NAME, Box::new(|agent_params| Ok(Box::new(ScriptedAgent::from_params(agent_params)?))));
+        agent_registry.register(SubprocessAgent::AGENT_NAME, Box::new(|agent_params| Ok(Box::new(SubprocessAgent::from_params(agent_params)?))));
+        agent_registry.register(AcpcAgent::AGENT_NAME, Box::new(|agent_params| Ok(Box::new(AcpcAgent::from_params(agent_params)?))));
+        agent_registry.register(HumanAgent::AGENT_NAME, Box::new(|_| Ok(Box::new(HumanAgent::new()))));
+        agent_registry
+    }
+
+    pub fn register(&mut self, agent_name: &str, agent_factory: AgentFactory) {
+        self.factories.insert(String::from(agent_name), agent_factory);
+    }
+
+    pub fn create(&self, agent_name: &str, agent_params: &serde_json::Value) -> Result<Box<dyn PlayerAgent>, GameError> {
+        match self.factories.get(agent_name) {
+            Some(agent_factory) => agent_factory(agent_params),
+            None => Err(GameError::UnknownAgent { agent_name: String::from(agent_name) }),
+        }
+    }
+
+    pub fn get_agent_names(&self) -> Vec<&str> {
+        let mut agent_names: Vec<&str> = self.factories.keys().map(|agent_name| agent_name.as_str()).collect();
+        agent_names.sort();
+        agent_names
+    }
+}
+
+impl Default for AgentRegistry {
+    fn default() -> Self {
+        AgentRegistry::with_defaults()
+    }
+}
@@ -0,0 +1,89 @@
+use crate::constants::PlayerAction;
+use crate::features::{PlayerView, GameEvent, CfrStrategy, LimitHoldem, RuleBasedAgent, GameError};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+
+
+// Plays a strategy trained on the abstracted heads-up limit hold'em game. Spots the strategy
+// never reached, and tables it was not trained for, are played by the rule-based agent instead.
+#[derive(Debug, Clone)]
+pub struct CfrAgent {
+    strategy: CfrStrategy,
+    rng: StdRng,
+    fallback_agent: RuleBasedAgent
+}
+
+impl CfrAgent {
+    pub const AGENT_NAME: &'static str = "cfr";
+
+    pub fn new(strategy: CfrStrategy) -> Self {
+        CfrAgent { strategy, rng: StdRng::from_entropy(), fallback_agent: RuleBasedAgent::new() }
+    }
+
+    pub fn with_rng_seed(mut self, rng_seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(rng_seed);
+        self
+    }
+
+    // Reads "strategy_file" and an optional "rng_seed" from a seat's agent params
+    pub fn from_params(agent_params: &serde_json::Value) -> Result<Self, GameError> {
+        let strategy_file = agent_params.get("strategy_file")
+            .and_then(|strategy_file| strategy_file.as_str())
+            .ok_or(GameError::InvalidConfig { reason: String::from("cfr agent needs a strategy_file") })?;
+        let cfr_agent = CfrAgent::new(CfrStrategy::from_json_file(strategy_file)?);
+        Ok(match agent_params.get("rng_seed").and_then(|rng_seed| rng_seed.as_u64()) {
+            Some(rng_seed) => cfr_agent.with_rng_seed(rng_seed),
+            None => cfr_agent,
+        })
+    }
+
+    pub fn get_strategy(&self) -> &CfrStrategy {
+        &self.strategy
+    }
+
+    pub(crate) fn get_fallback_agent(&mut self) -> &mut RuleBasedAgent {
+        &mut self.fallback_agent
+    }
+
+    // The betting this hand in the abstract game's notation, one string for each street
+    pub fn get_betting_rounds(player_view: &PlayerView) -> Vec<String> {
+        let mut betting_rounds = vec![String::new()];
+        for event in player_view.action_history.iter() {
+            match event {
+                GameEvent::HandStarted { .. } => betting_rounds = vec![String::new()],
+                GameEvent::StreetDealt { .. } => betting_rounds.push(String::new()),
+                GameEvent::PlayerActed { action, .. } => {
+                    let symbol = match action {
+                        PlayerAction::Fold => 'f',
+                        PlayerAction::Check | PlayerAction::Call => 'c',
+                        PlayerAction::Bet | PlayerAction::Raise => 'r',
+                    };
+                    if let Some(betting_round) = betting_rounds.last_mut() {
+                        betting_round.push(symbol);
+                    }
+                },
+                _ => {},
+            }
+        }
+        betting_rounds
+    }
+
+    pub fn get_info_set_key(player_view: &PlayerView) -> String {
+        LimitHoldem::get_info_set_key_for(&player_view.hole_cards, &player_view.board, &Self::get_betting_rounds(player_view))
+    }
+
+    // The strategy's action for this spot, or None when it has nothing to say about it
+    pub fn choose_action(&mut self, player_view: &PlayerView) -> Option<PlayerAction> {
+        if self.strategy.game_name != LimitHoldem::GAME_NAME {
+            return None;
+        }
+        let abstract_action = self.strategy.sample_action(&Self::get_info_set_key(player_view), &mut self.rng)?;
+        match abstract_action.as_str() {
+            "f" => Some(PlayerAction::Fold),
+            "c" => Some(PlayerAction::Call),
+            "r" => Some(PlayerAction::Raise),
+            _ => None,
+        }
+    }
+}
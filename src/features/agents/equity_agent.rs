@@ -0,0 +1,229 @@
+use crate::card::Card;
+use crate::constants::PlayerAction;
+use crate::features::{PlayerView, PokerRules, RuleBasedAgent, GameError, Personality, PersonalityState};
+use crate::PokerGame;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use serde::{Serialize, Deserialize};
+
+
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EquitySettings {
+    // Hands dealt out per decision to estimate equity
+    pub simulations: u32,
+    // Same seed, same decisions for the same spots
+    pub rng_seed: Option<u64>,
+    // Opponents are assumed to hold hands from this top share of all starting hands
+    pub opponent_range_percent: f32,
+    // Future winnings expected when a draw hits, as a multiple of the call
+    pub implied_odds: f32,
+    // Equity relative to an even share of the pot needed to bet, and to raise a bet
+    pub bet_strength: f32,
+    pub raise_strength: f32,
+    // Bets and raises as a share of the pot
+    pub bet_pot_fraction: f32
+}
+
+impl Default for EquitySettings {
+    fn default() -> Self {
+        EquitySettings {
+            simulations: 500,
+            rng_seed: None,
+            opponent_range_percent: 50.0,
+            implied_odds: 0.5,
+            bet_strength: 1.3,
+            raise_strength: 1.6,
+            bet_pot_fraction: 0.66
+        }
+    }
+}
+
+impl EquitySettings {
+    pub fn from_params(agent_params: &serde_json::Value) -> Result<Self, GameError> {
+        if agent_params.is_null() {
+            return Ok(EquitySettings::default());
+        }
+        let equity_settings: EquitySettings = serde_json::from_value(agent_params.clone())
+            .map_err(|error| GameError::InvalidConfig { reason: format!("equity agent: {}", error) })?;
+        if equity_settings.simulations == 0 {
+            return Err(GameError::InvalidConfig { reason: String::from("equity agent: at least one simulation is needed") });
+        }
+        if !(0.0..=100.0).contains(&equity_settings.opponent_range_percent) || equity_settings.opponent_range_percent == 0.0 {
+            return Err(GameError::InvalidConfig { reason: String::from("equity agent: opponent range must be above 0 and at most 100 percent") });
+        }
+        Ok(equity_settings)
+    }
+}
+
+// Estimates its equity against the assumed opponent range by dealing out the rest of the hand
+// many times, then takes whichever of fold, call or raise has the best expected value.
+// A personality, when given, adds bluffs, extra aggression and looser calls on top.
+#[derive(Debug, Clone)]
+pub struct EquityAgent {
+    equity_settings: EquitySettings,
+    // The weakest Chen score still inside the opponent range
+    range_threshold: i32,
+    rng: StdRng,
+    personality_state: Option<PersonalityState>
+}
+
+impl EquityAgent {
+    pub const AGENT_NAME: &'static str = "equity";
+    // Dealing stops looking for an in-range hand after this many tries
+    const RANGE_ATTEMPTS: u32 = 50;
+
+    pub fn new(equity_settings: EquitySettings) -> Self {
+        let rng = match equity_settings.rng_seed {
+            Some(rng_seed) => StdRng::seed_from_u64(rng_seed),
+            None => StdRng::from_entropy(),
+        };
+        let range_threshold = RuleBasedAgent::get_chen_threshold(equity_settings.opponent_range_percent);
+        EquityAgent { equity_settings, range_threshold, rng, personality_state: None }
+    }
+
+    pub fn with_personality(mut self, personality: Personality) -> Self {
+        self.personality_state = Some(PersonalityState::new(personality));
+        self
+    }
+
+    pub fn get_personality(&self) -> Option<&Personality> {
+        self.personality_state.as_ref().map(|personality_state| personality_state.get_personality())
+    }
+
+    pub fn observe_hand_end(&mut self, player_view: &PlayerView) {
+        if let Some(personality_state) = self.personality_state.as_mut() {
+            personality_state.on_hand_end(player_view);
+        }
+    }
+
+    pub fn get_equity_settings(&self) -> &EquitySettings {
+        &self.equity_settings
+    }
+
+    // The chance of winning the pot against the given number of opponents, ties counted as shares.
+    // With several decks in the shoe only one copy of each known card is out of play.
+    pub fn estimate_equity(&mut self, hole_cards: &[Card], board: &[Card], opponents: usize, deck_count: usize) -> f32 {
+        let mut unseen_cards: Vec<Card> = (0..deck_count.max(1)).flat_map(|_| Card::get_standard_deck()).collect();
+        for known_card in hole_cards.iter().chain(board.iter()) {
+            if let Some(position) = unseen_cards.iter().position(|card| card == known_card) {
+                unseen_cards.swap_remove(position);
+            }
+        }
+        let cards_to_come = 5usize.saturating_sub(board.len());
+        if opponents == 0 {
+            return 1.0;
+        }
+        if unseen_cards.len() < opponents * 2 + cards_to_come {
+            return 0.0;
+        }
+
+        let mut total_share = 0.0;
+        for _ in 0..self.equity_settings.simulations {
+            let mut deck = unseen_cards.clone();
+            deck.shuffle(&mut self.rng);
+
+            let mut opponent_hands: Vec<Vec<Card>> = Vec::new();
+            for _ in 0..opponents {
+                opponent_hands.push(self.deal_from_range(&mut deck));
+            }
+            let mut final_board = board.to_vec();
+            final_board.extend(deck.drain(..cards_to_come));
+
+            let mut own_cards = hole_cards.to_vec();
+            own_cards.extend(final_board.iter().cloned());
+            let own_value = PokerGame::evaluate_hand(&own_cards);
+            let mut tied_hands = 1;
+            let mut beaten = false;
+            for opponent_hand in opponent_hands.iter() {
+                let mut opponent_cards = opponent_hand.clone();
+                opponent_cards.extend(final_board.iter().cloned());
+                let opponent_value = PokerGame::evaluate_hand(&opponent_cards);
+                if opponent_value > own_value {
+                    beaten = true;
+                    break;
+                }
+                if opponent_value == own_value {
+                    tied_hands += 1;
+                }
+            }
+            if !beaten {
+                total_share += 1.0 / tied_hands as f32;
+            }
+        }
+        total_share / self.equity_settings.simulations as f32
+    }
+
+    // Two cards off the shuffled deck that fall inside the opponent range, when such a pair turns up
+    fn deal_from_range(&mut self, deck: &mut Vec<Card>) -> Vec<Card> {
+        for attempt in 0..Self::RANGE_ATTEMPTS {
+            let last_attempt = attempt + 1 == Self::RANGE_ATTEMPTS;
+            let hand = vec![deck[deck.len() - 1].clone(), deck[deck.len() - 2].clone()];
+            if last_attempt || RuleBasedAgent::get_chen_score(&hand) >= self.range_threshold {
+                deck.truncate(deck.len() - 2);
+                return hand;
+            }
+            deck.shuffle(&mut self.rng);
+        }
+        Vec::new()
+    }
+
+    // Whether a personality would not voluntarily play these hole cards before the flop
+    pub fn is_outside_preflop_range(&self, player_view: &PlayerView) -> bool {
+        match &self.personality_state {
+            Some(personality_state) if player_view.board.is_empty() => {
+                let (_, play_threshold) = personality_state.get_current().get_preflop_thresholds();
+                RuleBasedAgent::get_chen_score(&player_view.hole_cards) < play_threshold
+            },
+            _ => false,
+        }
+    }
+
+    // Chooses the action with the best expected value given the estimated equity
+    pub fn decide_by_value(&mut self, player_view: &PlayerView, equity: f32, opponents: usize) -> (PlayerAction, Option<u32>) {
+        let fair_share = 1.0 / (opponents as f32 + 1.0);
+        let strength = equity / fair_share;
+        let personality = self.personality_state.as_ref().map(|personality_state| personality_state.get_current());
+        let settings = &self.equity_settings;
+
+        // Without a personality there is no bluffing or extra aggression, and calls are made on value alone
+        let (bet_pot_fraction, aggressive, bluffing, looseness) = match &personality {
+            Some(personality) => (
+                personality.pick_bet_size(&mut self.rng),
+                self.rng.gen::<f32>() < personality.get_aggression_chance(),
+                self.rng.gen::<f32>() < personality.bluff_frequency,
+                personality.get_looseness(),
+            ),
+            None => (settings.bet_pot_fraction, false, false, 0.0),
+        };
+
+        if player_view.amount_to_call == 0 {
+            if strength >= settings.bet_strength || (strength >= 1.0 && aggressive) || bluffing {
+                return (PlayerAction::Bet, Some(RuleBasedAgent::get_bet_to(player_view, bet_pot_fraction)));
+            }
+            return (PlayerAction::Check, None);
+        }
+
+        let half_chance = personality.is_some() && self.rng.gen::<bool>();
+        if strength >= settings.raise_strength || (strength >= settings.bet_strength && aggressive && half_chance) || (bluffing && half_chance) {
+            return (PlayerAction::Raise, Some(RuleBasedAgent::get_raise_to(player_view, bet_pot_fraction)));
+        }
+        let amount_to_call = player_view.amount_to_call as f32;
+        // Implied odds only count while there are cards still to come
+        let implied_winnings = if player_view.board.len() < 5 { amount_to_call * settings.implied_odds } else { 0.0 };
+        let call_value = (equity + looseness) * (player_view.get_total_pot() as f32 + amount_to_call + implied_winnings) - amount_to_call;
+        if call_value > 0.0 {
+            (PlayerAction::Call, None)
+        } else {
+            (PlayerAction::Fold, None)
+        }
+    }
+}
+
+impl Default for EquityAgent {
+    fn default() -> Self {
+        EquityAgent::new(EquitySettings::default())
+    }
+}
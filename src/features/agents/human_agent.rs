@@ -0,0 +1,154 @@
+use crate::card::Card;
+use crate::constants::PlayerAction;
+use crate::features::{ActionResult, PlayerView};
+use std::io::{BufRead, BufReader, Write};
+
+
+
+// A person at the keyboard. Reads one command per line:
+// "c" to call (or check), "k" to check, "f" to fold, "b 40" to bet, "r 40" to raise to, "allin".
+pub struct HumanAgent {
+    input: Box<dyn BufRead + Send>,
+    output: Box<dyn Write + Send>
+}
+
+impl HumanAgent {
+    pub const AGENT_NAME: &'static str = "human";
+
+    pub fn new() -> Self {
+        HumanAgent::with_io(Box::new(BufReader::new(std::io::stdin())), Box::new(std::io::stdout()))
+    }
+
+    pub fn with_io(input: Box<dyn BufRead + Send>, output: Box<dyn Write + Send>) -> Self {
+        HumanAgent { input, output }
+    }
+
+    pub(crate) fn read_line(&mut self) -> Option<String> {
+        let mut line = String::new();
+        match self.input.read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(line),
+        }
+    }
+
+    pub(crate) fn write_text(&mut self, text: &str) {
+        // A closed terminal leaves nothing to report to
+        let _ = write!(self.output, "{}", text);
+        let _ = self.output.flush();
+    }
+
+    pub fn describe_view(player_view: &PlayerView) -> String {
+        let mut lines: Vec<String> = Vec::new();
+        lines.push(format!("--- Hand #{} {:?} ---", player_view.hand_number, player_view.hand_phase));
+        lines.push(format!("Your cards: {}", Self::describe_cards(&player_view.hole_cards)));
+        lines.push(format!("Board: {}", Self::describe_cards(&player_view.board)));
+        lines.push(format!("Pot: {}", player_view.get_total_pot()));
+        if let Some(time_left_ms) = player_view.time_left_ms {
+            lines.push(format!("Time left: {:.1}s, time bank {:.1}s", time_left_ms as f64 / 1000.0, player_view.time_bank_left_ms as f64 / 1000.0));
+        }
+        for seat_view in player_view.seats.iter() {
+            let mut markers: Vec<&str> = Vec::new();
+            if seat_view.seat == player_view.button_seat {
+                markers.push("button");
+            }
+            if seat_view.player_id == player_view.player_id {
+                markers.push("you");
+            }
+            if seat_view.folded {
+                markers.push("folded");
+            }
+            if seat_view.all_in {
+                markers.push("all in");
+            }
+            if seat_view.sitting_out {
+                markers.push("sitting out");
+            }
+            let markers = if markers.is_empty() { String::new() } else { format!(" ({})", markers.join(", ")) };
+            lines.push(format!("  Seat {} {}: {} chips, bet {}{}", seat_view.seat, seat_view.username, seat_view.coins, seat_view.current_bet, markers));
+        }
+        lines.push(String::from("Actions:"));
+        for action in player_view.legal_actions.iter() {
+            let description = match action {
+                PlayerAction::Check => String::from("  k      check"),
+                PlayerAction::Call => format!("  c      call {}", player_view.amount_to_call),
+                PlayerAction::Fold => String::from("  f      fold"),
+                PlayerAction::Bet => format!("  b <n>  bet {} to {}", player_view.minimum_bet, player_view.maximum_bet_to),
+                PlayerAction::Raise => format!("  r <n>  raise to {} to {}", player_view.minimum_raise_to, player_view.maximum_bet_to),
+            };
+            lines.push(description);
+        }
+        if player_view.is_legal(PlayerAction::Bet) || player_view.is_legal(PlayerAction::Raise) {
+            lines.push(format!("  allin  put in {} total", player_view.maximum_bet_to));
+        }
+        lines.join("\n") + "\n"
+    }
+
+    fn describe_cards(cards: &[Card]) -> String {
+        if cards.is_empty() {
+            return String::from("-");
+        }
+        cards.iter()
+            .map(|card| format!("{} of {}", card.get_rank(), card.get_suit()))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    // Turns one typed command into a legal action, or says why it cannot be played
+    pub fn parse_command(command: &str, player_view: &PlayerView) -> Result<ActionResult, String> {
+        let words: Vec<String> = command.split_whitespace().map(|word| word.to_lowercase()).collect();
+        let (verb, argument) = match words.as_slice() {
+            [verb] => (verb.as_str(), None),
+            [verb, argument] => (verb.as_str(), Some(argument.as_str())),
+            _ => return Err(String::from("Type one command, like \"c\", \"f\", \"r 40\" or \"allin\"")),
+        };
+
+        let action = match verb {
+            "c" | "call" if player_view.is_legal(PlayerAction::Call) => PlayerAction::Call,
+            "c" | "call" | "k" | "check" | "x" => PlayerAction::Check,
+            "f" | "fold" => PlayerAction::Fold,
+            "b" | "bet" => PlayerAction::Bet,
+            "r" | "raise" => PlayerAction::Raise,
+            "allin" | "all-in" => return Self::get_all_in_action(player_view),
+            _ => return Err(format!("Unknown command {:?}", verb)),
+        };
+        if !player_view.is_legal(action) {
+            return Err(format!("{:?} is not allowed right now", action));
+        }
+
+        match action {
+            PlayerAction::Bet | PlayerAction::Raise => {
+                let minimum = if action == PlayerAction::Bet { player_view.minimum_bet } else { player_view.minimum_raise_to };
+                let amount: u32 = argument
+                    .ok_or(format!("Say how much, like \"{} {}\"", verb, minimum))?
+                    .parse()
+                    .map_err(|_| String::from("The amount must be a whole number"))?;
+                if amount < minimum || amount > player_view.maximum_bet_to {
+                    return Err(format!("The amount must be between {} and {}", minimum, player_view.maximum_bet_to));
+                }
+                Ok(ActionResult { action, success: true, amount: Some(amount) })
+            },
+            _ if argument.is_some() => Err(format!("{:?} does not take an amount", action)),
+            _ => Ok(ActionResult { action, success: true, amount: None }),
+        }
+    }
+
+    // Everything in, as a bet or raise when one is open, otherwise as a call
+    fn get_all_in_action(player_view: &PlayerView) -> Result<ActionResult, String> {
+        let amount = Some(player_view.maximum_bet_to);
+        if player_view.is_legal(PlayerAction::Bet) {
+            Ok(ActionResult { action: PlayerAction::Bet, success: true, amount })
+        } else if player_view.is_legal(PlayerAction::Raise) {
+            Ok(ActionResult { action: PlayerAction::Raise, success: true, amount })
+        } else if player_view.is_legal(PlayerAction::Call) {
+            Ok(ActionResult { action: PlayerAction::Call, success: true, amount: None })
+        } else {
+            Err(String::from("There is nothing to put in right now"))
+        }
+    }
+}
+
+impl Default for HumanAgent {
+    fn default() -> Self {
+        HumanAgent::new()
+    }
+}
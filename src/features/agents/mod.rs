@@ -0,0 +1,23 @@
+mod player_agent;
+mod random_agent;
+mod human_agent;
+mod rule_based_agent;
+mod equity_agent;
+mod personality;
+mod cfr_agent;
+mod scripted_agent;
+mod subprocess_agent;
+mod acpc_agent;
+mod agent_registry;
+
+pub use player_agent::PlayerAgent;
+pub use random_agent::RandomAgent;
+pub use human_agent::HumanAgent;
+pub use rule_based_agent::RuleBasedAgent;
+pub use equity_agent::{EquityAgent, EquitySettings};
+pub use cfr_agent::CfrAgent;
+pub use scripted_agent::{ScriptedAgent, ScriptedStep};
+pub use subprocess_agent::{SubprocessAgent, BotRequest, BotReply};
+pub use acpc_agent::AcpcAgent;
+pub use personality::{Personality, PersonalityState, TiltSettings};
+pub use agent_registry::{AgentRegistry, AgentFactory};
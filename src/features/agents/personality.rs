@@ -0,0 +1,282 @@
+use crate::features::{PlayerView, GameError, GameEvent, RuleBasedAgent};
+use rand::Rng;
+use serde::{Serialize, Deserialize};
+use std::path::Path;
+
+
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TiltSettings {
+    // Losing at least this many big blinds in one hand sets off tilt, 0 never tilts
+    pub trigger_big_blinds: u32,
+    // Hands played on tilt before calming down
+    pub tilt_hands: u32,
+    // Added to vpip and pfr while tilted
+    pub vpip_increase: f32,
+    pub pfr_increase: f32,
+    // Aggression and bluffing are multiplied by this while tilted
+    pub aggression_multiplier: f32
+}
+
+impl Default for TiltSettings {
+    fn default() -> Self {
+        TiltSettings {
+            trigger_big_blinds: 0,
+            tilt_hands: 0,
+            vpip_increase: 0.0,
+            pfr_increase: 0.0,
+            aggression_multiplier: 1.0
+        }
+    }
+}
+
+// A playing style any built-in agent can take on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Personality {
+    pub name: String,
+    // Percent of hands played voluntarily before the flop
+    pub vpip: f32,
+    // Percent of hands raised before the flop
+    pub pfr: f32,
+    // Bets and raises for every call after the flop
+    pub aggression_factor: f32,
+    // Chance of betting or raising with a hand that is unlikely to win
+    pub bluff_frequency: f32,
+    // Bet and raise sizes as shares of the pot, one picked at random each time
+    pub bet_sizes: Vec<f32>,
+    pub tilt: TiltSettings
+}
+
+impl Default for Personality {
+    fn default() -> Self {
+        Personality {
+            name: String::from("balanced"),
+            vpip: 25.0,
+            pfr: 18.0,
+            aggression_factor: 2.0,
+            bluff_frequency: 0.1,
+            bet_sizes: vec![0.5, 0.75],
+            tilt: TiltSettings::default()
+        }
+    }
+}
+
+impl Personality {
+    pub const PRESET_NAMES: [&'static str; 5] = ["tight_passive", "loose_aggressive", "calling_station", "maniac", "nit"];
+
+    pub fn tight_passive() -> Self {
+        Personality {
+            name: String::from("tight_passive"),
+            vpip: 15.0,
+            pfr: 5.0,
+            aggression_factor: 0.8,
+            bluff_frequency: 0.02,
+            bet_sizes: vec![0.33, 0.5],
+            tilt: TiltSettings::default()
+        }
+    }
+
+    pub fn loose_aggressive() -> Self {
+        Personality {
+            name: String::from("loose_aggressive"),
+            vpip: 35.0,
+            pfr: 28.0,
+            aggression_factor: 3.5,
+            bluff_frequency: 0.25,
+            bet_sizes: vec![0.66, 1.0],
+            tilt: TiltSettings { trigger_big_blinds: 50, tilt_hands: 10, vpip_increase: 10.0, pfr_increase: 8.0, aggression_multiplier: 1.5 }
+        }
+    }
+
+    pub fn calling_station() -> Self {
+        Personality {
+            name: String::from("calling_station"),
+            vpip: 55.0,
+            pfr: 5.0,
+            aggression_factor: 0.4,
+            bluff_frequency: 0.02,
+            bet_sizes: vec![0.33, 0.5],
+            tilt: TiltSettings::default()
+        }
+    }
+
+    pub fn maniac() -> Self {
+        Personality {
+            name: String::from("maniac"),
+            vpip: 70.0,
+            pfr: 50.0,
+            aggression_factor: 6.0,
+            bluff_frequency: 0.45,
+            bet_sizes: vec![1.0, 1.5, 2.0],
+            tilt: TiltSettings { trigger_big_blinds: 30, tilt_hands: 20, vpip_increase: 15.0, pfr_increase: 15.0, aggression_multiplier: 2.0 }
+        }
+    }
+
+    pub fn nit() -> Self {
+        Personality {
+            name: String::from("nit"),
+            vpip: 9.0,
+            pfr: 7.0,
+            aggression_factor: 1.5,
+            bluff_frequency: 0.0,
+            bet_sizes: vec![0.5],
+            tilt: TiltSettings::default()
+        }
+    }
+
+    pub fn from_preset(preset_name: &str) -> Option<Self> {
+        match preset_name {
+            "tight_passive" => Some(Self::tight_passive()),
+            "loose_aggressive" => Some(Self::loose_aggressive()),
+            "calling_station" => Some(Self::calling_station()),
+            "maniac" => Some(Self::maniac()),
+            "nit" => Some(Self::nit()),
+            _ => None,
+        }
+    }
+
+    pub fn from_json_str(json: &str) -> Result<Self, GameError> {
+        let personality: Personality = serde_json::from_str(json)
+            .map_err(|error| GameError::InvalidConfig { reason: format!("personality: {}", error) })?;
+        personality.validate()?;
+        Ok(personality)
+    }
+
+    pub fn from_json_file<P: AsRef<Path>>(path: P) -> Result<Self, GameError> {
+        let json = std::fs::read_to_string(path.as_ref())
+            .map_err(|error| GameError::InvalidConfig { reason: format!("{}: {}", path.as_ref().display(), error) })?;
+        Self::from_json_str(&json)
+    }
+
+    // Reads the personality from a seat's agent params: "personality" names a preset or holds
+    // one inline, and "personality_file" points at a JSON file
+    pub fn from_params(agent_params: &serde_json::Value) -> Result<Option<Self>, GameError> {
+        if let Some(path) = agent_params.get("personality_file").and_then(|path| path.as_str()) {
+            return Self::from_json_file(path).map(Some);
+        }
+        match agent_params.get("personality") {
+            None | Some(serde_json::Value::Null) => Ok(None),
+            Some(serde_json::Value::String(preset_name)) => Self::from_preset(preset_name)
+                .map(Some)
+                .ok_or(GameError::InvalidConfig { reason: format!("no personality preset is called {:?}", preset_name) }),
+            Some(personality) => Self::from_json_str(&personality.to_string()).map(Some),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), GameError> {
+        let invalid = |reason: &str| Err(GameError::InvalidConfig { reason: format!("personality {}: {}", self.name, reason) });
+
+        if !(0.0..=100.0).contains(&self.vpip) || !(0.0..=100.0).contains(&self.pfr) {
+            return invalid("vpip and pfr are percentages between 0 and 100");
+        }
+        if self.pfr > self.vpip {
+            return invalid("pfr cannot be higher than vpip, since every raise is also a voluntary play");
+        }
+        if self.aggression_factor < 0.0 {
+            return invalid("aggression factor cannot be negative");
+        }
+        if !(0.0..=1.0).contains(&self.bluff_frequency) {
+            return invalid("bluff frequency is a chance between 0 and 1");
+        }
+        if self.bet_sizes.is_empty() || self.bet_sizes.iter().any(|&bet_size| bet_size <= 0.0) {
+            return invalid("at least one bet size above zero is needed");
+        }
+        if self.tilt.aggression_multiplier < 0.0 {
+            return invalid("tilt aggression multiplier cannot be negative");
+        }
+        Ok(())
+    }
+
+    // The same style pushed looser and wilder, as it plays while tilted
+    pub fn get_tilted(&self) -> Self {
+        let vpip = (self.vpip + self.tilt.vpip_increase).min(100.0);
+        Personality {
+            name: format!("{} (tilted)", self.name),
+            vpip,
+            pfr: (self.pfr + self.tilt.pfr_increase).min(vpip),
+            aggression_factor: self.aggression_factor * self.tilt.aggression_multiplier,
+            bluff_frequency: (self.bluff_frequency * self.tilt.aggression_multiplier).min(1.0),
+            bet_sizes: self.bet_sizes.clone(),
+            tilt: self.tilt.clone()
+        }
+    }
+
+    // How often a player who continues in the hand does so by betting or raising
+    pub fn get_aggression_chance(&self) -> f32 {
+        self.aggression_factor / (self.aggression_factor + 1.0)
+    }
+
+    // Equity a loose player pretends to have when deciding whether a call is worth it
+    pub fn get_looseness(&self) -> f32 {
+        ((self.vpip - 25.0) / 100.0).clamp(-0.15, 0.4)
+    }
+
+    // The weakest Chen scores still raised and still played, for this style's pfr and vpip
+    pub fn get_preflop_thresholds(&self) -> (i32, i32) {
+        (RuleBasedAgent::get_chen_threshold(self.pfr.max(0.1)), RuleBasedAgent::get_chen_threshold(self.vpip.max(0.1)))
+    }
+
+    pub fn pick_bet_size<R: Rng>(&self, rng: &mut R) -> f32 {
+        self.bet_sizes[rng.gen_range(0..self.bet_sizes.len())]
+    }
+}
+
+// A personality in play, which goes on tilt after a big loss and calms down after a few hands
+#[derive(Debug, Clone)]
+pub struct PersonalityState {
+    personality: Personality,
+    hand_number: Option<u32>,
+    tilt_hands_left: u32
+}
+
+impl PersonalityState {
+    pub fn new(personality: Personality) -> Self {
+        PersonalityState { personality, hand_number: None, tilt_hands_left: 0 }
+    }
+
+    pub fn get_personality(&self) -> &Personality {
+        &self.personality
+    }
+
+    pub fn is_tilted(&self) -> bool {
+        self.tilt_hands_left > 0
+    }
+
+    // Called with the final view of each hand, whether or not the player had to act in it.
+    // The stack the hand started with comes from its HandStarted event.
+    pub fn on_hand_end(&mut self, player_view: &PlayerView) {
+        if self.hand_number == Some(player_view.hand_number) {
+            return;
+        }
+        let start_stack = player_view.action_history.iter().find_map(|event| match event {
+            GameEvent::HandStarted { players, .. } => players.iter()
+                .find(|seat_summary| seat_summary.player_id == player_view.player_id)
+                .map(|seat_summary| seat_summary.coins),
+            _ => None,
+        });
+        // A hand sat out neither sets off tilt nor counts toward calming down
+        let start_stack = match start_stack {
+            Some(start_stack) => start_stack,
+            None => return,
+        };
+        self.hand_number = Some(player_view.hand_number);
+        let end_stack = player_view.get_own_seat().map_or(0, |seat_view| seat_view.coins);
+        let lost = start_stack.saturating_sub(end_stack);
+        let trigger = self.personality.tilt.trigger_big_blinds.saturating_mul(player_view.big_blind);
+        if trigger > 0 && lost >= trigger {
+            self.tilt_hands_left = self.personality.tilt.tilt_hands;
+        } else {
+            self.tilt_hands_left = self.tilt_hands_left.saturating_sub(1);
+        }
+    }
+
+    pub fn get_current(&self) -> Personality {
+        if self.is_tilted() {
+            self.personality.get_tilted()
+        } else {
+            self.personality.clone()
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use crate::features::{ActionResult, PlayerView};
+
+
+
+// Makes decisions for one seat. The view holds everything the seat may know,
+// including its legal actions, bet limits and time left. Agents are Send so that a table
+// with a time limit can wait for a decision on another thread and stop waiting in time.
+pub trait PlayerAgent: Send {
+    fn get_agent_name(&self) -> &str;
+    fn decide(&mut self, player_view: &PlayerView) -> ActionResult;
+
+    // Called for every seat once a hand is over, with the final view of it
+    fn on_hand_end(&mut self, _player_view: &PlayerView) {}
+
+    // Problems the agent ran into since it was last asked, like a bot that sent a bad reply.
+    // The table reports each one as an event.
+    fn take_problems(&mut self) -> Vec<String> {
+        Vec::new()
+    }
+}
@@ -0,0 +1,64 @@
+use crate::features::{PlayerView, Personality, PersonalityState, RuleBasedAgent};
+use rand::Rng;
+
+
+
+// Picks any legal action at random, with a random legal amount. A personality, when given,
+// weights the choices by its vpip, pfr and aggression, and sizes bets from its bet sizes.
+#[derive(Debug, Clone, Default)]
+pub struct RandomAgent {
+    personality_state: Option<PersonalityState>
+}
+
+impl RandomAgent {
+    pub const AGENT_NAME: &'static str = "random";
+
+    pub fn new() -> Self {
+        RandomAgent { personality_state: None }
+    }
+
+    pub fn with_personality(mut self, personality: Personality) -> Self {
+        self.personality_state = Some(PersonalityState::new(personality));
+        self
+    }
+
+    pub fn get_personality(&self) -> Option<&Personality> {
+        self.personality_state.as_ref().map(|personality_state| personality_state.get_personality())
+    }
+
+    pub fn observe_hand_end(&mut self, player_view: &PlayerView) {
+        if let Some(personality_state) = self.personality_state.as_mut() {
+            personality_state.on_hand_end(player_view);
+        }
+    }
+
+    pub fn get_current_personality(&self) -> Option<Personality> {
+        self.personality_state.as_ref().map(|personality_state| personality_state.get_current())
+    }
+
+    pub fn get_bet_amount(&self, player_view: &PlayerView) -> u32 {
+        if let Some(personality) = self.get_current_personality() {
+            return RuleBasedAgent::get_bet_to(player_view, personality.pick_bet_size(&mut rand::thread_rng()));
+        }
+        let min_bet = player_view.minimum_bet;
+        let max_bet = player_view.maximum_bet_to;
+        if min_bet >= max_bet {
+            max_bet
+        } else {
+            rand::thread_rng().gen_range(min_bet..=max_bet)
+        }
+    }
+
+    pub fn get_raise_amount(&self, player_view: &PlayerView) -> u32 {
+        if let Some(personality) = self.get_current_personality() {
+            return RuleBasedAgent::get_raise_to(player_view, personality.pick_bet_size(&mut rand::thread_rng()));
+        }
+        let min_raise = player_view.minimum_raise_to;
+        let max_raise = player_view.maximum_bet_to;
+        if min_raise >= max_raise {
+            max_raise
+        } else {
+            rand::thread_rng().gen_range(min_raise..=max_raise)
+        }
+    }
+}
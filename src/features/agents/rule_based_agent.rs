@@ -0,0 +1,374 @@
+use crate::card::Card;
+use crate::constants::{PlayerAction, TablePosition};
+use crate::features::{ActionResult, PlayerView, PokerRules, HandValue, TableConfig, Personality, PersonalityState};
+use crate::PokerGame;
+use rand::Rng;
+
+
+
+// A fixed-strategy baseline. Before the flop it plays hand groups by position, scored with
+// the Chen formula. After the flop it weighs made-hand strength and draws against the pot odds.
+// A personality, when given, sets how loose, aggressive and bluff-prone it plays.
+#[derive(Debug, Clone, Default)]
+pub struct RuleBasedAgent {
+    personality_state: Option<PersonalityState>
+}
+
+impl RuleBasedAgent {
+    pub const AGENT_NAME: &'static str = "rule_based";
+    // Hands this strong re-raise before the flop: big pairs and ace-king
+    const PREMIUM_CHEN_SCORE: i32 = 12;
+
+    pub fn new() -> Self {
+        RuleBasedAgent { personality_state: None }
+    }
+
+    pub fn with_personality(mut self, personality: Personality) -> Self {
+        self.personality_state = Some(PersonalityState::new(personality));
+        self
+    }
+
+    pub fn get_personality(&self) -> Option<&Personality> {
+        self.personality_state.as_ref().map(|personality_state| personality_state.get_personality())
+    }
+
+    pub fn observe_hand_end(&mut self, player_view: &PlayerView) {
+        if let Some(personality_state) = self.personality_state.as_mut() {
+            personality_state.on_hand_end(player_view);
+        }
+    }
+
+    fn get_current_personality(&self) -> Option<Personality> {
+        self.personality_state.as_ref().map(|personality_state| personality_state.get_current())
+    }
+
+    fn get_rank(card: &Card) -> u32 {
+        PokerGame::get_card_rank(card).unwrap_or_default()
+    }
+
+    // Bill Chen's preflop score, from -1 for the worst hands to 20 for aces
+    pub fn get_chen_score(hole_cards: &[Card]) -> i32 {
+        if hole_cards.len() != 2 {
+            return 0;
+        }
+        let first_rank = Self::get_rank(&hole_cards[0]);
+        let second_rank = Self::get_rank(&hole_cards[1]);
+        let (high_rank, low_rank) = (first_rank.max(second_rank), first_rank.min(second_rank));
+        let card_points = |rank: u32| -> f32 {
+            match rank {
+                14 => 10.0,
+                13 => 8.0,
+                12 => 7.0,
+                11 => 6.0,
+                _ => rank as f32 / 2.0,
+            }
+        };
+
+        let mut score = card_points(high_rank);
+        if high_rank == low_rank {
+            score = (score * 2.0).max(5.0);
+        } else {
+            if hole_cards[0].get_suit() == hole_cards[1].get_suit() {
+                score += 2.0;
+            }
+            let gap = high_rank - low_rank - 1;
+            score -= match gap {
+                0 => 0.0,
+                1 => 1.0,
+                2 => 2.0,
+                3 => 4.0,
+                _ => 5.0,
+            };
+            if gap <= 1 && high_rank < 12 {
+                score += 1.0;
+            }
+        }
+        score.ceil() as i32
+    }
+
+    pub fn get_position(player_view: &PlayerView) -> TablePosition {
+        if player_view.seat == player_view.small_blind_seat || player_view.seat == player_view.big_blind_seat {
+            return TablePosition::Blinds;
+        }
+        // Seats after the blinds in the order they act, ending on the button
+        let mut acting_seats: Vec<usize> = player_view.seats.iter()
+            .filter(|seat_view| !seat_view.sitting_out)
+            .map(|seat_view| seat_view.seat)
+            .filter(|&seat| seat != player_view.small_blind_seat && seat != player_view.big_blind_seat)
+            .collect();
+        acting_seats.sort_by_key(|&seat| (seat + TableConfig::SEAT_LIMIT - player_view.button_seat - 1) % TableConfig::SEAT_LIMIT);
+        let seats_behind = acting_seats.iter()
+            .position(|&seat| seat == player_view.seat)
+            .map_or(0, |position| acting_seats.len() - position - 1);
+        match seats_behind {
+            0 | 1 => TablePosition::Late,
+            2 | 3 => TablePosition::Middle,
+            _ => TablePosition::Early,
+        }
+    }
+
+    // The weakest Chen score among the given top percent of all starting hands
+    pub fn get_chen_threshold(range_percent: f32) -> i32 {
+        let deck = Card::get_standard_deck();
+        let mut chen_scores: Vec<i32> = Vec::new();
+        for first in 0..deck.len() {
+            for second in first + 1..deck.len() {
+                chen_scores.push(Self::get_chen_score(&[deck[first].clone(), deck[second].clone()]));
+            }
+        }
+        chen_scores.sort_by(|first, second| second.cmp(first));
+        let in_range = ((chen_scores.len() as f32 * range_percent / 100.0).ceil() as usize).clamp(1, chen_scores.len());
+        chen_scores[in_range - 1]
+    }
+
+    // The weakest Chen score worth opening with from each position
+    pub fn get_open_threshold(position: TablePosition) -> i32 {
+        match position {
+            TablePosition::Early => 9,
+            TablePosition::Middle => 8,
+            TablePosition::Late => 6,
+            TablePosition::Blinds => 7,
+        }
+    }
+
+    // Cards left in the deck that complete a flush or straight the hole cards are part of
+    pub fn count_outs(hole_cards: &[Card], board: &[Card]) -> u32 {
+        if board.len() < 3 || board.len() >= 5 {
+            return 0;
+        }
+        let mut cards = hole_cards.to_vec();
+        cards.extend(board.iter().cloned());
+        let made_hand = PokerGame::evaluate_hand(&cards);
+        let mut outs = 0;
+
+        let flush_suit = hole_cards.iter()
+            .map(|card| card.get_suit())
+            .find(|suit| cards.iter().filter(|card| card.get_suit() == *suit).count() == 4);
+        if flush_suit.is_some() && made_hand.category < HandValue::FLUSH {
+            outs += 9;
+        }
+
+        if made_hand.category < HandValue::STRAIGHT {
+            let mut ranks: Vec<u32> = cards.iter().map(Self::get_rank).collect();
+            if ranks.contains(&14) {
+                ranks.push(1);
+            }
+            let hole_ranks: Vec<u32> = hole_cards.iter().map(Self::get_rank).collect();
+            let mut completing_ranks: Vec<u32> = (1..=14)
+                .filter(|rank| !ranks.contains(rank))
+                .filter(|&rank| {
+                    (rank.saturating_sub(4).max(1)..=rank.min(10)).any(|low| {
+                        let window: Vec<u32> = (low..low + 5).collect();
+                        let uses_hole_card = window.iter().any(|window_rank| {
+                            hole_ranks.contains(window_rank) || (*window_rank == 1 && hole_ranks.contains(&14))
+                        });
+                        uses_hole_card && window.iter().all(|window_rank| *window_rank == rank || ranks.contains(window_rank))
+                    })
+                })
+                // A low ace and a high ace are the same card
+                .map(|rank| if rank == 1 { 14 } else { rank })
+                .collect();
+            completing_ranks.sort();
+            completing_ranks.dedup();
+            // A straight card of the flush suit was already counted
+            let straight_outs = completing_ranks.len() as u32 * 4;
+            outs += if flush_suit.is_some() { straight_outs.saturating_sub(completing_ranks.len() as u32) } else { straight_outs };
+        }
+        outs
+    }
+
+    // 0 for nothing, 1 for a weak pair, 2 for top pair with a good kicker or an overpair,
+    // 3 for two pairs or better. Only hands the hole cards improve count.
+    pub fn get_made_hand_tier(hole_cards: &[Card], board: &[Card]) -> u8 {
+        let mut cards = hole_cards.to_vec();
+        cards.extend(board.iter().cloned());
+        let made_hand = PokerGame::evaluate_hand(&cards);
+        let board_hand = PokerGame::evaluate_hand(board);
+        if made_hand.category <= board_hand.category && made_hand.category != HandValue::HIGH_CARD {
+            return 0;
+        }
+
+        match made_hand.category {
+            HandValue::HIGH_CARD => 0,
+            HandValue::ONE_PAIR => {
+                let pair_rank = made_hand.ranks[0];
+                let top_board_rank = board.iter().map(Self::get_rank).max().unwrap_or_default();
+                let best_kicker = hole_cards.iter().map(Self::get_rank).filter(|&rank| rank != pair_rank).max().unwrap_or(pair_rank);
+                if pair_rank > top_board_rank || (pair_rank == top_board_rank && best_kicker >= 11) {
+                    2
+                } else {
+                    1
+                }
+            },
+            _ => 3,
+        }
+    }
+
+    // A rough chance of winning at showdown, from the made hand or the draw, whichever is better
+    pub fn estimate_equity(hole_cards: &[Card], board: &[Card]) -> f32 {
+        let made_hand_equity: f32 = match Self::get_made_hand_tier(hole_cards, board) {
+            3 => 0.85,
+            2 => 0.7,
+            1 => 0.45,
+            _ => 0.1,
+        };
+        // The rule of four and two: each out is worth about 4% with two cards to come, 2% with one
+        let cards_to_come = if board.len() == 3 { 4.0 } else { 2.0 };
+        let draw_equity = (Self::count_outs(hole_cards, board) as f32 * cards_to_come / 100.0).min(0.6);
+        made_hand_equity.max(draw_equity)
+    }
+
+    pub fn get_pot_odds(player_view: &PlayerView) -> f32 {
+        let amount_to_call = player_view.amount_to_call as f32;
+        if amount_to_call == 0.0 {
+            return 0.0;
+        }
+        amount_to_call / (player_view.get_total_pot() as f32 + amount_to_call)
+    }
+
+    // A bet of the given share of the pot, as a street total within the legal limits
+    pub fn get_bet_to(player_view: &PlayerView, pot_fraction: f32) -> u32 {
+        let bet_size = (player_view.get_total_pot() as f32 * pot_fraction).round() as u32;
+        bet_size.clamp(player_view.minimum_bet.min(player_view.maximum_bet_to), player_view.maximum_bet_to)
+    }
+
+    // A raise of the given share of the pot after calling, as a street total within the legal limits
+    pub fn get_raise_to(player_view: &PlayerView, pot_fraction: f32) -> u32 {
+        let pot_after_call = (player_view.get_total_pot() + player_view.amount_to_call) as f32;
+        let raise_to = player_view.highest_bet + (pot_after_call * pot_fraction).round() as u32;
+        raise_to.clamp(player_view.minimum_raise_to.min(player_view.maximum_bet_to), player_view.maximum_bet_to)
+    }
+
+    // The weakest Chen scores that open with a raise, limp in, call a raise and re-raise
+    fn get_preflop_thresholds(&self, position: TablePosition) -> (i32, i32, i32, i32) {
+        let open_threshold = Self::get_open_threshold(position);
+        match self.get_current_personality() {
+            Some(personality) => {
+                // The personality sets the ranges for a middle seat, and position shifts them like the default chart
+                let position_shift = open_threshold - Self::get_open_threshold(TablePosition::Middle);
+                let (raise_threshold, play_threshold) = personality.get_preflop_thresholds();
+                let raise_threshold = raise_threshold + position_shift;
+                let play_threshold = play_threshold + position_shift;
+                (raise_threshold, play_threshold, play_threshold, (raise_threshold + 3).min(Self::PREMIUM_CHEN_SCORE))
+            },
+            None => {
+                let limp_threshold = match position {
+                    TablePosition::Late | TablePosition::Blinds => open_threshold - 2,
+                    _ => open_threshold,
+                };
+                (open_threshold, limp_threshold, open_threshold + 1, Self::PREMIUM_CHEN_SCORE)
+            },
+        }
+    }
+
+    pub fn decide_preflop(&self, player_view: &PlayerView) -> (PlayerAction, Option<u32>) {
+        let chen_score = Self::get_chen_score(&player_view.hole_cards);
+        let position = Self::get_position(player_view);
+        let (raise_threshold, limp_threshold, call_threshold, reraise_threshold) = self.get_preflop_thresholds(position);
+        let big_blind = player_view.big_blind.max(1);
+
+        if player_view.highest_bet <= big_blind {
+            if chen_score >= raise_threshold {
+                // Three big blinds, plus one for every player who limped in
+                let limpers = player_view.seats.iter()
+                    .filter(|seat_view| seat_view.seat != player_view.big_blind_seat && seat_view.current_bet >= big_blind)
+                    .count() as u32;
+                let open_to = big_blind * (3 + limpers);
+                return (PlayerAction::Raise, Some(open_to.clamp(player_view.minimum_raise_to.min(player_view.maximum_bet_to), player_view.maximum_bet_to)));
+            }
+            if chen_score >= limp_threshold {
+                return (PlayerAction::Call, None);
+            }
+            return (PlayerAction::Fold, None);
+        }
+
+        if chen_score >= reraise_threshold {
+            let reraise_to = (player_view.highest_bet * 3).clamp(player_view.minimum_raise_to.min(player_view.maximum_bet_to), player_view.maximum_bet_to);
+            return (PlayerAction::Raise, Some(reraise_to));
+        }
+        // Calling a raise needs a hand a little better than one worth opening, and a price
+        // that leaves room to play the hand. Loose players pay more.
+        let own_stack = player_view.get_own_seat().map_or(0, |seat_view| seat_view.coins);
+        let looseness = self.get_current_personality().map_or(0.0, |personality| personality.get_looseness());
+        let call_share_limit = (0.1 + looseness.max(0.0)) * own_stack as f32;
+        let affordable_call = player_view.amount_to_call as f32 <= call_share_limit;
+        if chen_score >= call_threshold && affordable_call {
+            return (PlayerAction::Call, None);
+        }
+        (PlayerAction::Fold, None)
+    }
+
+    pub fn decide_postflop(&self, player_view: &PlayerView) -> (PlayerAction, Option<u32>) {
+        let hole_cards = &player_view.hole_cards;
+        let board = &player_view.board;
+        let made_hand_tier = Self::get_made_hand_tier(hole_cards, board);
+        let outs = Self::count_outs(hole_cards, board);
+        let equity = Self::estimate_equity(hole_cards, board);
+
+        let personality = match self.get_current_personality() {
+            Some(personality) => personality,
+            None => {
+                if player_view.amount_to_call == 0 {
+                    return match made_hand_tier {
+                        3 => (PlayerAction::Bet, Some(Self::get_bet_to(player_view, 0.75))),
+                        2 => (PlayerAction::Bet, Some(Self::get_bet_to(player_view, 0.5))),
+                        // Strong draws bet as a semi-bluff
+                        _ if outs >= 8 => (PlayerAction::Bet, Some(Self::get_bet_to(player_view, 0.5))),
+                        _ => (PlayerAction::Check, None),
+                    };
+                }
+
+                if made_hand_tier == 3 {
+                    return (PlayerAction::Raise, Some(Self::get_raise_to(player_view, 0.75)));
+                }
+                return if equity >= Self::get_pot_odds(player_view) {
+                    (PlayerAction::Call, None)
+                } else {
+                    (PlayerAction::Fold, None)
+                };
+            },
+        };
+
+        let mut rng = rand::thread_rng();
+        let bet_size = personality.pick_bet_size(&mut rng);
+        let aggressive = rng.gen::<f32>() < personality.get_aggression_chance();
+        let bluffing = rng.gen::<f32>() < personality.bluff_frequency;
+        let has_something = made_hand_tier >= 2 || outs >= 8;
+
+        if player_view.amount_to_call == 0 {
+            let betting = made_hand_tier == 3 || (has_something && aggressive) || bluffing;
+            return if betting {
+                (PlayerAction::Bet, Some(Self::get_bet_to(player_view, bet_size)))
+            } else {
+                (PlayerAction::Check, None)
+            };
+        }
+
+        if made_hand_tier == 3 || (has_something && aggressive && rng.gen::<bool>()) || (bluffing && rng.gen::<bool>()) {
+            return (PlayerAction::Raise, Some(Self::get_raise_to(player_view, bet_size)));
+        }
+        if equity + personality.get_looseness() >= Self::get_pot_odds(player_view) {
+            (PlayerAction::Call, None)
+        } else {
+            (PlayerAction::Fold, None)
+        }
+    }
+
+    // Falls back to the nearest legal action: a raise becomes a call, a call or bet becomes
+    // a check, and a fold becomes a check when checking is free
+    pub fn make_legal(player_view: &PlayerView, action: PlayerAction, amount: Option<u32>) -> ActionResult {
+        let fallbacks: &[PlayerAction] = match action {
+            PlayerAction::Raise => &[PlayerAction::Raise, PlayerAction::Bet, PlayerAction::Call, PlayerAction::Check],
+            PlayerAction::Bet => &[PlayerAction::Bet, PlayerAction::Raise, PlayerAction::Check, PlayerAction::Call],
+            PlayerAction::Call => &[PlayerAction::Call, PlayerAction::Check],
+            PlayerAction::Check => &[PlayerAction::Check, PlayerAction::Fold],
+            PlayerAction::Fold => &[PlayerAction::Check, PlayerAction::Fold],
+        };
+        match fallbacks.iter().find(|&&fallback| player_view.is_legal(fallback)) {
+            Some(&PlayerAction::Bet) => ActionResult { action: PlayerAction::Bet, success: true, amount: Some(amount.unwrap_or(player_view.minimum_bet).max(player_view.minimum_bet).min(player_view.maximum_bet_to)) },
+            Some(&PlayerAction::Raise) => ActionResult { action: PlayerAction::Raise, success: true, amount: Some(amount.unwrap_or(player_view.minimum_raise_to).max(player_view.minimum_raise_to).min(player_view.maximum_bet_to)) },
+            Some(&fallback) => ActionResult { action: fallback, success: true, amount: None },
+            None => ActionResult { action: PlayerAction::Fold, success: false, amount: None },
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use crate::constants::PlayerAction;
+use crate::features::{PlayerView, GameError};
+
+
+
+// One planned action, written like "call", "bet 20" or "raise to 60". Legal actions listed
+// in brackets after it, as in "raise to 60 [fold call raise]", must be exactly what is offered.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptedStep {
+    pub text: String,
+    pub action: PlayerAction,
+    pub amount: Option<u32>,
+    pub expected_legal_actions: Option<Vec<PlayerAction>>
+}
+
+impl ScriptedStep {
+    pub fn parse(text: &str) -> Result<Self, GameError> {
+        let invalid = |reason: &str| GameError::InvalidConfig { reason: format!("script step {:?}: {}", text, reason) };
+
+        let (action_text, expected_text) = match text.split_once('[') {
+            Some((action_text, expected_text)) => {
+                let expected_text = expected_text.trim().strip_suffix(']').ok_or(invalid("the legal action list needs a closing ]"))?;
+                (action_text, Some(expected_text))
+            },
+            None => (text, None),
+        };
+        let expected_legal_actions = match expected_text {
+            Some(expected_text) => Some(expected_text
+                .split(|character: char| character == ',' || character.is_whitespace())
+                .filter(|word| !word.is_empty())
+                .map(|word| Self::parse_action(word).ok_or(invalid(&format!("{:?} is not an action", word))))
+                .collect::<Result<Vec<PlayerAction>, GameError>>()?),
+            None => None,
+        };
+
+        let words: Vec<String> = action_text.split_whitespace().map(|word| word.to_lowercase()).collect();
+        let words: Vec<&str> = words.iter().map(|word| word.as_str()).filter(|word| *word != "to").collect();
+        let (action, amount) = match words.as_slice() {
+            [verb] => (Self::parse_action(verb).ok_or(invalid("unknown action"))?, None),
+            [verb, amount] => {
+                let amount: u32 = amount.parse().map_err(|_| invalid("the amount must be a whole number"))?;
+                (Self::parse_action(verb).ok_or(invalid("unknown action"))?, Some(amount))
+            },
+            _ => return Err(invalid("write one action, like \"call\" or \"raise to 60\"")),
+        };
+        match (action, amount) {
+            (PlayerAction::Bet | PlayerAction::Raise, None) => Err(invalid("bets and raises need an amount")),
+            (PlayerAction::Check | PlayerAction::Call | PlayerAction::Fold, Some(_)) => Err(invalid("only bets and raises take an amount")),
+            _ => Ok(ScriptedStep { text: String::from(text.trim()), action, amount, expected_legal_actions }),
+        }
+    }
+
+    fn parse_action(word: &str) -> Option<PlayerAction> {
+        match word.to_lowercase().as_str() {
+            "fold" => Some(PlayerAction::Fold),
+            "check" => Some(PlayerAction::Check),
+            "call" => Some(PlayerAction::Call),
+            "bet" => Some(PlayerAction::Bet),
+            "raise" => Some(PlayerAction::Raise),
+            _ => None,
+        }
+    }
+}
+
+// Plays a fixed list of steps, for scenario tests with a stacked deck. It panics as soon as
+// the game disagrees with the script: the step's action is not legal, the legal actions are
+// not the ones the step lists, or the script has run out.
+#[derive(Debug, Clone)]
+pub struct ScriptedAgent {
+    steps: Vec<ScriptedStep>,
+    next_step: usize
+}
+
+impl ScriptedAgent {
+    pub const AGENT_NAME: &'static str = "scripted";
+
+    pub fn new(steps: Vec<ScriptedStep>) -> Self {
+        ScriptedAgent { steps, next_step: 0 }
+    }
+
+    pub fn from_script(script: &[&str]) -> Result<Self, GameError> {
+        let steps = script.iter().map(|text| ScriptedStep::parse(text)).collect::<Result<Vec<ScriptedStep>, GameError>>()?;
+        Ok(ScriptedAgent::new(steps))
+    }
+
+    // Reads "script", a list of steps, from a seat's agent params
+    pub fn from_params(agent_params: &serde_json::Value) -> Result<Self, GameError> {
+        let script = agent_params.get("script")
+            .and_then(|script| script.as_array())
+            .ok_or(GameError::InvalidConfig { reason: String::from("scripted agent needs a script, a list of steps like \"call\"") })?;
+        let steps = script.iter()
+            .map(|step| match step.as_str() {
+                Some(text) => ScriptedStep::parse(text),
+                None => Err(GameError::InvalidConfig { reason: format!("script step {} is not a string", step) }),
+            })
+            .collect::<Result<Vec<ScriptedStep>, GameError>>()?;
+        Ok(ScriptedAgent::new(steps))
+    }
+
+    pub fn get_steps(&self) -> &Vec<ScriptedStep> {
+        &self.steps
+    }
+
+    pub fn get_steps_left(&self) -> usize {
+        self.steps.len() - self.next_step
+    }
+
+    pub(crate) fn take_step(&mut self, player_view: &PlayerView) -> ScriptedStep {
+        let step_number = self.next_step + 1;
+        let situation = format!("player {} in hand {} at {:?}", player_view.player_id, player_view.hand_number, player_view.hand_phase);
+        let step = match self.steps.get(self.next_step) {
+            Some(step) => step.clone(),
+            None => panic!("Script ran out: {} was asked to act after all {} steps, offered {:?}", situation, self.steps.len(), player_view.legal_actions),
+        };
+        if let Some(expected_legal_actions) = &step.expected_legal_actions {
+            let same_actions = expected_legal_actions.len() == player_view.legal_actions.len()
+                && expected_legal_actions.iter().all(|action| player_view.is_legal(*action));
+            if !same_actions {
+                panic!("Script step {} {:?} for {} expected legal actions {:?}, offered {:?}", step_number, step.text, situation, expected_legal_actions, player_view.legal_actions);
+            }
+        }
+        if !player_view.is_legal(step.action) {
+            panic!("Script step {} {:?} for {} is not legal, offered {:?}", step_number, step.text, situation, player_view.legal_actions);
+        }
+        self.next_step += 1;
+        step
+    }
+}
@@ -0,0 +1,246 @@
+use crate::constants::PlayerAction;
+use crate::features::{PlayerView, ActionResult, GameError};
+use serde::{Serialize, Deserialize};
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+
+
+// Sent to the bot as one line of JSON each time it is its turn
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BotRequest {
+    pub request_id: u64,
+    pub view: PlayerView
+}
+
+// The bot's answer, one line of JSON like {"action": "raise", "amount": 60}. Amounts are
+// street totals, as in the view. A request_id, when given, must match the request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BotReply {
+    #[serde(default)]
+    pub request_id: Option<u64>,
+    pub action: String,
+    #[serde(default)]
+    pub amount: Option<u32>
+}
+
+// A bot in its own process, written in any language, that talks newline-delimited JSON over
+// stdin and stdout. Bots get the time the table's limits leave them, shown in the view.
+// Slow, crashed and confused bots fold and the problem is reported to the table.
+pub struct SubprocessAgent {
+    command: String,
+    args: Vec<String>,
+    child: Child,
+    input: Option<ChildStdin>,
+    replies: Receiver<String>,
+    next_request_id: u64,
+    crashed: bool,
+    problems: Vec<String>,
+    reported_problem_count: usize
+}
+
+impl SubprocessAgent {
+    pub const AGENT_NAME: &'static str = "subprocess";
+
+    pub fn new(command: &str, args: Vec<String>) -> Result<Self, GameError> {
+        let mut child = Command::new(command)
+            .args(args.iter())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|error| GameError::InvalidConfig { reason: format!("could not start bot {:?}: {}", command, error) })?;
+        let input = child.stdin.take();
+        let output = child.stdout.take()
+            .ok_or(GameError::InvalidConfig { reason: format!("bot {:?} has no stdout to read", command) })?;
+
+        // Lines are read on their own thread so a silent bot can be timed out
+        let (reply_sender, replies) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(output).lines() {
+                match line {
+                    Ok(line) => if reply_sender.send(line).is_err() { break },
+                    Err(_) => break,
+                }
+            }
+        });
+
+        Ok(SubprocessAgent {
+            command: String::from(command),
+            args,
+            child,
+            input,
+            replies,
+            next_request_id: 1,
+            crashed: false,
+            problems: Vec::new(),
+            reported_problem_count: 0
+        })
+    }
+
+    // Reads "command" and optional "args" from a seat's agent params
+    pub fn from_params(agent_params: &serde_json::Value) -> Result<Self, GameError> {
+        let command = agent_params.get("command")
+            .and_then(|command| command.as_str())
+            .ok_or(GameError::InvalidConfig { reason: String::from("subprocess agent needs a command to run") })?;
+        let args = match agent_params.get("args") {
+            None | Some(serde_json::Value::Null) => Vec::new(),
+            Some(args) => serde_json::from_value::<Vec<String>>(args.clone())
+                .map_err(|_| GameError::InvalidConfig { reason: String::from("subprocess agent args must be a list of strings") })?,
+        };
+        SubprocessAgent::new(command, args)
+    }
+
+    pub fn get_command_line(&self) -> String {
+        std::iter::once(&self.command).chain(self.args.iter()).cloned().collect::<Vec<String>>().join(" ")
+    }
+
+    pub fn get_problems(&self) -> &Vec<String> {
+        &self.problems
+    }
+
+    // The problems logged since this was last called
+    pub(crate) fn take_new_problems(&mut self) -> Vec<String> {
+        let new_problems = self.problems[self.reported_problem_count..].to_vec();
+        self.reported_problem_count = self.problems.len();
+        new_problems
+    }
+
+    pub fn has_crashed(&self) -> bool {
+        self.crashed
+    }
+
+    pub(crate) fn close_input(&mut self) {
+        self.input = None;
+    }
+
+    pub(crate) fn stop_process(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+
+    fn log_problem(&mut self, player_view: &PlayerView, problem: String) {
+        let problem = format!("Bot {:?} for player {} in hand {}: {}", self.get_command_line(), player_view.player_id, player_view.hand_number, problem);
+        self.problems.push(problem);
+    }
+
+    fn mark_crashed(&mut self, player_view: &PlayerView, problem: String) {
+        if !self.crashed {
+            self.crashed = true;
+            self.log_problem(player_view, format!("{}, folding its hands from now on", problem));
+        }
+    }
+
+    // Folds, or checks when folding is not allowed
+    pub fn get_fold_action(player_view: &PlayerView) -> ActionResult {
+        let action = [PlayerAction::Fold, PlayerAction::Check, PlayerAction::Call].into_iter()
+            .find(|&action| player_view.is_legal(action))
+            .unwrap_or(PlayerAction::Fold);
+        ActionResult { action, success: true, amount: None }
+    }
+
+    // Waits for the next line until the deadline, or for as long as it takes without one
+    pub(crate) fn receive_line(lines: &Receiver<String>, deadline: Option<Instant>) -> Result<String, RecvTimeoutError> {
+        match deadline {
+            Some(deadline) => lines.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+            None => lines.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        }
+    }
+
+    // Sends the view and waits for the bot's reply, folding on any problem
+    pub(crate) fn request_action(&mut self, player_view: &PlayerView) -> ActionResult {
+        if !self.crashed {
+            if let Ok(Some(exit_status)) = self.child.try_wait() {
+                self.mark_crashed(player_view, format!("exited with {}", exit_status));
+            }
+        }
+        if self.crashed {
+            return Self::get_fold_action(player_view);
+        }
+
+        // Replies that came in too late for earlier requests are thrown away
+        while self.replies.try_recv().is_ok() {}
+        let request_id = self.next_request_id;
+        self.next_request_id += 1;
+        let request = BotRequest { request_id, view: player_view.clone() };
+        let request_line = match serde_json::to_string(&request) {
+            Ok(request_line) => request_line,
+            Err(error) => {
+                self.log_problem(player_view, format!("the view could not be written as JSON: {}", error));
+                return Self::get_fold_action(player_view);
+            },
+        };
+        let sent = match self.input.as_mut() {
+            Some(input) => writeln!(input, "{}", request_line).and_then(|_| input.flush()),
+            None => Err(std::io::Error::from(std::io::ErrorKind::BrokenPipe)),
+        };
+        if let Err(error) = sent {
+            self.mark_crashed(player_view, format!("stopped reading its input ({})", error));
+            return Self::get_fold_action(player_view);
+        }
+
+        let deadline = player_view.time_left_ms.and_then(|time_left_ms| Instant::now().checked_add(Duration::from_millis(time_left_ms)));
+        loop {
+            match Self::receive_line(&self.replies, deadline) {
+                Ok(reply_line) => {
+                    if reply_line.trim().is_empty() {
+                        continue;
+                    }
+                    let reply: BotReply = match serde_json::from_str(&reply_line) {
+                        Ok(reply) => reply,
+                        Err(error) => {
+                            self.log_problem(player_view, format!("sent a malformed reply {:?} ({})", reply_line, error));
+                            return Self::get_fold_action(player_view);
+                        },
+                    };
+                    if reply.request_id.is_some_and(|reply_id| reply_id != request_id) {
+                        continue;
+                    }
+                    return match Self::check_reply(&reply, player_view) {
+                        Ok(action_result) => action_result,
+                        Err(problem) => {
+                            self.log_problem(player_view, problem);
+                            Self::get_fold_action(player_view)
+                        },
+                    };
+                },
+                Err(RecvTimeoutError::Timeout) => {
+                    self.log_problem(player_view, format!("did not reply within {}ms", player_view.time_left_ms.unwrap_or_default()));
+                    return Self::get_fold_action(player_view);
+                },
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.mark_crashed(player_view, String::from("closed its output"));
+                    return Self::get_fold_action(player_view);
+                },
+            }
+        }
+    }
+
+    // Turns a reply into a legal action, or says what is wrong with it
+    pub fn check_reply(reply: &BotReply, player_view: &PlayerView) -> Result<ActionResult, String> {
+        let action = match reply.action.to_lowercase().as_str() {
+            "fold" => PlayerAction::Fold,
+            "check" => PlayerAction::Check,
+            "call" => PlayerAction::Call,
+            "bet" => PlayerAction::Bet,
+            "raise" => PlayerAction::Raise,
+            _ => return Err(format!("replied with unknown action {:?}", reply.action)),
+        };
+        if !player_view.is_legal(action) {
+            return Err(format!("replied {:?}, which is not legal here, offered {:?}", action, player_view.legal_actions));
+        }
+        match action {
+            PlayerAction::Bet | PlayerAction::Raise => {
+                let minimum = if action == PlayerAction::Bet { player_view.minimum_bet } else { player_view.minimum_raise_to };
+                let amount = reply.amount.ok_or(format!("replied {:?} without an amount", action))?;
+                if amount < minimum || amount > player_view.maximum_bet_to {
+                    return Err(format!("replied {:?} to {}, outside {} to {}", action, amount, minimum, player_view.maximum_bet_to));
+                }
+                Ok(ActionResult { action, success: true, amount: Some(amount) })
+            },
+            _ => Ok(ActionResult { action, success: true, amount: None }),
+        }
+    }
+}
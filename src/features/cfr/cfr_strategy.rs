@@ -0,0 +1,51 @@
+use crate::constants::CfrVariant;
+use crate::features::GameError;
+use rand::Rng;
+use serde::{Serialize, Deserialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+
+
+// The average strategy a trainer learned, as action probabilities for each information set
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CfrStrategy {
+    pub game_name: String,
+    pub variant: CfrVariant,
+    pub iterations: u64,
+    pub info_sets: BTreeMap<String, Vec<(String, f64)>>
+}
+
+impl CfrStrategy {
+    pub fn get_action_probabilities(&self, info_set_key: &str) -> Option<&Vec<(String, f64)>> {
+        self.info_sets.get(info_set_key)
+    }
+
+    pub fn sample_action<R: Rng>(&self, info_set_key: &str, rng: &mut R) -> Option<String> {
+        let action_probabilities = self.get_action_probabilities(info_set_key)?;
+        let mut remaining: f64 = rng.gen();
+        for (action, probability) in action_probabilities.iter() {
+            remaining -= probability;
+            if remaining < 0.0 {
+                return Some(action.clone());
+            }
+        }
+        action_probabilities.last().map(|(action, _)| action.clone())
+    }
+
+    pub fn to_json_file<P: AsRef<Path>>(&self, path: P) -> Result<(), GameError> {
+        let json = serde_json::to_string_pretty(self).map_err(|error| GameError::StrategyNotSaved { reason: error.to_string() })?;
+        std::fs::write(path.as_ref(), json)
+            .map_err(|error| GameError::StrategyNotSaved { reason: format!("{}: {}", path.as_ref().display(), error) })
+    }
+
+    pub fn from_json_str(json: &str) -> Result<Self, GameError> {
+        serde_json::from_str(json).map_err(|error| GameError::InvalidStrategy { reason: error.to_string() })
+    }
+
+    pub fn from_json_file<P: AsRef<Path>>(path: P) -> Result<Self, GameError> {
+        let json = std::fs::read_to_string(path.as_ref())
+            .map_err(|error| GameError::InvalidStrategy { reason: format!("{}: {}", path.as_ref().display(), error) })?;
+        Self::from_json_str(&json)
+    }
+}
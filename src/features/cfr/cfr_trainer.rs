@@ -0,0 +1,393 @@
+use crate::constants::CfrVariant;
+use crate::features::{ExtensiveGame, CfrStrategy, GameError};
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use serde::{Serialize, Deserialize};
+use std::collections::{BTreeMap, HashMap};
+
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfoSetNode {
+    pub actions: Vec<String>,
+    pub regret_sum: Vec<f64>,
+    pub strategy_sum: Vec<f64>
+}
+
+impl InfoSetNode {
+    pub fn new(actions: Vec<String>) -> Self {
+        let action_count = actions.len();
+        InfoSetNode { actions, regret_sum: vec![0.0; action_count], strategy_sum: vec![0.0; action_count] }
+    }
+
+    // Regret matching: play each action in proportion to its positive regret
+    pub fn get_current_strategy(&self) -> Vec<f64> {
+        Self::normalize(self.regret_sum.iter().map(|regret| regret.max(0.0)).collect())
+    }
+
+    // The average over all iterations, which is what converges to equilibrium
+    pub fn get_average_strategy(&self) -> Vec<f64> {
+        Self::normalize(self.strategy_sum.clone())
+    }
+
+    fn normalize(weights: Vec<f64>) -> Vec<f64> {
+        let total: f64 = weights.iter().sum();
+        if total > 0.0 {
+            weights.iter().map(|weight| weight / total).collect()
+        } else {
+            vec![1.0 / weights.len() as f64; weights.len()]
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrainingReport {
+    pub iteration: u64,
+    pub info_set_count: usize,
+    // How much a best responder gains on average against the strategy, None when the game is too big to measure
+    pub exploitability: Option<f64>
+}
+
+// Counterfactual regret minimization for two-player zero-sum games
+pub struct CfrTrainer<G: ExtensiveGame> {
+    game: G,
+    variant: CfrVariant,
+    info_sets: HashMap<String, InfoSetNode>,
+    // Regret changes from the tree walk in progress, applied once it is done so every visit
+    // to an information set in one walk plays the same strategy
+    pending_regrets: HashMap<String, Vec<f64>>,
+    iteration: u64,
+    rng: StdRng
+}
+
+impl<G: ExtensiveGame> CfrTrainer<G> {
+    pub fn new(game: G, variant: CfrVariant) -> Result<Self, GameError> {
+        if variant.needs_chance_outcomes() && !Self::lists_chance_outcomes(&game) {
+            return Err(GameError::TrainingUnsupported {
+                reason: format!("{} has too many deals to walk the whole tree, use a sampling variant", game.get_game_name()),
+            });
+        }
+        Ok(CfrTrainer {
+            game,
+            variant,
+            info_sets: HashMap::new(),
+            pending_regrets: HashMap::new(),
+            iteration: 0,
+            rng: StdRng::from_entropy()
+        })
+    }
+
+    pub fn with_rng_seed(mut self, rng_seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(rng_seed);
+        self
+    }
+
+    fn lists_chance_outcomes(game: &G) -> bool {
+        let initial_state = game.get_initial_state();
+        !game.is_chance_node(&initial_state) || game.get_chance_outcomes(&initial_state).is_some()
+    }
+
+    pub fn get_game(&self) -> &G {
+        &self.game
+    }
+
+    pub fn get_variant(&self) -> CfrVariant {
+        self.variant
+    }
+
+    pub fn get_iteration(&self) -> u64 {
+        self.iteration
+    }
+
+    pub fn get_info_sets(&self) -> &HashMap<String, InfoSetNode> {
+        &self.info_sets
+    }
+
+    pub fn train(&mut self, iterations: u64) {
+        for _ in 0..iterations {
+            self.run_iteration();
+        }
+    }
+
+    // Trains and measures the strategy every report_interval iterations and at the end
+    pub fn train_with_reports<F: FnMut(&TrainingReport)>(&mut self, iterations: u64, report_interval: u64, mut on_report: F) -> Vec<TrainingReport> {
+        let mut training_reports: Vec<TrainingReport> = Vec::new();
+        for done in 1..=iterations {
+            self.run_iteration();
+            if done == iterations || (report_interval > 0 && done % report_interval == 0) {
+                let training_report = TrainingReport {
+                    iteration: self.iteration,
+                    info_set_count: self.info_sets.len(),
+                    exploitability: self.get_exploitability(),
+                };
+                on_report(&training_report);
+                training_reports.push(training_report);
+            }
+        }
+        training_reports
+    }
+
+    fn run_iteration(&mut self) {
+        self.iteration += 1;
+        let initial_state = self.game.get_initial_state();
+        match self.variant {
+            CfrVariant::Vanilla | CfrVariant::ChanceSampling => {
+                self.walk_tree(&initial_state, None, [1.0, 1.0], 1.0);
+                self.apply_pending_regrets();
+            },
+            CfrVariant::CfrPlus => {
+                for player in 0..2 {
+                    self.walk_tree(&initial_state, Some(player), [1.0, 1.0], 1.0);
+                    self.apply_pending_regrets();
+                }
+            },
+            CfrVariant::ExternalSampling => {
+                for player in 0..2 {
+                    self.sample_tree(&initial_state, player);
+                }
+            },
+        }
+    }
+
+    fn apply_pending_regrets(&mut self) {
+        let floor_regrets = self.variant == CfrVariant::CfrPlus;
+        for (info_set_key, regret_changes) in self.pending_regrets.drain() {
+            if let Some(node) = self.info_sets.get_mut(&info_set_key) {
+                for (regret, regret_change) in node.regret_sum.iter_mut().zip(regret_changes.iter()) {
+                    *regret += regret_change;
+                    if floor_regrets {
+                        *regret = regret.max(0.0);
+                    }
+                }
+            }
+        }
+    }
+
+    fn get_node_strategy(&mut self, state: &G::State) -> (String, Vec<String>, Vec<f64>) {
+        let info_set_key = self.game.get_info_set_key(state);
+        let game = &self.game;
+        let node = self.info_sets.entry(info_set_key.clone())
+            .or_insert_with(|| InfoSetNode::new(game.get_legal_actions(state)));
+        (info_set_key, node.actions.clone(), node.get_current_strategy())
+    }
+
+    // One pass over the tree, returning player 0's value. Regrets are updated for the given
+    // player, or both when None. Chance is sampled in chance sampling and walked in full otherwise.
+    fn walk_tree(&mut self, state: &G::State, updating_player: Option<usize>, reach: [f64; 2], chance_reach: f64) -> f64 {
+        if self.game.is_terminal(state) {
+            return self.game.get_payoff(state, 0);
+        }
+        if self.game.is_chance_node(state) {
+            let chance_outcomes = match (self.variant, self.game.get_chance_outcomes(state)) {
+                (CfrVariant::ChanceSampling, _) | (_, None) => vec![(self.game.sample_chance_outcome(state, &mut self.rng), 1.0)],
+                (_, Some(chance_outcomes)) => chance_outcomes,
+            };
+            return chance_outcomes.iter()
+                .map(|(outcome, probability)| {
+                    let next_state = self.game.apply_action(state, outcome);
+                    probability * self.walk_tree(&next_state, updating_player, reach, chance_reach * probability)
+                })
+                .sum();
+        }
+
+        let player = self.game.get_current_player(state);
+        let (info_set_key, actions, strategy) = self.get_node_strategy(state);
+        let mut action_values: Vec<f64> = Vec::new();
+        for (action, probability) in actions.iter().zip(strategy.iter()) {
+            let mut next_reach = reach;
+            next_reach[player] *= probability;
+            let next_state = self.game.apply_action(state, action);
+            action_values.push(self.walk_tree(&next_state, updating_player, next_reach, chance_reach));
+        }
+        let node_value: f64 = action_values.iter().zip(strategy.iter()).map(|(value, probability)| value * probability).sum();
+
+        if updating_player.is_none() || updating_player == Some(player) {
+            // Values are player 0's, so player 1's regrets use them negated
+            let sign = if player == 0 { 1.0 } else { -1.0 };
+            let opponent_reach = reach[1 - player] * chance_reach;
+            let average_weight = if self.variant == CfrVariant::CfrPlus { self.iteration as f64 } else { 1.0 };
+            let regret_changes = self.pending_regrets.entry(info_set_key.clone()).or_insert_with(|| vec![0.0; actions.len()]);
+            for (regret_change, action_value) in regret_changes.iter_mut().zip(action_values.iter()) {
+                *regret_change += opponent_reach * sign * (action_value - node_value);
+            }
+            if let Some(node) = self.info_sets.get_mut(&info_set_key) {
+                for (strategy_total, probability) in node.strategy_sum.iter_mut().zip(strategy.iter()) {
+                    *strategy_total += average_weight * reach[player] * probability;
+                }
+            }
+        }
+        node_value
+    }
+
+    // External sampling: every action of the updating player is tried, while chance and the
+    // opponent are sampled. Returns the updating player's value.
+    fn sample_tree(&mut self, state: &G::State, updating_player: usize) -> f64 {
+        if self.game.is_terminal(state) {
+            return self.game.get_payoff(state, updating_player);
+        }
+        if self.game.is_chance_node(state) {
+            let outcome = self.game.sample_chance_outcome(state, &mut self.rng);
+            let next_state = self.game.apply_action(state, &outcome);
+            return self.sample_tree(&next_state, updating_player);
+        }
+
+        let player = self.game.get_current_player(state);
+        let (info_set_key, actions, strategy) = self.get_node_strategy(state);
+        if player != updating_player {
+            if let Some(node) = self.info_sets.get_mut(&info_set_key) {
+                for (strategy_total, probability) in node.strategy_sum.iter_mut().zip(strategy.iter()) {
+                    *strategy_total += probability;
+                }
+            }
+            let action_index = Self::sample_index(&strategy, &mut self.rng);
+            let next_state = self.game.apply_action(state, &actions[action_index]);
+            return self.sample_tree(&next_state, updating_player);
+        }
+
+        let action_values: Vec<f64> = actions.iter()
+            .map(|action| {
+                let next_state = self.game.apply_action(state, action);
+                self.sample_tree(&next_state, updating_player)
+            })
+            .collect();
+        let node_value: f64 = action_values.iter().zip(strategy.iter()).map(|(value, probability)| value * probability).sum();
+        if let Some(node) = self.info_sets.get_mut(&info_set_key) {
+            for (regret, action_value) in node.regret_sum.iter_mut().zip(action_values.iter()) {
+                *regret += action_value - node_value;
+            }
+        }
+        node_value
+    }
+
+    fn sample_index(probabilities: &[f64], rng: &mut StdRng) -> usize {
+        let mut remaining: f64 = rng.gen();
+        for (index, probability) in probabilities.iter().enumerate() {
+            remaining -= probability;
+            if remaining < 0.0 {
+                return index;
+            }
+        }
+        probabilities.len() - 1
+    }
+
+    fn get_average_strategy(&self, state: &G::State) -> Vec<f64> {
+        match self.info_sets.get(&self.game.get_info_set_key(state)) {
+            Some(node) => node.get_average_strategy(),
+            None => {
+                let action_count = self.game.get_legal_actions(state).len();
+                vec![1.0 / action_count as f64; action_count]
+            },
+        }
+    }
+
+    pub fn get_strategy(&self) -> CfrStrategy {
+        let info_sets: BTreeMap<String, Vec<(String, f64)>> = self.info_sets.iter()
+            .map(|(info_set_key, node)| {
+                let action_probabilities = node.actions.iter().cloned().zip(node.get_average_strategy()).collect();
+                (info_set_key.clone(), action_probabilities)
+            })
+            .collect();
+        CfrStrategy {
+            game_name: String::from(self.game.get_game_name()),
+            variant: self.variant,
+            iterations: self.iteration,
+            info_sets
+        }
+    }
+
+    // Player 0's expected winnings when both players use the average strategy
+    pub fn get_expected_value(&self) -> Option<f64> {
+        self.get_profile_value(&self.game.get_initial_state())
+    }
+
+    fn get_profile_value(&self, state: &G::State) -> Option<f64> {
+        if self.game.is_terminal(state) {
+            return Some(self.game.get_payoff(state, 0));
+        }
+        let branches: Vec<(String, f64)> = if self.game.is_chance_node(state) {
+            self.game.get_chance_outcomes(state)?
+        } else {
+            self.game.get_legal_actions(state).into_iter().zip(self.get_average_strategy(state)).collect()
+        };
+        let mut value = 0.0;
+        for (action, probability) in branches.iter() {
+            value += probability * self.get_profile_value(&self.game.apply_action(state, action))?;
+        }
+        Some(value)
+    }
+
+    // The average of what each player gains by switching to a best response against the
+    // other's average strategy. It is zero at equilibrium.
+    pub fn get_exploitability(&self) -> Option<f64> {
+        let best_response_values = [self.get_best_response_value(0)?, self.get_best_response_value(1)?];
+        Some((best_response_values[0] + best_response_values[1]) / 2.0)
+    }
+
+    pub fn get_best_response_value(&self, responder: usize) -> Option<f64> {
+        let initial_state = self.game.get_initial_state();
+        let mut info_set_states: HashMap<String, Vec<(G::State, f64)>> = HashMap::new();
+        self.collect_info_set_states(&initial_state, responder, 1.0, &mut info_set_states)?;
+        let mut best_actions: HashMap<String, usize> = HashMap::new();
+        Some(self.get_best_response_state_value(&initial_state, responder, &info_set_states, &mut best_actions))
+    }
+
+    // Groups the responder's states by information set, each weighted by how likely chance
+    // and the opponent are to reach it
+    fn collect_info_set_states(&self, state: &G::State, responder: usize, weight: f64, info_set_states: &mut HashMap<String, Vec<(G::State, f64)>>) -> Option<()> {
+        if self.game.is_terminal(state) {
+            return Some(());
+        }
+        let branches: Vec<(String, f64)> = if self.game.is_chance_node(state) {
+            self.game.get_chance_outcomes(state)?
+        } else if self.game.get_current_player(state) == responder {
+            info_set_states.entry(self.game.get_info_set_key(state)).or_default().push((state.clone(), weight));
+            self.game.get_legal_actions(state).into_iter().map(|action| (action, 1.0)).collect()
+        } else {
+            self.game.get_legal_actions(state).into_iter().zip(self.get_average_strategy(state)).collect()
+        };
+        for (action, probability) in branches.iter() {
+            self.collect_info_set_states(&self.game.apply_action(state, action), responder, weight * probability, info_set_states)?;
+        }
+        Some(())
+    }
+
+    fn get_best_response_state_value(&self, state: &G::State, responder: usize, info_set_states: &HashMap<String, Vec<(G::State, f64)>>, best_actions: &mut HashMap<String, usize>) -> f64 {
+        if self.game.is_terminal(state) {
+            return self.game.get_payoff(state, responder);
+        }
+        if self.game.is_chance_node(state) {
+            return self.game.get_chance_outcomes(state).unwrap_or_default().iter()
+                .map(|(outcome, probability)| probability * self.get_best_response_state_value(&self.game.apply_action(state, outcome), responder, info_set_states, best_actions))
+                .sum();
+        }
+        let actions = self.game.get_legal_actions(state);
+        if self.game.get_current_player(state) != responder {
+            return actions.iter().zip(self.get_average_strategy(state))
+                .map(|(action, probability)| probability * self.get_best_response_state_value(&self.game.apply_action(state, action), responder, info_set_states, best_actions))
+                .sum();
+        }
+
+        // The responder cannot tell the states in an information set apart, so one action is
+        // chosen for all of them, the one best on average over the set
+        let info_set_key = self.game.get_info_set_key(state);
+        let best_action = match best_actions.get(&info_set_key) {
+            Some(&best_action) => best_action,
+            None => {
+                let weighted_states = info_set_states.get(&info_set_key).cloned().unwrap_or_default();
+                let action_values: Vec<f64> = actions.iter()
+                    .map(|action| {
+                        weighted_states.iter()
+                            .map(|(info_set_state, weight)| weight * self.get_best_response_state_value(&self.game.apply_action(info_set_state, action), responder, info_set_states, best_actions))
+                            .sum()
+                    })
+                    .collect();
+                let best_action = (0..actions.len())
+                    .max_by(|&first, &second| action_values[first].total_cmp(&action_values[second]))
+                    .unwrap_or_default();
+                best_actions.insert(info_set_key, best_action);
+                best_action
+            },
+        };
+        self.get_best_response_state_value(&self.game.apply_action(state, &actions[best_action]), responder, info_set_states, best_actions)
+    }
+}
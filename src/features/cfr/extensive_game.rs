@@ -0,0 +1,24 @@
+use rand::rngs::StdRng;
+
+
+
+// A two-player zero-sum game in extensive form, which the CFR trainer can solve.
+// Actions and chance outcomes are short strings so learned strategies stay readable.
+pub trait ExtensiveGame {
+    type State: Clone;
+
+    fn get_game_name(&self) -> &str;
+    fn get_initial_state(&self) -> Self::State;
+    fn is_terminal(&self, state: &Self::State) -> bool;
+    // What the player wins or loses at a terminal state
+    fn get_payoff(&self, state: &Self::State, player: usize) -> f64;
+    fn is_chance_node(&self, state: &Self::State) -> bool;
+    // Every chance outcome with its probability, or None when there are too many to list
+    fn get_chance_outcomes(&self, state: &Self::State) -> Option<Vec<(String, f64)>>;
+    fn sample_chance_outcome(&self, state: &Self::State, rng: &mut StdRng) -> String;
+    fn get_current_player(&self, state: &Self::State) -> usize;
+    fn get_legal_actions(&self, state: &Self::State) -> Vec<String>;
+    // Everything the player to act knows, so states they cannot tell apart share a key
+    fn get_info_set_key(&self, state: &Self::State) -> String;
+    fn apply_action(&self, state: &Self::State, action: &str) -> Self::State;
+}
@@ -0,0 +1,23 @@
+// Three-card poker: each player antes one chip and gets a jack, queen or king, then one
+// round of betting with a single one-chip bet. Player 0 expects to lose 1/18 of a chip a hand
+// at equilibrium, which makes it a known answer for the trainer.
+#[derive(Debug, Clone, Default)]
+pub struct KuhnPoker;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KuhnState {
+    // Card of each player dealt so far, 0 for the jack up to 2 for the king
+    pub cards: Vec<usize>,
+    // "p" to pass and "b" to bet, in order
+    pub history: String
+}
+
+impl KuhnPoker {
+    pub const GAME_NAME: &'static str = "kuhn";
+    pub const GAME_VALUE: f64 = -1.0 / 18.0;
+    pub const CARD_NAMES: [&'static str; 3] = ["J", "Q", "K"];
+
+    pub fn new() -> Self {
+        KuhnPoker
+    }
+}
@@ -0,0 +1,66 @@
+// Six-card poker with two jacks, two queens and two kings. Each player antes one chip and gets
+// one card, then bets in two rounds with a public card dealt between them. Bets are two chips in
+// the first round and four in the second, with at most two raises a round. A pair with the public
+// card wins, otherwise the higher card. Player 0 expects to lose about 0.0856 chips a hand at equilibrium.
+#[derive(Debug, Clone, Default)]
+pub struct LeducPoker;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeducState {
+    // Cards 0 to 5, where card / 2 is the rank
+    pub private_cards: Vec<usize>,
+    pub public_card: Option<usize>,
+    // "c" to check or call, "r" to bet or raise and "f" to fold, one string for each round
+    pub rounds: Vec<String>
+}
+
+impl LeducPoker {
+    pub const GAME_NAME: &'static str = "leduc";
+    pub const GAME_VALUE: f64 = -0.0856;
+    pub const RANK_NAMES: [&'static str; 3] = ["J", "Q", "K"];
+    pub const DECK_SIZE: usize = 6;
+    pub const ANTE: f64 = 1.0;
+    pub const BET_SIZES: [f64; 2] = [2.0, 4.0];
+    pub const MAX_RAISES: usize = 2;
+
+    pub fn new() -> Self {
+        LeducPoker
+    }
+
+    pub fn is_round_over(round: &str) -> bool {
+        round == "cc" || (round.ends_with('c') && round.contains('r')) || round.ends_with('f')
+    }
+
+    pub fn get_folded_player(state: &LeducState) -> Option<usize> {
+        state.rounds.iter()
+            .find_map(|round| round.find('f'))
+            .map(|position| position % 2)
+    }
+
+    // Chips each player has put in, antes included
+    pub fn get_contributions(state: &LeducState) -> [f64; 2] {
+        let mut contributions = [Self::ANTE, Self::ANTE];
+        for (round_index, round) in state.rounds.iter().enumerate() {
+            for (position, action) in round.chars().enumerate() {
+                let player = position % 2;
+                let opponent = 1 - player;
+                match action {
+                    'r' => contributions[player] = contributions[opponent] + Self::BET_SIZES[round_index],
+                    'c' => contributions[player] = contributions[opponent],
+                    _ => {},
+                }
+            }
+        }
+        contributions
+    }
+
+    pub fn get_rank(card: usize) -> usize {
+        card / 2
+    }
+
+    pub(crate) fn get_remaining_cards(state: &LeducState) -> Vec<usize> {
+        (0..Self::DECK_SIZE)
+            .filter(|card| !state.private_cards.contains(card) && state.public_card != Some(*card))
+            .collect()
+    }
+}
@@ -0,0 +1,111 @@
+use crate::card::Card;
+use crate::features::RuleBasedAgent;
+
+
+
+// Heads-up limit hold'em on the crate's rules, abstracted so CFR can train on it. Real cards are
+// dealt and compared at showdown, but players only see a strength bucket for their hand on each
+// street. Player 0 is the button and small blind. Blinds are one and two chips, bets two chips
+// before the turn and four after, with four bets a street counting the big blind.
+#[derive(Debug, Clone, Default)]
+pub struct LimitHoldem;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LimitHoldemState {
+    // Indexes into the standard deck
+    pub hole_cards: Vec<Vec<usize>>,
+    pub board: Vec<usize>,
+    // "c" to check or call, "r" to bet or raise and "f" to fold, one string for each street
+    pub rounds: Vec<String>
+}
+
+impl LimitHoldem {
+    pub const GAME_NAME: &'static str = "limit_holdem";
+    pub const BLINDS: [f64; 2] = [1.0, 2.0];
+    pub const BET_SIZES: [f64; 4] = [2.0, 2.0, 4.0, 4.0];
+    pub const BETS_PER_ROUND: usize = 4;
+    pub const STREET_CARDS: [usize; 4] = [0, 3, 1, 1];
+
+    pub fn new() -> Self {
+        LimitHoldem
+    }
+
+    // The button acts first before the flop and last after it
+    pub fn get_first_player(round_index: usize) -> usize {
+        if round_index == 0 { 0 } else { 1 }
+    }
+
+    pub fn is_round_over(round: &str) -> bool {
+        round == "cc" || (round.ends_with('c') && round.contains('r')) || round.ends_with('f')
+    }
+
+    pub fn get_folded_player(state: &LimitHoldemState) -> Option<usize> {
+        state.rounds.iter().enumerate().find_map(|(round_index, round)| {
+            round.find('f').map(|position| (Self::get_first_player(round_index) + position) % 2)
+        })
+    }
+
+    // Chips each player has put in, blinds included
+    pub fn get_contributions(state: &LimitHoldemState) -> [f64; 2] {
+        let mut contributions = Self::BLINDS;
+        for (round_index, round) in state.rounds.iter().enumerate() {
+            for (position, action) in round.chars().enumerate() {
+                let player = (Self::get_first_player(round_index) + position) % 2;
+                let opponent = 1 - player;
+                match action {
+                    'r' => contributions[player] = contributions[opponent] + Self::BET_SIZES[round_index],
+                    'c' => contributions[player] = contributions[player].max(contributions[opponent]),
+                    _ => {},
+                }
+            }
+        }
+        contributions
+    }
+
+    // Raises still allowed on this street, where the big blind counts as the first bet
+    pub fn get_raises_left(round_index: usize, round: &str) -> usize {
+        let bets_made = round.matches('r').count() + if round_index == 0 { 1 } else { 0 };
+        Self::BETS_PER_ROUND.saturating_sub(bets_made)
+    }
+
+    // 0 to 4 from the weakest to the strongest hands, by Chen score before the flop and by
+    // made hand and draws after it
+    pub fn get_bucket(hole_cards: &[Card], board: &[Card]) -> u8 {
+        if board.is_empty() {
+            return match RuleBasedAgent::get_chen_score(hole_cards) {
+                score if score < 5 => 0,
+                score if score < 7 => 1,
+                score if score < 9 => 2,
+                score if score < 11 => 3,
+                _ => 4,
+            };
+        }
+        match RuleBasedAgent::get_made_hand_tier(hole_cards, board) {
+            3 => 4,
+            2 => 3,
+            1 => 2,
+            _ if RuleBasedAgent::count_outs(hole_cards, board) >= 8 => 1,
+            _ => 0,
+        }
+    }
+
+    // The same key the trained strategy uses, from the player's cards and the betting so far.
+    // There is one bucket for every street reached, so the key keeps everything the player has seen.
+    pub fn get_info_set_key_for(hole_cards: &[Card], board: &[Card], rounds: &[String]) -> String {
+        let mut board_sizes: Vec<usize> = Vec::new();
+        let mut board_size = 0;
+        for street_cards in Self::STREET_CARDS.iter().take(rounds.len().max(1)) {
+            board_size += street_cards;
+            board_sizes.push(board_size.min(board.len()));
+        }
+        let buckets: Vec<String> = board_sizes.iter()
+            .map(|&board_size| Self::get_bucket(hole_cards, &board[..board_size]).to_string())
+            .collect();
+        format!("{}:{}", buckets.join("."), rounds.join("/"))
+    }
+
+    pub(crate) fn get_cards(card_indexes: &[usize]) -> Vec<Card> {
+        let deck = Card::get_standard_deck();
+        card_indexes.iter().map(|&card_index| deck[card_index].clone()).collect()
+    }
+}
@@ -0,0 +1,13 @@
+mod extensive_game;
+mod cfr_trainer;
+mod cfr_strategy;
+mod kuhn_poker;
+mod leduc_poker;
+mod limit_holdem;
+
+pub use extensive_game::ExtensiveGame;
+pub use cfr_trainer::{CfrTrainer, InfoSetNode, TrainingReport};
+pub use cfr_strategy::CfrStrategy;
+pub use kuhn_poker::{KuhnPoker, KuhnState};
+pub use leduc_poker::{LeducPoker, LeducState};
+pub use limit_holdem::{LimitHoldem, LimitHoldemState};
@@ -0,0 +1,82 @@
+use crate::constants::{ChipAccount, TransferReason};
+use crate::features::GameError;
+use serde::{Serialize, Deserialize};
+
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChipTransfer {
+    pub hand_number: u32,
+    pub from: ChipAccount,
+    pub to: ChipAccount,
+    pub amount: u32,
+    pub reason: TransferReason
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChipLedger {
+    // Everything bought in so far, which the table must always account for
+    total_chips: u32,
+    transfers: Vec<ChipTransfer>
+}
+
+impl ChipLedger {
+    pub fn record_transfer(&mut self, hand_number: u32, from: ChipAccount, to: ChipAccount, amount: u32, reason: TransferReason) {
+        if amount == 0 {
+            return;
+        }
+        if from == ChipAccount::Cashier {
+            self.total_chips += amount;
+        }
+        if to == ChipAccount::Cashier {
+            self.total_chips -= amount;
+        }
+        self.transfers.push(ChipTransfer { hand_number, from, to, amount, reason });
+    }
+
+    // Drops every transfer after the first transfer_count, as if they never happened
+    pub(crate) fn truncate(&mut self, transfer_count: usize) {
+        for transfer in self.transfers.drain(transfer_count.min(self.transfers.len())..) {
+            if transfer.from == ChipAccount::Cashier {
+                self.total_chips -= transfer.amount;
+            }
+            if transfer.to == ChipAccount::Cashier {
+                self.total_chips += transfer.amount;
+            }
+        }
+    }
+
+    pub fn get_total_chips(&self) -> u32 {
+        self.total_chips
+    }
+
+    pub fn get_transfers(&self) -> &Vec<ChipTransfer> {
+        &self.transfers
+    }
+
+    pub fn get_hand_transfers(&self, hand_number: u32) -> Vec<&ChipTransfer> {
+        self.transfers.iter()
+            .filter(|transfer| transfer.hand_number == hand_number)
+            .collect()
+    }
+
+    // Net chips that have moved into an account, negative when more went out than came in
+    pub fn get_balance(&self, account: ChipAccount) -> i64 {
+        self.transfers.iter()
+            .map(|transfer| {
+                let incoming = if transfer.to == account { transfer.amount as i64 } else { 0 };
+                let outgoing = if transfer.from == account { transfer.amount as i64 } else { 0 };
+                incoming - outgoing
+            })
+            .sum()
+    }
+
+    pub fn audit(&self, stacks: u32, pot: u32, rake: u32) -> Result<(), GameError> {
+        let actual = stacks + pot + rake;
+        if actual == self.total_chips {
+            Ok(())
+        } else {
+            Err(GameError::ChipsNotConserved { expected: self.total_chips, actual })
+        }
+    }
+}
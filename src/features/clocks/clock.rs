@@ -0,0 +1,8 @@
+use std::time::Duration;
+
+
+
+pub trait Clock {
+    // Time since an arbitrary fixed start, which only ever moves forward
+    fn now(&self) -> Duration;
+}
@@ -0,0 +1,22 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+
+
+// Only moves when advanced by hand. Clones share the same time, so a caller can
+// keep one and give another to the game.
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    pub elapsed: Rc<Cell<Duration>>
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        ManualClock::default()
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.elapsed.set(self.elapsed.get() + duration);
+    }
+}
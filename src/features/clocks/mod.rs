@@ -0,0 +1,7 @@
+mod clock;
+mod system_clock;
+mod manual_clock;
+
+pub use clock::Clock;
+pub use system_clock::SystemClock;
+pub use manual_clock::ManualClock;
@@ -0,0 +1,20 @@
+use std::time::Instant;
+
+
+
+#[derive(Debug, Clone)]
+pub struct SystemClock {
+    pub started_at: Instant
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        SystemClock { started_at: Instant::now() }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        SystemClock::new()
+    }
+}
@@ -0,0 +1,12 @@
+// Proof of dealer authority. Only the engine can create one, so anything that
+// moves chips or reveals hole cards asks for it instead of trusting an id string.
+#[derive(Debug, Clone)]
+pub struct DealerToken {
+    _private: ()
+}
+
+impl DealerToken {
+    pub(crate) fn new() -> Self {
+        DealerToken { _private: () }
+    }
+}
@@ -0,0 +1,22 @@
+use crate::constants::StraddleType;
+use serde::{Serialize, Deserialize};
+
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ForcedBets {
+    pub small_blind: u32,
+    pub big_blind: u32,
+    // Dead money posted by every player dealt in
+    pub ante: u32,
+    // Dead money posted by the big blind on behalf of the whole table
+    pub big_blind_ante: u32,
+    // A live blind raise of twice the big blind
+    pub straddle: Option<StraddleType>
+}
+
+impl ForcedBets {
+    pub fn get_straddle_amount(&self) -> u32 {
+        self.big_blind * 2
+    }
+}
@@ -0,0 +1,141 @@
+use std::fmt;
+use crate::constants::{GameErrorKind, PlayerAction};
+
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameError {
+    NoGame,
+    NoDeck,
+    DeckAlreadyGenerated,
+    InvalidDeckCount,
+    BlindsNotSpecified,
+    BlindsDisabled,
+    BlindsNotPosted,
+    BlindsAlreadyPosted,
+    CardsNotDealt,
+    NotEnoughPlayers,
+    TableFull,
+    HandInProgress,
+    PlayerNotFound { player_index: usize },
+    InvalidConfig { reason: String },
+    UnknownAgent { agent_name: String },
+    AgentNotFound { player_id: u32 },
+    NothingToUndo { requested: usize, available: usize },
+    InvalidSnapshot { reason: String },
+    TrainingUnsupported { reason: String },
+    InvalidStrategy { reason: String },
+    InvalidStats { reason: String },
+    NotPlayersTurn { player_index: usize },
+    BetAlreadyOpen { highest_bet: u32 },
+    NothingToRaise,
+    RaiseNotAboveBet { highest_bet: u32 },
+    InsufficientCoins { required: u32, available: u32 },
+    BelowMinimumBet { minimum: u32 },
+    BelowMinimumRaise { minimum: u32 },
+    AboveMaximumBet { maximum: u32 },
+    RaiseCapReached,
+    NothingToCall,
+    CannotCheck { highest_bet: u32 },
+    LastPlayerCannotFold,
+    DeckExhausted,
+    FundsNotTransferred { player_index: usize, action: PlayerAction },
+    PlayerDecisionFailed { player_index: usize },
+    ChipsNotConserved { expected: u32, actual: u32 },
+    StrategyNotSaved { reason: String },
+    StatsNotSaved { reason: String }
+}
+
+impl GameError {
+    pub fn get_kind(&self) -> GameErrorKind {
+        match self {
+            GameError::NoGame
+            | GameError::NoDeck
+            | GameError::DeckAlreadyGenerated
+            | GameError::InvalidDeckCount
+            | GameError::BlindsNotSpecified
+            | GameError::BlindsDisabled
+            | GameError::BlindsNotPosted
+            | GameError::BlindsAlreadyPosted
+            | GameError::CardsNotDealt
+            | GameError::NotEnoughPlayers
+            | GameError::TableFull
+            | GameError::HandInProgress
+            | GameError::PlayerNotFound { .. }
+            | GameError::InvalidConfig { .. }
+            | GameError::UnknownAgent { .. }
+            | GameError::AgentNotFound { .. }
+            | GameError::NothingToUndo { .. }
+            | GameError::InvalidSnapshot { .. }
+            | GameError::TrainingUnsupported { .. }
+            | GameError::InvalidStrategy { .. }
+            | GameError::InvalidStats { .. } => GameErrorKind::Setup,
+            GameError::NotPlayersTurn { .. }
+            | GameError::BetAlreadyOpen { .. }
+            | GameError::NothingToRaise
+            | GameError::RaiseNotAboveBet { .. }
+            | GameError::InsufficientCoins { .. }
+            | GameError::BelowMinimumBet { .. }
+            | GameError::BelowMinimumRaise { .. }
+            | GameError::AboveMaximumBet { .. }
+            | GameError::RaiseCapReached
+            | GameError::NothingToCall
+            | GameError::CannotCheck { .. }
+            | GameError::LastPlayerCannotFold => GameErrorKind::IllegalAction,
+            GameError::DeckExhausted
+            | GameError::FundsNotTransferred { .. }
+            | GameError::PlayerDecisionFailed { .. }
+            | GameError::ChipsNotConserved { .. }
+            | GameError::StrategyNotSaved { .. }
+            | GameError::StatsNotSaved { .. } => GameErrorKind::Internal,
+        }
+    }
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::NoGame => write!(f, "You need to create a game first or load an existing game"),
+            GameError::NoDeck => write!(f, "Dealer has no deck yet. Generate a deck first."),
+            GameError::DeckAlreadyGenerated => write!(f, "Dealer already has a deck."),
+            GameError::InvalidDeckCount => write!(f, "At least one deck is needed."),
+            GameError::BlindsNotSpecified => write!(f, "Please specify if playing with blinds."),
+            GameError::BlindsDisabled => write!(f, "Blinds not enabled for this game"),
+            GameError::BlindsNotPosted => write!(f, "Please set blind amount if playing with blinds."),
+            GameError::BlindsAlreadyPosted => write!(f, "Blinds have already been set for this hand."),
+            GameError::CardsNotDealt => write!(f, "Please deal players with cards."),
+            GameError::NotEnoughPlayers => write!(f, "Not enough players to start a hand."),
+            GameError::TableFull => write!(f, "Table is full."),
+            GameError::HandInProgress => write!(f, "The current hand is still in progress."),
+            GameError::PlayerNotFound { player_index } => write!(f, "There is no player {} at the table.", player_index),
+            GameError::InvalidConfig { reason } => write!(f, "Invalid table config: {}", reason),
+            GameError::UnknownAgent { agent_name } => write!(f, "No agent is registered as {:?}", agent_name),
+            GameError::AgentNotFound { player_id } => write!(f, "Player {} has no agent to decide for them", player_id),
+            GameError::NothingToUndo { requested, available } => write!(f, "Cannot undo {} actions, only {} recorded this hand.", requested, available),
+            GameError::InvalidSnapshot { reason } => write!(f, "Snapshot could not be read: {}", reason),
+            GameError::TrainingUnsupported { reason } => write!(f, "Cannot train: {}", reason),
+            GameError::InvalidStrategy { reason } => write!(f, "Strategy could not be read: {}", reason),
+            GameError::InvalidStats { reason } => write!(f, "Player stats could not be read: {}", reason),
+            GameError::NotPlayersTurn { player_index } => write!(f, "It is not player {}'s turn to act.", player_index),
+            GameError::BetAlreadyOpen { highest_bet } => write!(f, "There is already a bet of {}. Raise instead.", highest_bet),
+            GameError::NothingToRaise => write!(f, "There is no bet to raise. Bet instead."),
+            GameError::RaiseNotAboveBet { highest_bet } => write!(f, "Raise must be above the current bet of {}", highest_bet),
+            GameError::InsufficientCoins { required, available } => write!(f, "Not enough coins: {} needed, {} available", required, available),
+            GameError::BelowMinimumBet { minimum } => write!(f, "Minimum bet is: {}", minimum),
+            GameError::BelowMinimumRaise { minimum } => write!(f, "Minimum raise is to: {}", minimum),
+            GameError::AboveMaximumBet { maximum } => write!(f, "Maximum bet is to: {}", maximum),
+            GameError::RaiseCapReached => write!(f, "Betting is capped for this street. Call or fold."),
+            GameError::NothingToCall => write!(f, "Nothing to call. Check instead."),
+            GameError::CannotCheck { highest_bet } => write!(f, "Cannot check facing a bet of {}", highest_bet),
+            GameError::LastPlayerCannotFold => write!(f, "Everyone else has folded. The last player cannot fold."),
+            GameError::DeckExhausted => write!(f, "The deck ran out of cards."),
+            GameError::FundsNotTransferred { player_index, action } => write!(f, "Player {} was not charged for {:?}", player_index, action),
+            GameError::PlayerDecisionFailed { player_index } => write!(f, "Failed to get action from player {}", player_index),
+            GameError::ChipsNotConserved { expected, actual } => write!(f, "Chips do not add up: expected {}, found {}", expected, actual),
+            GameError::StrategyNotSaved { reason } => write!(f, "Strategy could not be saved: {}", reason),
+            GameError::StatsNotSaved { reason } => write!(f, "Player stats could not be saved: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
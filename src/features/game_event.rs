@@ -0,0 +1,39 @@
+use crate::card::Card;
+use crate::constants::{PlayerAction, HandPhase, BlindType};
+use crate::features::HandValue;
+use serde::{Serialize, Deserialize};
+
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeatSummary {
+    pub player_id: u32,
+    pub username: String,
+    pub seat: usize,
+    pub coins: u32
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum GameEvent {
+    HandStarted { hand_number: u32, button_seat: usize, players: Vec<SeatSummary> },
+    BlindsPosted { player_id: u32, blind_type: BlindType, amount: u32 },
+    // Hole cards stay private, so observers only learn how many were dealt
+    HoleCardsDealt { player_id: u32, card_count: usize },
+    // amount is what the action moved from the player's stack into the pot
+    PlayerActed { player_id: u32, action: PlayerAction, amount: u32, all_in: bool },
+    // An illegal choice, and the action the engine took instead once the player ran out of attempts
+    ActionRejected { player_id: u32, action: PlayerAction, amount: Option<u32>, reason: String, replaced_with: Option<PlayerAction> },
+    // Something went wrong with the agent playing a seat, like a bot that crashed or sent a bad reply
+    AgentProblem { player_id: u32, problem: String },
+    StreetDealt { hand_phase: HandPhase, cards: Vec<Card>, board: Vec<Card> },
+    // Each shown hand with the best five-card value it was paid on
+    Showdown { hands: Vec<(u32, Vec<Card>, HandValue)> },
+    UncalledBetReturned { player_id: u32, amount: u32 },
+    // winners holds (player_id, winnings) after rake
+    PotAwarded { amount: u32, rake: u32, winners: Vec<(u32, u32)> },
+    HandEnded { hand_number: u32, total_rake: u32 },
+    PlayerLeft { player_id: u32, username: String },
+    // The action the engine took on the player's behalf
+    PlayerTimedOut { player_id: u32, action: PlayerAction },
+    PlayerDisconnected { player_id: u32 }
+}
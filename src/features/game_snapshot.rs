@@ -0,0 +1,151 @@
+use crate::card::Card;
+use crate::dealer::Dealer;
+use crate::player::Player;
+use crate::poker_game::PokerGame;
+use crate::constants::{HandPhase, BettingStructure};
+use crate::features::{ForcedBets, RakeSettings, HouseLedger, ChipLedger, GameEvent, GameError, TimeLimits, TableConfig, StatsTracker};
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+
+
+
+// The full state of a table, minus its observers. Hole cards and the deck order are not
+// part of Player and Dealer's serde, so this is the only format that carries them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameSnapshot {
+    pub(crate) game_id: String,
+    pub(crate) players: Vec<Player>,
+    pub(crate) game_dealer: Dealer,
+    pub(crate) pot: u32,
+    pub(crate) waiting_players: Vec<Player>,
+    pub(crate) max_seats: usize,
+    pub(crate) hand_number: u32,
+    pub(crate) with_blinds: Option<bool>,
+    pub(crate) button_seat: usize,
+    pub(crate) small_blind_seat: usize,
+    pub(crate) big_blind_seat: usize,
+    pub(crate) straddle_seat: Option<usize>,
+    pub(crate) blinds_initialized: bool,
+    pub(crate) big_blind: u32,
+    pub(crate) forced_bets: ForcedBets,
+    pub(crate) betting_structure: BettingStructure,
+    pub(crate) time_limits: TimeLimits,
+    pub(crate) rake_settings: RakeSettings,
+    pub(crate) house_ledger: HouseLedger,
+    pub(crate) chip_ledger: ChipLedger,
+    pub(crate) hand_phase: HandPhase,
+    pub(crate) action_index: Option<usize>,
+    pub(crate) hand_history: Vec<GameEvent>,
+    pub(crate) highest_bet: u32,
+    pub(crate) last_raise_size: u32,
+    pub(crate) street_bet_count: u32,
+    pub(crate) community_deck: Vec<Card>,
+    // (player_id, hole cards) for everyone seated or waiting
+    pub(crate) hole_cards: Vec<(u32, Vec<Card>)>,
+    pub(crate) deck: Option<Vec<Card>>,
+    pub(crate) stacked_decks: Vec<Vec<Card>>,
+    // Each player's notes on the others, by username
+    #[serde(default)]
+    pub(crate) stats_trackers: HashMap<String, StatsTracker>
+}
+
+impl GameSnapshot {
+    pub fn get_game_id(&self) -> &str {
+        &self.game_id
+    }
+
+    pub fn get_hand_number(&self) -> u32 {
+        self.hand_number
+    }
+
+    pub fn get_hand_phase(&self) -> HandPhase {
+        self.hand_phase
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, GameError> {
+        serde_json::to_vec(self).map_err(|error| GameError::InvalidSnapshot { reason: error.to_string() })
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, GameError> {
+        let snapshot: GameSnapshot = serde_json::from_slice(bytes).map_err(|error| GameError::InvalidSnapshot { reason: error.to_string() })?;
+        snapshot.validate()?;
+        Ok(snapshot)
+    }
+
+    // Bytes can come from anywhere, so anything the engine indexes by or counts down from is
+    // checked before a table is built on them
+    pub fn validate(&self) -> Result<(), GameError> {
+        let invalid = |reason: String| Err(GameError::InvalidSnapshot { reason });
+
+        if self.max_seats == 0 || self.max_seats > TableConfig::SEAT_LIMIT {
+            return invalid(format!("max_seats must be between 1 and {}", TableConfig::SEAT_LIMIT));
+        }
+        let mut taken_seats: Vec<usize> = Vec::new();
+        for player in self.players.iter().chain(self.waiting_players.iter()) {
+            if player.get_seat() >= self.max_seats {
+                return invalid(format!("player {} sits at seat {} of a {} seat table", player.get_player_id(), player.get_seat(), self.max_seats));
+            }
+            if taken_seats.contains(&player.get_seat()) {
+                return invalid(format!("seat {} is taken more than once", player.get_seat()));
+            }
+            taken_seats.push(player.get_seat());
+        }
+        let table_seats = [Some(self.button_seat), Some(self.small_blind_seat), Some(self.big_blind_seat), self.straddle_seat];
+        if let Some(seat) = table_seats.iter().flatten().find(|&&seat| seat >= self.max_seats) {
+            return invalid(format!("seat {} is outside a {} seat table", seat, self.max_seats));
+        }
+        if let Some(action_index) = self.action_index.filter(|&action_index| action_index >= self.players.len()) {
+            return invalid(format!("player {} is to act but only {} are seated", action_index, self.players.len()));
+        }
+
+        if self.community_deck.len() > 5 {
+            return invalid(format!("the board has {} cards", self.community_deck.len()));
+        }
+        let cards_left = self.deck.as_ref().map(|deck| deck.len()).unwrap_or(0);
+        let board_cards_to_come = 5 - self.community_deck.len();
+        if self.hand_phase != HandPhase::Initial && self.hand_phase != HandPhase::HandOver && cards_left < board_cards_to_come {
+            return invalid(format!("{} cards are left to deal {} more to the board", cards_left, board_cards_to_come));
+        }
+        for stacked_cards in self.stacked_decks.iter() {
+            PokerGame::check_stacked_cards(self.game_dealer.get_deck_count(), stacked_cards)?;
+        }
+        // Until the pots are paid out, the pot holds exactly what the players have put in
+        let total_bets = match Self::checked_sum(self.players.iter().map(|player| player.get_total_bet())) {
+            Some(total_bets) => total_bets,
+            None => return invalid(String::from("the players' bets add up to more chips than a table can hold")),
+        };
+        if self.hand_phase != HandPhase::HandOver && self.pot != total_bets {
+            return invalid(format!("the pot of {} does not match the {} put in", self.pot, total_bets));
+        }
+        // Every chip bought in is in a stack, the pot or the house's rake
+        let stacks = Self::checked_sum(self.players.iter().chain(self.waiting_players.iter()).map(|player| player.get_coins()));
+        let table_chips = stacks
+            .and_then(|stacks| stacks.checked_add(self.pot))
+            .and_then(|chips| chips.checked_add(self.house_ledger.get_total_rake()));
+        match table_chips {
+            Some(table_chips) if table_chips == self.chip_ledger.get_total_chips() => {},
+            Some(table_chips) => return invalid(format!("the table holds {} chips but {} were bought in", table_chips, self.chip_ledger.get_total_chips())),
+            None => return invalid(String::from("the stacks add up to more chips than a table can hold")),
+        }
+        if !(0.0..=100.0).contains(&self.rake_settings.percentage) {
+            return invalid(String::from("rake percentage must be between 0 and 100"));
+        }
+        Ok(())
+    }
+
+    fn checked_sum(mut amounts: impl Iterator<Item = u32>) -> Option<u32> {
+        amounts.try_fold(0u32, |total, amount| total.checked_add(amount))
+    }
+}
+
+// What undoing one action needs. The session's ledgers and the hand history only grow
+// during a hand, so instead of copies they are cut back to the lengths they had. Stats change
+// only when the hand ends, so just the point before the hand's last action keeps a copy.
+#[derive(Debug, Clone)]
+pub(crate) struct UndoPoint {
+    pub(crate) snapshot: GameSnapshot,
+    pub(crate) chip_transfer_count: usize,
+    pub(crate) house_hand_count: usize,
+    pub(crate) hand_history_length: usize,
+    pub(crate) stats_trackers_before_hand: Option<HashMap<String, StatsTracker>>
+}
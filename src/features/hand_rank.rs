@@ -1,6 +1,7 @@
 use crate::card::Card;
+use serde::{Serialize, Deserialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HandRank {
     pub hand_rank: u8,
     pub hand_rank_name: String,
@@ -0,0 +1,38 @@
+use serde::{Serialize, Deserialize};
+
+
+
+// A comparable score for the best five cards. Categories run from 0 (high card) to 8
+// (straight flush) and ties are broken by ranks, highest first.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct HandValue {
+    pub category: u8,
+    pub ranks: Vec<u32>
+}
+
+impl HandValue {
+    pub const HIGH_CARD: u8 = 0;
+    pub const ONE_PAIR: u8 = 1;
+    pub const TWO_PAIRS: u8 = 2;
+    pub const THREE_OF_A_KIND: u8 = 3;
+    pub const STRAIGHT: u8 = 4;
+    pub const FLUSH: u8 = 5;
+    pub const FULL_HOUSE: u8 = 6;
+    pub const FOUR_OF_A_KIND: u8 = 7;
+    pub const STRAIGHT_FLUSH: u8 = 8;
+
+    pub fn get_category_name(&self) -> &'static str {
+        match self.category {
+            Self::ONE_PAIR => "One Pair",
+            Self::TWO_PAIRS => "Two Pairs",
+            Self::THREE_OF_A_KIND => "Three of a Kind",
+            Self::STRAIGHT => "Straight",
+            Self::FLUSH => "Flush",
+            Self::FULL_HOUSE => "Full House",
+            Self::FOUR_OF_A_KIND => "Four of a Kind",
+            Self::STRAIGHT_FLUSH if self.ranks.first() == Some(&14) => "Royal Flush",
+            Self::STRAIGHT_FLUSH => "Straight Flush",
+            _ => "High Card",
+        }
+    }
+}
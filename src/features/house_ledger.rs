@@ -0,0 +1,42 @@
+use serde::{Serialize, Deserialize};
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandRake {
+    pub hand_number: u32,
+    pub pot_rakes: Vec<u32>,
+    pub total_rake: u32
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HouseLedger {
+    total_rake: u32,
+    hands: Vec<HandRake>
+}
+
+impl HouseLedger {
+    pub fn record_hand(&mut self, hand_number: u32, pot_rakes: Vec<u32>) -> &HandRake {
+        let total_rake: u32 = pot_rakes.iter().sum();
+        self.total_rake += total_rake;
+        self.hands.push(HandRake { hand_number, pot_rakes, total_rake });
+        &self.hands[self.hands.len() - 1]
+    }
+
+    pub(crate) fn truncate(&mut self, hand_count: usize) {
+        for hand in self.hands.drain(hand_count.min(self.hands.len())..) {
+            self.total_rake -= hand.total_rake;
+        }
+    }
+
+    pub fn get_total_rake(&self) -> u32 {
+        self.total_rake
+    }
+
+    pub fn get_hand_rake(&self, hand_number: u32) -> Option<&HandRake> {
+        self.hands.iter().find(|hand| hand.hand_number == hand_number)
+    }
+
+    pub fn get_hands(&self) -> &Vec<HandRake> {
+        &self.hands
+    }
+}
@@ -2,8 +2,45 @@ mod action_result;
 mod player_type;
 mod poker_rules;
 mod hand_rank;
+mod hand_value;
+mod forced_bets;
+mod rake_settings;
+mod house_ledger;
+mod game_event;
+mod game_error;
+mod chip_ledger;
+mod dealer_token;
+mod player_view;
+mod game_snapshot;
+mod time_limits;
+mod table_config;
+mod observers;
+mod clocks;
+mod agents;
+mod cfr;
+mod acpc;
+mod stats;
 
 pub use action_result::ActionResult;
 pub use player_type::StandardAutoPlayer;
 pub use poker_rules::PokerRules;
-pub use hand_rank::HandRank;
\ No newline at end of file
+pub use hand_rank::HandRank;
+pub use hand_value::HandValue;
+pub use forced_bets::ForcedBets;
+pub use rake_settings::RakeSettings;
+pub use house_ledger::{HouseLedger, HandRake};
+pub use game_event::{GameEvent, SeatSummary};
+pub use game_error::GameError;
+pub use chip_ledger::{ChipLedger, ChipTransfer};
+pub use dealer_token::DealerToken;
+pub use player_view::{PlayerView, SeatView, PotView};
+pub use game_snapshot::GameSnapshot;
+pub(crate) use game_snapshot::UndoPoint;
+pub use time_limits::TimeLimits;
+pub use table_config::{TableConfig, SeatConfig};
+pub use observers::{GameObserver, ConsoleObserver};
+pub use clocks::{Clock, SystemClock, ManualClock};
+pub use cfr::{ExtensiveGame, CfrTrainer, InfoSetNode, TrainingReport, CfrStrategy, KuhnPoker, KuhnState, LeducPoker, LeducState, LimitHoldem, LimitHoldemState};
+pub use acpc::{AcpcMatchState, AcpcDealer};
+pub use stats::{PlayerStats, StatsTracker};
+pub use agents::{PlayerAgent, CfrAgent, AcpcAgent, ScriptedAgent, ScriptedStep, SubprocessAgent, BotRequest, BotReply, RandomAgent, HumanAgent, RuleBasedAgent, EquityAgent, EquitySettings, Personality, PersonalityState, TiltSettings, AgentRegistry, AgentFactory};
\ No newline at end of file
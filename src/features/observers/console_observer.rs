@@ -0,0 +1,20 @@
+use std::collections::HashMap;
+
+
+
+#[derive(Debug, Clone, Default)]
+pub struct ConsoleObserver {
+    pub usernames: HashMap<u32, String>
+}
+
+impl ConsoleObserver {
+    pub fn new() -> Self {
+        ConsoleObserver::default()
+    }
+
+    pub fn get_username(&self, player_id: u32) -> String {
+        self.usernames.get(&player_id)
+            .cloned()
+            .unwrap_or_else(|| format!("Player {}", player_id))
+    }
+}
@@ -0,0 +1,7 @@
+use crate::features::GameEvent;
+
+
+
+pub trait GameObserver {
+    fn on_event(&mut self, event: &GameEvent);
+}
@@ -0,0 +1,5 @@
+mod game_observer;
+mod console_observer;
+
+pub use game_observer::GameObserver;
+pub use console_observer::ConsoleObserver;
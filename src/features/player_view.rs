@@ -0,0 +1,86 @@
+use crate::card::Card;
+use crate::constants::{HandPhase, PlayerAction, BettingStructure};
+use crate::features::{GameEvent, PlayerStats};
+use serde::{Serialize, Deserialize};
+
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeatView {
+    pub player_id: u32,
+    pub username: String,
+    pub seat: usize,
+    pub coins: u32,
+    pub current_bet: u32,
+    pub total_bet: u32,
+    pub folded: bool,
+    pub all_in: bool,
+    pub sitting_out: bool,
+    // What the viewing player has seen this player do, in this session and saved ones
+    #[serde(default)]
+    pub stats: PlayerStats
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PotView {
+    pub amount: u32,
+    pub eligible_player_ids: Vec<u32>
+}
+
+// Everything one seat is allowed to know. Only that seat's own hole cards are included.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerView {
+    pub hand_number: u32,
+    pub hand_phase: HandPhase,
+    pub player_id: u32,
+    pub seat: usize,
+    pub hole_cards: Vec<Card>,
+    pub board: Vec<Card>,
+    // Decks shuffled together into the shoe, so how many copies of each card there are
+    #[serde(default = "PlayerView::default_deck_count")]
+    pub deck_count: usize,
+    pub seats: Vec<SeatView>,
+    pub pots: Vec<PotView>,
+    pub button_seat: usize,
+    pub small_blind_seat: usize,
+    pub big_blind_seat: usize,
+    pub big_blind: u32,
+    pub betting_structure: BettingStructure,
+    pub highest_bet: u32,
+    pub action_history: Vec<GameEvent>,
+    pub is_to_act: bool,
+    pub legal_actions: Vec<PlayerAction>,
+    pub amount_to_call: u32,
+    // Bet and raise amounts are totals for the street, capped by the all-in amount
+    pub minimum_bet: u32,
+    pub minimum_raise_to: u32,
+    pub maximum_bet_to: u32,
+    // Milliseconds left for this decision including the time bank, None without a time limit
+    #[serde(default)]
+    pub time_left_ms: Option<u64>,
+    // Milliseconds of the session time bank not spent yet
+    #[serde(default)]
+    pub time_bank_left_ms: u64
+}
+
+impl PlayerView {
+    fn default_deck_count() -> usize {
+        1
+    }
+
+    pub fn get_own_seat(&self) -> Option<&SeatView> {
+        self.get_seat_view(self.player_id)
+    }
+
+    pub fn get_seat_view(&self, player_id: u32) -> Option<&SeatView> {
+        self.seats.iter().find(|seat_view| seat_view.player_id == player_id)
+    }
+
+    pub fn get_total_pot(&self) -> u32 {
+        self.pots.iter().map(|pot| pot.amount).sum()
+    }
+
+    pub fn is_legal(&self, action: PlayerAction) -> bool {
+        self.legal_actions.contains(&action)
+    }
+}
@@ -1,3 +1,4 @@
+#[allow(clippy::module_inception)]
 mod poker_rules;
 
 pub use poker_rules::PokerRules;
\ No newline at end of file
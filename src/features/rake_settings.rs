@@ -0,0 +1,33 @@
+use serde::{Serialize, Deserialize};
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RakeSettings {
+    pub percentage: f64,
+    // Largest rake taken from a single pot, None for no cap
+    pub cap: Option<u32>,
+    pub no_flop_no_drop: bool,
+    // (minimum players dealt in, cap) pairs that replace the cap at fuller tables
+    pub player_count_caps: Vec<(usize, u32)>
+}
+
+impl RakeSettings {
+    pub fn get_cap(&self, players_dealt_in: usize) -> Option<u32> {
+        self.player_count_caps.iter()
+            .filter(|(minimum_players, _)| players_dealt_in >= *minimum_players)
+            .max_by_key(|(minimum_players, _)| *minimum_players)
+            .map(|(_, cap)| *cap)
+            .or(self.cap)
+    }
+
+    pub fn calculate_rake(&self, pot_amount: u32, players_dealt_in: usize, flop_dealt: bool) -> u32 {
+        if self.no_flop_no_drop && !flop_dealt {
+            return 0;
+        }
+        let rake = (pot_amount as f64 * self.percentage / 100.0).floor() as u32;
+        match self.get_cap(players_dealt_in) {
+            Some(cap) => std::cmp::min(rake, cap),
+            None => rake,
+        }
+    }
+}
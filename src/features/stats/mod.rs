@@ -0,0 +1,5 @@
+mod player_stats;
+mod stats_tracker;
+
+pub use player_stats::PlayerStats;
+pub use stats_tracker::StatsTracker;
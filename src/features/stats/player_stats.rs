@@ -0,0 +1,86 @@
+use serde::{Serialize, Deserialize};
+
+
+
+// Counts of what one player has been seen doing. Rates are None until there has been a
+// chance to see them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlayerStats {
+    pub hands: u32,
+    // Called, bet or raised before the flop, blinds aside
+    pub voluntary_hands: u32,
+    pub preflop_raises: u32,
+    // Faced a single raise before the flop, and re-raised it
+    pub three_bet_chances: u32,
+    pub three_bets: u32,
+    // Bets and raises, and calls, after the flop
+    pub postflop_aggressive_actions: u32,
+    pub postflop_calls: u32,
+    // Faced a flop bet from the player who made the last raise before the flop, and folded to it
+    pub continuation_bets_faced: u32,
+    pub folds_to_continuation_bet: u32,
+    pub saw_flop: u32,
+    pub went_to_showdown: u32,
+    pub won_at_showdown: u32
+}
+
+impl PlayerStats {
+    fn get_percentage(count: u32, chances: u32) -> Option<f32> {
+        if chances == 0 {
+            None
+        } else {
+            Some(count as f32 * 100.0 / chances as f32)
+        }
+    }
+
+    pub fn get_vpip(&self) -> Option<f32> {
+        Self::get_percentage(self.voluntary_hands, self.hands)
+    }
+
+    pub fn get_pfr(&self) -> Option<f32> {
+        Self::get_percentage(self.preflop_raises, self.hands)
+    }
+
+    pub fn get_three_bet(&self) -> Option<f32> {
+        Self::get_percentage(self.three_bets, self.three_bet_chances)
+    }
+
+    // Bets and raises for every call after the flop
+    pub fn get_aggression_factor(&self) -> Option<f32> {
+        if self.postflop_calls == 0 {
+            None
+        } else {
+            Some(self.postflop_aggressive_actions as f32 / self.postflop_calls as f32)
+        }
+    }
+
+    pub fn get_fold_to_continuation_bet(&self) -> Option<f32> {
+        Self::get_percentage(self.folds_to_continuation_bet, self.continuation_bets_faced)
+    }
+
+    // Went to showdown, out of the hands where the flop was seen
+    pub fn get_wtsd(&self) -> Option<f32> {
+        Self::get_percentage(self.went_to_showdown, self.saw_flop)
+    }
+
+    // Won money at showdown, out of the showdowns reached
+    pub fn get_wsd(&self) -> Option<f32> {
+        Self::get_percentage(self.won_at_showdown, self.went_to_showdown)
+    }
+
+    pub fn add(&mut self, other: &PlayerStats) {
+        self.hands += other.hands;
+        self.voluntary_hands += other.voluntary_hands;
+        self.preflop_raises += other.preflop_raises;
+        self.three_bet_chances += other.three_bet_chances;
+        self.three_bets += other.three_bets;
+        self.postflop_aggressive_actions += other.postflop_aggressive_actions;
+        self.postflop_calls += other.postflop_calls;
+        self.continuation_bets_faced += other.continuation_bets_faced;
+        self.folds_to_continuation_bet += other.folds_to_continuation_bet;
+        self.saw_flop += other.saw_flop;
+        self.went_to_showdown += other.went_to_showdown;
+        self.won_at_showdown += other.won_at_showdown;
+    }
+}
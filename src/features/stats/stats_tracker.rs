@@ -0,0 +1,145 @@
+use crate::constants::PlayerAction;
+use crate::features::{GameEvent, GameError, PlayerStats};
+use serde::{Serialize, Deserialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+
+
+// One player's notes on everyone they have played with, built only from what happened at
+// the table: actions, and the hands shown down. Kept by username so notes carry over to
+// later sessions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatsTracker {
+    players: BTreeMap<String, PlayerStats>
+}
+
+impl StatsTracker {
+    pub fn new() -> Self {
+        StatsTracker::default()
+    }
+
+    pub fn get_stats(&self, username: &str) -> Option<&PlayerStats> {
+        self.players.get(username)
+    }
+
+    pub fn get_all_stats(&self) -> &BTreeMap<String, PlayerStats> {
+        &self.players
+    }
+
+    // Adds one finished hand, given as the events it produced
+    pub fn record_hand(&mut self, hand_history: &[GameEvent]) {
+        let mut usernames: HashMap<u32, String> = HashMap::new();
+        let mut hand_stats: BTreeMap<u32, PlayerStats> = BTreeMap::new();
+        let mut folded_player_ids: Vec<u32> = Vec::new();
+        let mut streets_dealt = 0;
+        let mut preflop_raise_count = 0;
+        let mut preflop_aggressor: Option<u32> = None;
+        let mut three_bet_chance_ids: Vec<u32> = Vec::new();
+        let mut flop_bet_made = false;
+        let mut continuation_bet_open = false;
+        let mut continuation_bet_faced_ids: Vec<u32> = Vec::new();
+        let mut showdown_player_ids: Vec<u32> = Vec::new();
+
+        for event in hand_history.iter() {
+            match event {
+                GameEvent::HandStarted { players, .. } => {
+                    usernames = players.iter().map(|seat_summary| (seat_summary.player_id, seat_summary.username.clone())).collect();
+                },
+                GameEvent::HoleCardsDealt { player_id, .. } => hand_stats.entry(*player_id).or_default().hands = 1,
+                GameEvent::StreetDealt { .. } => {
+                    streets_dealt += 1;
+                    if streets_dealt == 1 {
+                        for (player_id, stats) in hand_stats.iter_mut() {
+                            if !folded_player_ids.contains(player_id) {
+                                stats.saw_flop = 1;
+                            }
+                        }
+                    }
+                },
+                GameEvent::PlayerActed { player_id, action, .. } => {
+                    let aggressive = matches!(action, PlayerAction::Bet | PlayerAction::Raise);
+                    let stats = hand_stats.entry(*player_id).or_default();
+                    if streets_dealt == 0 {
+                        if matches!(action, PlayerAction::Call | PlayerAction::Bet | PlayerAction::Raise) {
+                            stats.voluntary_hands = 1;
+                        }
+                        // The first time a player acts facing just the opening raise is their chance to 3-bet
+                        if preflop_raise_count == 1 && preflop_aggressor != Some(*player_id) && !three_bet_chance_ids.contains(player_id) {
+                            three_bet_chance_ids.push(*player_id);
+                            stats.three_bet_chances = 1;
+                            if aggressive {
+                                stats.three_bets = 1;
+                            }
+                        }
+                        if aggressive {
+                            stats.preflop_raises = 1;
+                            preflop_raise_count += 1;
+                            preflop_aggressor = Some(*player_id);
+                        }
+                    } else {
+                        if aggressive {
+                            stats.postflop_aggressive_actions += 1;
+                        } else if *action == PlayerAction::Call {
+                            stats.postflop_calls += 1;
+                        }
+                        if streets_dealt == 1 {
+                            if continuation_bet_open && preflop_aggressor != Some(*player_id) && !continuation_bet_faced_ids.contains(player_id) {
+                                continuation_bet_faced_ids.push(*player_id);
+                                stats.continuation_bets_faced = 1;
+                                if *action == PlayerAction::Fold {
+                                    stats.folds_to_continuation_bet = 1;
+                                }
+                            }
+                            // Only the first flop bet, made by the last preflop raiser, is a continuation bet
+                            if aggressive {
+                                continuation_bet_open = !flop_bet_made && preflop_aggressor == Some(*player_id);
+                                flop_bet_made = true;
+                            }
+                        }
+                    }
+                    if *action == PlayerAction::Fold {
+                        folded_player_ids.push(*player_id);
+                    }
+                },
+                GameEvent::Showdown { hands } if hands.len() > 1 => {
+                    for (player_id, _, _) in hands.iter() {
+                        hand_stats.entry(*player_id).or_default().went_to_showdown = 1;
+                        showdown_player_ids.push(*player_id);
+                    }
+                },
+                GameEvent::PotAwarded { winners, .. } => {
+                    for (player_id, _) in winners.iter() {
+                        if showdown_player_ids.contains(player_id) {
+                            hand_stats.entry(*player_id).or_default().won_at_showdown = 1;
+                        }
+                    }
+                },
+                _ => {},
+            }
+        }
+
+        for (player_id, stats) in hand_stats {
+            if let Some(username) = usernames.get(&player_id) {
+                self.players.entry(username.clone()).or_default().add(&stats);
+            }
+        }
+    }
+
+    pub fn to_json_file<P: AsRef<Path>>(&self, path: P) -> Result<(), GameError> {
+        let json = serde_json::to_string_pretty(self).map_err(|error| GameError::StatsNotSaved { reason: error.to_string() })?;
+        std::fs::write(path.as_ref(), json)
+            .map_err(|error| GameError::StatsNotSaved { reason: format!("{}: {}", path.as_ref().display(), error) })
+    }
+
+    pub fn from_json_str(json: &str) -> Result<Self, GameError> {
+        serde_json::from_str(json).map_err(|error| GameError::InvalidStats { reason: error.to_string() })
+    }
+
+    pub fn from_json_file<P: AsRef<Path>>(path: P) -> Result<Self, GameError> {
+        let json = std::fs::read_to_string(path.as_ref())
+            .map_err(|error| GameError::InvalidStats { reason: format!("{}: {}", path.as_ref().display(), error) })?;
+        Self::from_json_str(&json)
+    }
+}
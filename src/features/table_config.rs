@@ -0,0 +1,260 @@
+use crate::constants::{BettingStructure, GameVariant};
+use crate::features::{ForcedBets, RakeSettings, TimeLimits, GameError, AgentRegistry};
+use crate::poker_game::PokerGame;
+use crate::card::Card;
+use serde::{Serialize, Deserialize};
+use std::path::Path;
+
+
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeatConfig {
+    pub id: u32,
+    pub username: String,
+    pub coins: u32,
+    // Left empty to take the first free seat
+    #[serde(default)]
+    pub seat: Option<usize>,
+    // Name the agent is registered under in the AgentRegistry
+    #[serde(default = "SeatConfig::default_agent")]
+    pub agent: String,
+    #[serde(default)]
+    pub agent_params: serde_json::Value
+}
+
+impl SeatConfig {
+    fn default_agent() -> String {
+        String::from(crate::features::RandomAgent::AGENT_NAME)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TableConfig {
+    pub max_seats: usize,
+    pub players: Vec<SeatConfig>,
+    pub stakes: ForcedBets,
+    pub betting_structure: BettingStructure,
+    pub variant: GameVariant,
+    pub deck_count: usize,
+    // Shuffles and the starting button are reproducible when set
+    pub rng_seed: Option<u64>,
+    // Short card names like "Ah" dealt in this order on the first hand, for scripted scenarios
+    pub stacked_deck: Vec<String>,
+    pub time_limits: TimeLimits,
+    // Each player's notes on the others are loaded from and saved to this directory
+    pub stats_directory: Option<String>,
+    pub rake: RakeSettings,
+    pub dealer_type: String
+}
+
+impl Default for TableConfig {
+    fn default() -> Self {
+        TableConfig {
+            max_seats: 10,
+            players: Vec::new(),
+            stakes: ForcedBets { small_blind: 1, big_blind: 2, ..ForcedBets::default() },
+            betting_structure: BettingStructure::default(),
+            variant: GameVariant::default(),
+            deck_count: 1,
+            rng_seed: None,
+            stacked_deck: Vec::new(),
+            time_limits: TimeLimits::default(),
+            stats_directory: None,
+            rake: RakeSettings::default(),
+            dealer_type: String::from("auto")
+        }
+    }
+}
+
+impl TableConfig {
+    pub const SEAT_LIMIT: usize = 10;
+    const CARDS_PER_DECK: usize = 52;
+    const COMMUNITY_CARDS: usize = 5;
+
+    pub fn new() -> Self {
+        TableConfig::default()
+    }
+
+    pub fn with_max_seats(mut self, max_seats: usize) -> Self {
+        self.max_seats = max_seats;
+        self
+    }
+
+    pub fn with_player(mut self, id: u32, username: &str, coins: u32) -> Self {
+        self.players.push(SeatConfig {
+            id,
+            username: String::from(username),
+            coins,
+            seat: None,
+            agent: SeatConfig::default_agent(),
+            agent_params: serde_json::Value::Null
+        });
+        self
+    }
+
+    pub fn with_agent_player(mut self, id: u32, username: &str, coins: u32, agent: &str, agent_params: serde_json::Value) -> Self {
+        self.players.push(SeatConfig {
+            id,
+            username: String::from(username),
+            coins,
+            seat: None,
+            agent: String::from(agent),
+            agent_params
+        });
+        self
+    }
+
+    pub fn with_player_at_seat(mut self, id: u32, username: &str, coins: u32, seat: usize) -> Self {
+        self.players.push(SeatConfig {
+            id,
+            username: String::from(username),
+            coins,
+            seat: Some(seat),
+            agent: SeatConfig::default_agent(),
+            agent_params: serde_json::Value::Null
+        });
+        self
+    }
+
+    pub fn with_blinds(mut self, small_blind: u32, big_blind: u32) -> Self {
+        self.stakes.small_blind = small_blind;
+        self.stakes.big_blind = big_blind;
+        self
+    }
+
+    pub fn with_stakes(mut self, stakes: ForcedBets) -> Self {
+        self.stakes = stakes;
+        self
+    }
+
+    pub fn with_betting_structure(mut self, betting_structure: BettingStructure) -> Self {
+        self.betting_structure = betting_structure;
+        self
+    }
+
+    pub fn with_variant(mut self, variant: GameVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    pub fn with_deck_count(mut self, deck_count: usize) -> Self {
+        self.deck_count = deck_count;
+        self
+    }
+
+    pub fn with_rng_seed(mut self, rng_seed: u64) -> Self {
+        self.rng_seed = Some(rng_seed);
+        self
+    }
+
+    pub fn with_stacked_deck(mut self, short_names: &[&str]) -> Self {
+        self.stacked_deck = short_names.iter().map(|short_name| String::from(*short_name)).collect();
+        self
+    }
+
+    pub fn get_stacked_cards(&self) -> Result<Vec<Card>, GameError> {
+        self.stacked_deck.iter()
+            .map(|short_name| Card::from_short_name(short_name)
+                .ok_or(GameError::InvalidConfig { reason: format!("{:?} is not a card, use short names like \"Ah\" or \"Td\"", short_name) }))
+            .collect()
+    }
+
+    pub fn with_time_limits(mut self, time_limits: TimeLimits) -> Self {
+        self.time_limits = time_limits;
+        self
+    }
+
+    pub fn with_stats_directory(mut self, stats_directory: &str) -> Self {
+        self.stats_directory = Some(String::from(stats_directory));
+        self
+    }
+
+    pub fn with_rake(mut self, rake: RakeSettings) -> Self {
+        self.rake = rake;
+        self
+    }
+
+    pub fn from_json_str(json: &str) -> Result<Self, GameError> {
+        serde_json::from_str(json).map_err(|error| GameError::InvalidConfig { reason: error.to_string() })
+    }
+
+    pub fn from_json_file<P: AsRef<Path>>(path: P) -> Result<Self, GameError> {
+        let json = std::fs::read_to_string(path.as_ref())
+            .map_err(|error| GameError::InvalidConfig { reason: format!("{}: {}", path.as_ref().display(), error) })?;
+        Self::from_json_str(&json)
+    }
+
+    pub fn validate(&self) -> Result<(), GameError> {
+        let invalid = |reason: String| Err(GameError::InvalidConfig { reason });
+
+        if self.max_seats < 2 || self.max_seats > Self::SEAT_LIMIT {
+            return invalid(format!("max_seats must be between 2 and {}", Self::SEAT_LIMIT));
+        }
+        if self.players.len() < 2 {
+            return invalid(String::from("at least two players are needed"));
+        }
+        if self.players.len() > self.max_seats {
+            return invalid(format!("{} players do not fit in {} seats", self.players.len(), self.max_seats));
+        }
+
+        let mut player_ids: Vec<u32> = Vec::new();
+        let mut taken_seats: Vec<usize> = Vec::new();
+        for seat_config in &self.players {
+            if player_ids.contains(&seat_config.id) {
+                return invalid(format!("player id {} is used more than once", seat_config.id));
+            }
+            player_ids.push(seat_config.id);
+            if seat_config.coins == 0 {
+                return invalid(format!("{} has no coins to play with", seat_config.username));
+            }
+            if let Some(seat) = seat_config.seat {
+                if seat >= self.max_seats {
+                    return invalid(format!("seat {} is outside a {} seat table", seat, self.max_seats));
+                }
+                if taken_seats.contains(&seat) {
+                    return invalid(format!("seat {} is assigned more than once", seat));
+                }
+                taken_seats.push(seat);
+            }
+        }
+
+        let stakes = &self.stakes;
+        let has_other_forced_bets = stakes.small_blind > 0 || stakes.ante > 0 || stakes.big_blind_ante > 0 || stakes.straddle.is_some();
+        if stakes.big_blind == 0 && has_other_forced_bets {
+            return invalid(String::from("forced bets are only posted in games with a big blind"));
+        }
+        if stakes.small_blind > stakes.big_blind {
+            return invalid(String::from("small blind cannot be larger than the big blind"));
+        }
+        if self.betting_structure == BettingStructure::FixedLimit && stakes.big_blind == 0 {
+            return invalid(String::from("fixed limit bet sizes come from the big blind"));
+        }
+
+        if self.deck_count == 0 {
+            return invalid(String::from("at least one deck is needed"));
+        }
+        let cards_needed = self.players.len() * 2 + Self::COMMUNITY_CARDS;
+        if self.deck_count * Self::CARDS_PER_DECK < cards_needed {
+            return invalid(format!("{} decks cannot deal {} cards", self.deck_count, cards_needed));
+        }
+        PokerGame::check_stacked_cards(self.deck_count, &self.get_stacked_cards()?)?;
+
+        if self.time_limits.action_seconds == Some(0) {
+            return invalid(String::from("action time limit must be at least one second"));
+        }
+        if !(0.0..=100.0).contains(&self.rake.percentage) {
+            return invalid(String::from("rake percentage must be between 0 and 100"));
+        }
+        Ok(())
+    }
+
+    pub fn build(self) -> Result<PokerGame, GameError> {
+        self.build_with_registry(&AgentRegistry::with_defaults())
+    }
+
+    pub fn build_with_registry(self, registry: &AgentRegistry) -> Result<PokerGame, GameError> {
+        self.validate()?;
+        PokerGame::from_table_config(self, registry)
+    }
+}
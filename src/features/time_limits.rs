@@ -0,0 +1,12 @@
+use serde::{Serialize, Deserialize};
+
+
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimeLimits {
+    // Seconds a player has for each decision, None for no limit
+    pub action_seconds: Option<u64>,
+    // Extra seconds a player can draw on over the whole session once the action clock runs out
+    pub time_bank_seconds: u64
+}
@@ -0,0 +1,26 @@
+use crate::features::{PlayerAgent, AcpcAgent, ActionResult, PlayerView};
+use crate::constants::PlayerAction;
+
+
+
+impl PlayerAgent for AcpcAgent {
+    fn get_agent_name(&self) -> &str {
+        AcpcAgent::AGENT_NAME
+    }
+
+    fn decide(&mut self, player_view: &PlayerView) -> ActionResult {
+        if player_view.legal_actions.is_empty() {
+            // It is not this seat's turn, so the client is not asked
+            return ActionResult { action: PlayerAction::Fold, success: false, amount: None };
+        }
+        self.request_action(player_view)
+    }
+
+    fn on_hand_end(&mut self, player_view: &PlayerView) {
+        self.send_final_state(player_view);
+    }
+
+    fn take_problems(&mut self) -> Vec<String> {
+        self.take_new_problems()
+    }
+}
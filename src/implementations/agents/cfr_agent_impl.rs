@@ -0,0 +1,22 @@
+use crate::features::{PlayerAgent, CfrAgent, RuleBasedAgent, ActionResult, PlayerView};
+use crate::constants::PlayerAction;
+
+
+
+impl PlayerAgent for CfrAgent {
+    fn get_agent_name(&self) -> &str {
+        CfrAgent::AGENT_NAME
+    }
+
+    fn decide(&mut self, player_view: &PlayerView) -> ActionResult {
+        if player_view.legal_actions.is_empty() {
+            return ActionResult { action: PlayerAction::Fold, success: false, amount: None };
+        }
+
+        match self.choose_action(player_view) {
+            // Limit bets have one size, so the smallest legal amount is the bet
+            Some(action) => RuleBasedAgent::make_legal(player_view, action, None),
+            None => self.get_fallback_agent().decide(player_view),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use crate::features::{PlayerAgent, EquityAgent, RuleBasedAgent, ActionResult, PlayerView};
+use crate::constants::PlayerAction;
+
+
+
+impl PlayerAgent for EquityAgent {
+    fn get_agent_name(&self) -> &str {
+        EquityAgent::AGENT_NAME
+    }
+
+    fn decide(&mut self, player_view: &PlayerView) -> ActionResult {
+        if player_view.legal_actions.is_empty() {
+            return ActionResult { action: PlayerAction::Fold, success: false, amount: None };
+        }
+
+        if self.is_outside_preflop_range(player_view) {
+            return RuleBasedAgent::make_legal(player_view, PlayerAction::Fold, None);
+        }
+        let opponents = player_view.seats.iter()
+            .filter(|seat_view| seat_view.player_id != player_view.player_id && !seat_view.folded && !seat_view.sitting_out)
+            .count();
+        let equity = self.estimate_equity(&player_view.hole_cards, &player_view.board, opponents, player_view.deck_count);
+        let (action, amount) = self.decide_by_value(player_view, equity, opponents);
+        RuleBasedAgent::make_legal(player_view, action, amount)
+    }
+
+    fn on_hand_end(&mut self, player_view: &PlayerView) {
+        self.observe_hand_end(player_view);
+    }
+}
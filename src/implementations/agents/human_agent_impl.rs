@@ -0,0 +1,30 @@
+use crate::features::{PlayerAgent, HumanAgent, ActionResult, PlayerView};
+use crate::constants::PlayerAction;
+
+
+
+impl PlayerAgent for HumanAgent {
+    fn get_agent_name(&self) -> &str {
+        HumanAgent::AGENT_NAME
+    }
+
+    fn decide(&mut self, player_view: &PlayerView) -> ActionResult {
+        if player_view.legal_actions.is_empty() {
+            return ActionResult { action: PlayerAction::Fold, success: false, amount: None };
+        }
+
+        self.write_text(&HumanAgent::describe_view(player_view));
+        loop {
+            self.write_text("> ");
+            let command = match self.read_line() {
+                Some(command) => command,
+                // The player left the terminal, so no decision can be made
+                None => return ActionResult { action: PlayerAction::Fold, success: false, amount: None },
+            };
+            match HumanAgent::parse_command(&command, player_view) {
+                Ok(action_result) => return action_result,
+                Err(reason) => self.write_text(&format!("{}\n", reason)),
+            }
+        }
+    }
+}
@@ -0,0 +1,8 @@
+mod random_agent_impl;
+mod human_agent_impl;
+mod rule_based_agent_impl;
+mod equity_agent_impl;
+mod cfr_agent_impl;
+mod scripted_agent_impl;
+mod subprocess_agent_impl;
+mod acpc_agent_impl;
@@ -0,0 +1,51 @@
+use crate::features::{PlayerAgent, RandomAgent, ActionResult, PlayerView};
+use crate::constants::{PlayerAction, HandPhase};
+use rand::Rng;
+
+
+
+impl PlayerAgent for RandomAgent {
+    fn get_agent_name(&self) -> &str {
+        RandomAgent::AGENT_NAME
+    }
+
+    fn decide(&mut self, player_view: &PlayerView) -> ActionResult {
+        if player_view.legal_actions.is_empty() {
+            // It is not this seat's turn, so there is nothing to choose from
+            return ActionResult { action: PlayerAction::Fold, success: false, amount: None };
+        }
+
+        let mut rng = rand::thread_rng();
+        let valid_actions = &player_view.legal_actions;
+        let chosen_action = match self.get_current_personality() {
+            Some(personality) => {
+                let preflop = player_view.hand_phase == HandPhase::PreFlop;
+                // Before the flop vpip decides whether to play and pfr how often that means raising.
+                // Afterwards the aggression factor decides between betting and calling.
+                let continue_chance = if preflop { personality.vpip / 100.0 } else { (personality.vpip / 100.0 + 0.3).min(1.0) };
+                let aggression_chance = if preflop && personality.vpip > 0.0 { personality.pfr / personality.vpip } else { personality.get_aggression_chance() };
+                let aggressive_action = [PlayerAction::Raise, PlayerAction::Bet].into_iter().find(|&action| player_view.is_legal(action));
+                let passive_action = [PlayerAction::Check, PlayerAction::Call].into_iter().find(|&action| player_view.is_legal(action));
+                let can_check = player_view.is_legal(PlayerAction::Check);
+
+                if !can_check && rng.gen::<f32>() >= continue_chance {
+                    PlayerAction::Fold
+                } else if rng.gen::<f32>() < aggression_chance {
+                    aggressive_action.or(passive_action).unwrap_or(PlayerAction::Fold)
+                } else {
+                    passive_action.or(aggressive_action).unwrap_or(PlayerAction::Fold)
+                }
+            },
+            None => valid_actions[rng.gen_range(0..valid_actions.len())],
+        };
+        match chosen_action {
+            PlayerAction::Bet => ActionResult { action: PlayerAction::Bet, success: true, amount: Some(self.get_bet_amount(player_view)) },
+            PlayerAction::Raise => ActionResult { action: PlayerAction::Raise, success: true, amount: Some(self.get_raise_amount(player_view)) },
+            _ => ActionResult { action: chosen_action, success: true, amount: None },
+        }
+    }
+
+    fn on_hand_end(&mut self, player_view: &PlayerView) {
+        self.observe_hand_end(player_view);
+    }
+}
@@ -0,0 +1,27 @@
+use crate::features::{PlayerAgent, RuleBasedAgent, ActionResult, PlayerView};
+use crate::constants::{PlayerAction, HandPhase};
+
+
+
+impl PlayerAgent for RuleBasedAgent {
+    fn get_agent_name(&self) -> &str {
+        RuleBasedAgent::AGENT_NAME
+    }
+
+    fn decide(&mut self, player_view: &PlayerView) -> ActionResult {
+        if player_view.legal_actions.is_empty() {
+            return ActionResult { action: PlayerAction::Fold, success: false, amount: None };
+        }
+
+        let (action, amount) = if player_view.hand_phase == HandPhase::PreFlop || player_view.board.is_empty() {
+            self.decide_preflop(player_view)
+        } else {
+            self.decide_postflop(player_view)
+        };
+        RuleBasedAgent::make_legal(player_view, action, amount)
+    }
+
+    fn on_hand_end(&mut self, player_view: &PlayerView) {
+        self.observe_hand_end(player_view);
+    }
+}
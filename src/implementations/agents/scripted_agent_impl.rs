@@ -0,0 +1,20 @@
+use crate::features::{PlayerAgent, ScriptedAgent, ActionResult, PlayerView};
+use crate::constants::PlayerAction;
+
+
+
+impl PlayerAgent for ScriptedAgent {
+    fn get_agent_name(&self) -> &str {
+        ScriptedAgent::AGENT_NAME
+    }
+
+    fn decide(&mut self, player_view: &PlayerView) -> ActionResult {
+        if player_view.legal_actions.is_empty() {
+            // It is not this seat's turn, so no step is used up
+            return ActionResult { action: PlayerAction::Fold, success: false, amount: None };
+        }
+
+        let step = self.take_step(player_view);
+        ActionResult { action: step.action, success: true, amount: step.amount }
+    }
+}
@@ -0,0 +1,30 @@
+use crate::features::{PlayerAgent, SubprocessAgent, ActionResult, PlayerView};
+use crate::constants::PlayerAction;
+
+
+
+impl PlayerAgent for SubprocessAgent {
+    fn get_agent_name(&self) -> &str {
+        SubprocessAgent::AGENT_NAME
+    }
+
+    fn decide(&mut self, player_view: &PlayerView) -> ActionResult {
+        if player_view.legal_actions.is_empty() {
+            // It is not this seat's turn, so the bot is not asked
+            return ActionResult { action: PlayerAction::Fold, success: false, amount: None };
+        }
+        self.request_action(player_view)
+    }
+
+    fn take_problems(&mut self) -> Vec<String> {
+        self.take_new_problems()
+    }
+}
+
+impl Drop for SubprocessAgent {
+    fn drop(&mut self) {
+        // Closing stdin lets a well-behaved bot exit, the kill covers the rest
+        self.close_input();
+        self.stop_process();
+    }
+}
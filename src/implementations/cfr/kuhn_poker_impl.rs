@@ -0,0 +1,72 @@
+use crate::features::{ExtensiveGame, KuhnPoker, KuhnState};
+use rand::Rng;
+use rand::rngs::StdRng;
+
+
+
+impl ExtensiveGame for KuhnPoker {
+    type State = KuhnState;
+
+    fn get_game_name(&self) -> &str {
+        KuhnPoker::GAME_NAME
+    }
+
+    fn get_initial_state(&self) -> KuhnState {
+        KuhnState { cards: Vec::new(), history: String::new() }
+    }
+
+    fn is_terminal(&self, state: &KuhnState) -> bool {
+        matches!(state.history.as_str(), "pp" | "bp" | "bb" | "pbp" | "pbb")
+    }
+
+    fn get_payoff(&self, state: &KuhnState, player: usize) -> f64 {
+        let player_zero_wins = state.cards[0] > state.cards[1];
+        let player_zero_payoff = match state.history.as_str() {
+            "bp" => 1.0,
+            "pbp" => -1.0,
+            "pp" if player_zero_wins => 1.0,
+            "pp" => -1.0,
+            _ if player_zero_wins => 2.0,
+            _ => -2.0,
+        };
+        if player == 0 { player_zero_payoff } else { -player_zero_payoff }
+    }
+
+    fn is_chance_node(&self, state: &KuhnState) -> bool {
+        state.cards.len() < 2
+    }
+
+    fn get_chance_outcomes(&self, state: &KuhnState) -> Option<Vec<(String, f64)>> {
+        let remaining_cards: Vec<usize> = (0..KuhnPoker::CARD_NAMES.len()).filter(|card| !state.cards.contains(card)).collect();
+        let probability = 1.0 / remaining_cards.len() as f64;
+        Some(remaining_cards.iter().map(|card| (card.to_string(), probability)).collect())
+    }
+
+    fn sample_chance_outcome(&self, state: &KuhnState, rng: &mut StdRng) -> String {
+        let remaining_cards: Vec<usize> = (0..KuhnPoker::CARD_NAMES.len()).filter(|card| !state.cards.contains(card)).collect();
+        remaining_cards[rng.gen_range(0..remaining_cards.len())].to_string()
+    }
+
+    fn get_current_player(&self, state: &KuhnState) -> usize {
+        state.history.len() % 2
+    }
+
+    fn get_legal_actions(&self, _state: &KuhnState) -> Vec<String> {
+        vec![String::from("p"), String::from("b")]
+    }
+
+    fn get_info_set_key(&self, state: &KuhnState) -> String {
+        let player = self.get_current_player(state);
+        format!("{}:{}", KuhnPoker::CARD_NAMES[state.cards[player]], state.history)
+    }
+
+    fn apply_action(&self, state: &KuhnState, action: &str) -> KuhnState {
+        let mut next_state = state.clone();
+        if self.is_chance_node(state) {
+            next_state.cards.push(action.parse().unwrap_or_default());
+        } else {
+            next_state.history.push_str(action);
+        }
+        next_state
+    }
+}
@@ -0,0 +1,100 @@
+use crate::features::{ExtensiveGame, LeducPoker, LeducState};
+use rand::Rng;
+use rand::rngs::StdRng;
+
+
+
+impl ExtensiveGame for LeducPoker {
+    type State = LeducState;
+
+    fn get_game_name(&self) -> &str {
+        LeducPoker::GAME_NAME
+    }
+
+    fn get_initial_state(&self) -> LeducState {
+        LeducState { private_cards: Vec::new(), public_card: None, rounds: vec![String::new()] }
+    }
+
+    fn is_terminal(&self, state: &LeducState) -> bool {
+        LeducPoker::get_folded_player(state).is_some()
+            || (state.rounds.len() == 2 && LeducPoker::is_round_over(&state.rounds[1]))
+    }
+
+    fn get_payoff(&self, state: &LeducState, player: usize) -> f64 {
+        let contributions = LeducPoker::get_contributions(state);
+        let opponent = 1 - player;
+        if let Some(folded_player) = LeducPoker::get_folded_player(state) {
+            return if folded_player == player { -contributions[player] } else { contributions[opponent] };
+        }
+
+        let public_rank = state.public_card.map(LeducPoker::get_rank);
+        // A pair beats any unpaired card, and otherwise the higher rank wins
+        let get_strength = |card: usize| {
+            let rank = LeducPoker::get_rank(card);
+            if Some(rank) == public_rank { 10 + rank } else { rank }
+        };
+        let own_strength = get_strength(state.private_cards[player]);
+        let opponent_strength = get_strength(state.private_cards[opponent]);
+        if own_strength > opponent_strength {
+            contributions[opponent]
+        } else if own_strength < opponent_strength {
+            -contributions[player]
+        } else {
+            0.0
+        }
+    }
+
+    fn is_chance_node(&self, state: &LeducState) -> bool {
+        state.private_cards.len() < 2
+            || (state.rounds.len() == 1 && LeducPoker::is_round_over(&state.rounds[0]) && LeducPoker::get_folded_player(state).is_none())
+    }
+
+    fn get_chance_outcomes(&self, state: &LeducState) -> Option<Vec<(String, f64)>> {
+        let remaining_cards = LeducPoker::get_remaining_cards(state);
+        let probability = 1.0 / remaining_cards.len() as f64;
+        Some(remaining_cards.iter().map(|card| (card.to_string(), probability)).collect())
+    }
+
+    fn sample_chance_outcome(&self, state: &LeducState, rng: &mut StdRng) -> String {
+        let remaining_cards = LeducPoker::get_remaining_cards(state);
+        remaining_cards[rng.gen_range(0..remaining_cards.len())].to_string()
+    }
+
+    fn get_current_player(&self, state: &LeducState) -> usize {
+        state.rounds.last().map_or(0, |round| round.len() % 2)
+    }
+
+    fn get_legal_actions(&self, state: &LeducState) -> Vec<String> {
+        let round = state.rounds.last().cloned().unwrap_or_default();
+        let raises = round.matches('r').count();
+        let mut legal_actions: Vec<String> = Vec::new();
+        if round.ends_with('r') {
+            legal_actions.push(String::from("f"));
+        }
+        legal_actions.push(String::from("c"));
+        if raises < LeducPoker::MAX_RAISES {
+            legal_actions.push(String::from("r"));
+        }
+        legal_actions
+    }
+
+    fn get_info_set_key(&self, state: &LeducState) -> String {
+        let player = self.get_current_player(state);
+        let private_rank = LeducPoker::RANK_NAMES[LeducPoker::get_rank(state.private_cards[player])];
+        let public_rank = state.public_card.map_or("", |card| LeducPoker::RANK_NAMES[LeducPoker::get_rank(card)]);
+        format!("{}{}:{}", private_rank, public_rank, state.rounds.join("/"))
+    }
+
+    fn apply_action(&self, state: &LeducState, action: &str) -> LeducState {
+        let mut next_state = state.clone();
+        if state.private_cards.len() < 2 {
+            next_state.private_cards.push(action.parse().unwrap_or_default());
+        } else if self.is_chance_node(state) {
+            next_state.public_card = Some(action.parse().unwrap_or_default());
+            next_state.rounds.push(String::new());
+        } else if let Some(round) = next_state.rounds.last_mut() {
+            round.push_str(action);
+        }
+        next_state
+    }
+}
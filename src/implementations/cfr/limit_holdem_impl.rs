@@ -0,0 +1,117 @@
+use crate::features::{ExtensiveGame, LimitHoldem, LimitHoldemState, PokerRules};
+use crate::PokerGame;
+use rand::seq::index::sample;
+use rand::rngs::StdRng;
+
+
+
+impl ExtensiveGame for LimitHoldem {
+    type State = LimitHoldemState;
+
+    fn get_game_name(&self) -> &str {
+        LimitHoldem::GAME_NAME
+    }
+
+    fn get_initial_state(&self) -> LimitHoldemState {
+        LimitHoldemState { hole_cards: Vec::new(), board: Vec::new(), rounds: Vec::new() }
+    }
+
+    fn is_terminal(&self, state: &LimitHoldemState) -> bool {
+        LimitHoldem::get_folded_player(state).is_some()
+            || (state.rounds.len() == 4 && LimitHoldem::is_round_over(&state.rounds[3]))
+    }
+
+    fn get_payoff(&self, state: &LimitHoldemState, player: usize) -> f64 {
+        let contributions = LimitHoldem::get_contributions(state);
+        let opponent = 1 - player;
+        if let Some(folded_player) = LimitHoldem::get_folded_player(state) {
+            return if folded_player == player { -contributions[player] } else { contributions[opponent] };
+        }
+
+        let get_hand_value = |hand_player: usize| {
+            let mut card_indexes = state.hole_cards[hand_player].clone();
+            card_indexes.extend(state.board.iter());
+            PokerGame::evaluate_hand(&LimitHoldem::get_cards(&card_indexes))
+        };
+        let own_value = get_hand_value(player);
+        let opponent_value = get_hand_value(opponent);
+        if own_value > opponent_value {
+            contributions[opponent]
+        } else if own_value < opponent_value {
+            -contributions[player]
+        } else {
+            0.0
+        }
+    }
+
+    fn is_chance_node(&self, state: &LimitHoldemState) -> bool {
+        if state.hole_cards.is_empty() {
+            return true;
+        }
+        let street_over = state.rounds.last().is_some_and(|round| LimitHoldem::is_round_over(round));
+        street_over && state.rounds.len() < 4 && LimitHoldem::get_folded_player(state).is_none()
+    }
+
+    // There are far too many deals to list, so this game trains with the sampling variants only
+    fn get_chance_outcomes(&self, _state: &LimitHoldemState) -> Option<Vec<(String, f64)>> {
+        None
+    }
+
+    // The cards for the next deal as deck indexes: four hole cards, then the flop, turn and river
+    fn sample_chance_outcome(&self, state: &LimitHoldemState, rng: &mut StdRng) -> String {
+        let used_cards: Vec<usize> = state.hole_cards.iter().flatten().chain(state.board.iter()).cloned().collect();
+        let unused_cards: Vec<usize> = (0..52).filter(|card| !used_cards.contains(card)).collect();
+        let cards_needed = if state.hole_cards.is_empty() { 4 } else { LimitHoldem::STREET_CARDS[state.rounds.len()] };
+        sample(rng, unused_cards.len(), cards_needed).iter()
+            .map(|position| unused_cards[position].to_string())
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+
+    fn get_current_player(&self, state: &LimitHoldemState) -> usize {
+        let round_index = state.rounds.len().saturating_sub(1);
+        let actions_taken = state.rounds.last().map_or(0, |round| round.len());
+        (LimitHoldem::get_first_player(round_index) + actions_taken) % 2
+    }
+
+    fn get_legal_actions(&self, state: &LimitHoldemState) -> Vec<String> {
+        let round_index = state.rounds.len().saturating_sub(1);
+        let round = state.rounds.last().cloned().unwrap_or_default();
+        let player = self.get_current_player(state);
+        let contributions = LimitHoldem::get_contributions(state);
+        let mut legal_actions: Vec<String> = Vec::new();
+        if contributions[player] < contributions[1 - player] {
+            legal_actions.push(String::from("f"));
+        }
+        legal_actions.push(String::from("c"));
+        if LimitHoldem::get_raises_left(round_index, &round) > 0 {
+            legal_actions.push(String::from("r"));
+        }
+        legal_actions
+    }
+
+    fn get_info_set_key(&self, state: &LimitHoldemState) -> String {
+        let player = self.get_current_player(state);
+        LimitHoldem::get_info_set_key_for(
+            &LimitHoldem::get_cards(&state.hole_cards[player]),
+            &LimitHoldem::get_cards(&state.board),
+            &state.rounds,
+        )
+    }
+
+    fn apply_action(&self, state: &LimitHoldemState, action: &str) -> LimitHoldemState {
+        let mut next_state = state.clone();
+        if self.is_chance_node(state) {
+            let dealt_cards: Vec<usize> = action.split(',').filter_map(|card| card.parse().ok()).collect();
+            if state.hole_cards.is_empty() {
+                next_state.hole_cards = dealt_cards.chunks(2).map(|hand| hand.to_vec()).collect();
+            } else {
+                next_state.board.extend(dealt_cards);
+            }
+            next_state.rounds.push(String::new());
+        } else if let Some(round) = next_state.rounds.last_mut() {
+            round.push_str(action);
+        }
+        next_state
+    }
+}
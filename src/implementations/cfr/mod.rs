@@ -0,0 +1,3 @@
+mod kuhn_poker_impl;
+mod leduc_poker_impl;
+mod limit_holdem_impl;
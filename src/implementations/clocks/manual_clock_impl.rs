@@ -0,0 +1,10 @@
+use crate::features::{Clock, ManualClock};
+use std::time::Duration;
+
+
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.elapsed.get()
+    }
+}
@@ -0,0 +1,2 @@
+mod system_clock_impl;
+mod manual_clock_impl;
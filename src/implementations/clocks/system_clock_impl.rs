@@ -0,0 +1,10 @@
+use crate::features::{Clock, SystemClock};
+use std::time::Duration;
+
+
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+}
@@ -1,2 +1,6 @@
 mod player_type;
 mod poker_rules;
+mod observers;
+mod clocks;
+mod agents;
+mod cfr;
@@ -0,0 +1,68 @@
+use crate::features::{GameEvent, GameObserver, ConsoleObserver};
+
+
+
+impl GameObserver for ConsoleObserver {
+    fn on_event(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::HandStarted { hand_number, button_seat, players } => {
+                for player in players {
+                    self.usernames.insert(player.player_id, player.username.clone());
+                }
+                println!("===================================== Hand {} ============================================", hand_number);
+                println!("Button is at seat {}", button_seat);
+            },
+            GameEvent::BlindsPosted { player_id, blind_type, amount } => {
+                println!("{:?} posts {:?} of {}", self.get_username(*player_id), blind_type, amount);
+            },
+            GameEvent::HoleCardsDealt { player_id, card_count } => {
+                println!("{:?} is dealt {} cards", self.get_username(*player_id), card_count);
+            },
+            GameEvent::PlayerActed { player_id, action, amount, all_in } => {
+                let all_in_note = if *all_in { " and is all-in" } else { "" };
+                println!("{:?} chose to {:?} {}{}", self.get_username(*player_id), action, amount, all_in_note);
+            },
+            GameEvent::ActionRejected { player_id, action, amount, reason, replaced_with } => {
+                let amount_note = amount.map(|amount| format!(" {}", amount)).unwrap_or_default();
+                println!("Invalid {:?}{} from {:?}: {}", action, amount_note, self.get_username(*player_id), reason);
+                if let Some(replaced_with) = replaced_with {
+                    println!("{:?} is out of attempts and will {:?}", self.get_username(*player_id), replaced_with);
+                }
+            },
+            GameEvent::AgentProblem { problem, .. } => {
+                println!("{}", problem);
+            },
+            GameEvent::StreetDealt { hand_phase, cards, board } => {
+                println!("{:?}: {:?}", hand_phase, cards);
+                println!("Board: {:?}", board);
+            },
+            GameEvent::Showdown { hands } => {
+                println!("ShowHands");
+                for (player_id, cards, hand_value) in hands {
+                    println!("{:?} shows {:?} for {}", self.get_username(*player_id), cards, hand_value.get_category_name());
+                }
+            },
+            GameEvent::UncalledBetReturned { player_id, amount } => {
+                println!("Returned uncalled bet of {} to {:?}", amount, self.get_username(*player_id));
+            },
+            GameEvent::PotAwarded { amount, rake, winners } => {
+                let winner_names: Vec<String> = winners.iter()
+                    .map(|(player_id, winnings)| format!("{} ({})", self.get_username(*player_id), winnings))
+                    .collect();
+                println!("Pot of {} (rake {}) awarded to {}", amount, rake, winner_names.join(", "));
+            },
+            GameEvent::HandEnded { hand_number, total_rake } => {
+                println!("Hand {} is over. Rake: {}", hand_number, total_rake);
+            },
+            GameEvent::PlayerLeft { username, .. } => {
+                println!("{:?} is out of coins and leaves the table.", username);
+            },
+            GameEvent::PlayerTimedOut { player_id, action } => {
+                println!("{:?} ran out of time and will {:?}", self.get_username(*player_id), action);
+            },
+            GameEvent::PlayerDisconnected { player_id } => {
+                println!("{:?} disconnected and will sit out from the next hand", self.get_username(*player_id));
+            },
+        }
+    }
+}
@@ -0,0 +1,2 @@
+mod console_observer_impl;
+mod shared_observer_impl;
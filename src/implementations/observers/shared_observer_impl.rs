@@ -0,0 +1,12 @@
+use crate::features::{GameEvent, GameObserver};
+use std::rc::Rc;
+use std::cell::RefCell;
+
+
+
+// Lets a caller keep a handle to an observer after subscribing it to a game
+impl<T: GameObserver> GameObserver for Rc<RefCell<T>> {
+    fn on_event(&mut self, event: &GameEvent) {
+        self.borrow_mut().on_event(event);
+    }
+}
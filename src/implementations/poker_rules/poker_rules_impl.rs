@@ -1,5 +1,5 @@
 use crate::card::{Card};
-use crate::features::{PokerRules, StandardAutoPlayer, HandRank};
+use crate::features::{PokerRules, HandRank, HandValue};
 use crate::player::Player;
 use crate::dealer::Dealer;
 use crate::PokerGame;
@@ -8,16 +8,16 @@ use crate::PokerGame;
 
 
 impl PokerRules for PokerGame {
-    fn get_flush(hand: &Vec<Card>) -> (bool, Vec<Card>) {
-        let mut sorted_hand = hand.clone();
+    fn get_flush(hand: &[Card]) -> (bool, Vec<Card>) {
+        let mut sorted_hand = hand.to_vec();
         sorted_hand.sort_by_key(|card| String::from(card.get_rank()).clone());
-        let first_suit = sorted_hand[0].get_suit().clone();
+        let first_suit = sorted_hand[0].get_suit();
         let is_flush = sorted_hand.iter().all(|card| card.get_suit() == first_suit);
         (is_flush, sorted_hand)
     }
     
-    fn get_straight(hand: &Vec<Card>) -> (bool, Vec<Card>) {
-        let mut sorted_hand = hand.clone();
+    fn get_straight(hand: &[Card]) -> (bool, Vec<Card>) {
+        let mut sorted_hand = hand.to_vec();
         sorted_hand.sort_by_key(|card| String::from(card.get_rank()).clone());
         let mut is_straight = false;
         if sorted_hand.len() >= 5 {
@@ -45,8 +45,8 @@ impl PokerRules for PokerGame {
         (is_straight, sorted_hand)
     }
 
-    fn get_four_of_a_kind(hand: &Vec<Card>) -> (bool, Vec<Card>) {
-        let mut sorted_hand = hand.clone();
+    fn get_four_of_a_kind(hand: &[Card]) -> (bool, Vec<Card>) {
+        let mut sorted_hand = hand.to_vec();
         sorted_hand.sort_by_key(|card| String::from(card.get_rank()).clone());
         let ranks: Vec<u32> = sorted_hand
             .iter()
@@ -67,8 +67,8 @@ impl PokerRules for PokerGame {
         (false, sorted_hand)
     }
 
-    fn get_full_house(hand: &Vec<Card>) -> (bool, Vec<Card>) {
-        let mut sorted_hand = hand.clone();
+    fn get_full_house(hand: &[Card]) -> (bool, Vec<Card>) {
+        let mut sorted_hand = hand.to_vec();
         sorted_hand.sort_by_key(|card| String::from(card.get_rank()).clone());
         let ranks: Vec<u32> = sorted_hand
             .iter()
@@ -98,8 +98,8 @@ impl PokerRules for PokerGame {
         (false, sorted_hand)
     }
     
-    fn get_three_of_a_kind(hand: &Vec<Card>) -> (bool, Vec<Card>) {
-        let mut sorted_hand = hand.clone();
+    fn get_three_of_a_kind(hand: &[Card]) -> (bool, Vec<Card>) {
+        let mut sorted_hand = hand.to_vec();
         sorted_hand.sort_by_key(|card| String::from(card.get_rank()).clone());
         let ranks: Vec<u32> = sorted_hand
             .iter()
@@ -120,8 +120,8 @@ impl PokerRules for PokerGame {
         (false, sorted_hand)
     }
 
-    fn get_two_pairs(hand: &Vec<Card>) -> (bool, Vec<Card>) {
-        let mut sorted_hand = hand.clone();
+    fn get_two_pairs(hand: &[Card]) -> (bool, Vec<Card>) {
+        let mut sorted_hand = hand.to_vec();
         sorted_hand.sort_by_key(|card| String::from(card.get_rank()).clone());
         let ranks: Vec<u32> = sorted_hand
             .iter()
@@ -150,8 +150,8 @@ impl PokerRules for PokerGame {
     }
 
     
-    fn get_one_pair(hand: &Vec<Card>) -> (bool, Vec<Card>) {
-        let mut sorted_hand = hand.clone();
+    fn get_one_pair(hand: &[Card]) -> (bool, Vec<Card>) {
+        let mut sorted_hand = hand.to_vec();
         sorted_hand.sort_by_key(|card| String::from(card.get_rank()).clone());
         let ranks: Vec<u32> = sorted_hand
             .iter()
@@ -173,7 +173,7 @@ impl PokerRules for PokerGame {
     }
     
 
-    fn get_royal_flush(hand: &Vec<Card>) -> (bool, Vec<Card>) {
+    fn get_royal_flush(hand: &[Card]) -> (bool, Vec<Card>) {
         let (is_flush, sorted_hand) = Self::get_flush(hand);
         if is_flush {
             let ranks: Vec<u32> = sorted_hand
@@ -189,24 +189,24 @@ impl PokerRules for PokerGame {
         (false, sorted_hand)
     }
 
-    fn get_kicker(hand: &Vec<Card>, exclude_rank: &str) -> (Option<u32>, Option<Card>) {
-        let mut sorted_hand = hand.clone();
+    fn get_kicker(hand: &[Card], exclude_rank: &str) -> (Option<u32>, Option<Card>) {
+        let mut sorted_hand = hand.to_vec();
         sorted_hand.sort_by_key(|card| String::from(card.get_rank()).clone());
         sorted_hand.reverse();
         for card in &sorted_hand {
             if card.get_rank() != exclude_rank {
-                return (Self::get_card_rank(&card), Some(card.clone()));
+                return (Self::get_card_rank(card), Some(card.clone()));
             }
         }
         (None, None)
     }
 
-    fn get_high_card_rank(cards: &Vec<Card>) -> (Option<u32>, Option<Card>) {
-        let mut sorted_cards = cards.clone();
+    fn get_high_card_rank(cards: &[Card]) -> (Option<u32>, Option<Card>) {
+        let mut sorted_cards = cards.to_vec();
         sorted_cards.sort_by_key(|card| String::from(card.get_rank()).clone());
         sorted_cards.reverse();
         if let Some(card) = sorted_cards.first() {
-            return (Self::get_card_rank(&card), Some(card.clone()));
+            (Self::get_card_rank(card), Some(card.clone()))
         } else {
             (None, None)
         }
@@ -226,19 +226,100 @@ impl PokerRules for PokerGame {
         }
     } 
 
+    fn evaluate_hand(cards: &[Card]) -> HandValue {
+        let ranks: Vec<u32> = cards.iter().filter_map(Self::get_card_rank).collect();
+        let mut rank_counts: Vec<(usize, u32)> = Vec::new();
+        for rank in (2..=14).rev() {
+            let count = ranks.iter().filter(|&&card_rank| card_rank == rank).count();
+            if count > 0 {
+                rank_counts.push((count, rank));
+            }
+        }
+        // Bigger groups first, then higher ranks
+        rank_counts.sort_by(|first, second| second.cmp(first));
+
+        // The high card of the best straight among the given ranks, counting the ace low as well
+        let get_straight_high = |straight_ranks: &[u32]| -> Option<u32> {
+            (5..=14).rev().find(|&high| {
+                (high - 4..=high).all(|rank| straight_ranks.contains(&rank) || (rank == 1 && straight_ranks.contains(&14)))
+            })
+        };
+
+        let flush_ranks: Option<Vec<u32>> = ["Hearts", "Diamonds", "Clubs", "Spades"].iter()
+            .map(|suit| {
+                let mut suited_ranks: Vec<u32> = cards.iter()
+                    .filter(|card| card.get_suit() == *suit)
+                    .filter_map(Self::get_card_rank)
+                    .collect();
+                suited_ranks.sort_by(|first, second| second.cmp(first));
+                suited_ranks
+            })
+            .find(|suited_ranks| suited_ranks.len() >= 5);
+        if let Some(high) = flush_ranks.as_deref().and_then(get_straight_high) {
+            return HandValue { category: HandValue::STRAIGHT_FLUSH, ranks: vec![high] };
+        }
+
+        let get_kickers = |excluded: &[u32], amount: usize| -> Vec<u32> {
+            let mut kickers: Vec<u32> = rank_counts.iter()
+                .map(|&(_, rank)| rank)
+                .filter(|rank| !excluded.contains(rank))
+                .collect();
+            kickers.sort_by(|first, second| second.cmp(first));
+            kickers.truncate(amount);
+            kickers
+        };
+
+        let groups: Vec<usize> = rank_counts.iter().map(|&(count, _)| count).collect();
+        if groups.first() == Some(&4) {
+            let quad_rank = rank_counts[0].1;
+            let mut hand_ranks = vec![quad_rank];
+            hand_ranks.extend(get_kickers(&[quad_rank], 1));
+            return HandValue { category: HandValue::FOUR_OF_A_KIND, ranks: hand_ranks };
+        }
+        if groups.first() == Some(&3) && groups.get(1).is_some_and(|&count| count >= 2) {
+            return HandValue { category: HandValue::FULL_HOUSE, ranks: vec![rank_counts[0].1, rank_counts[1].1] };
+        }
+        if let Some(mut flush_ranks) = flush_ranks {
+            flush_ranks.truncate(5);
+            return HandValue { category: HandValue::FLUSH, ranks: flush_ranks };
+        }
+        if let Some(high) = get_straight_high(&ranks) {
+            return HandValue { category: HandValue::STRAIGHT, ranks: vec![high] };
+        }
+        if groups.first() == Some(&3) {
+            let trip_rank = rank_counts[0].1;
+            let mut hand_ranks = vec![trip_rank];
+            hand_ranks.extend(get_kickers(&[trip_rank], 2));
+            return HandValue { category: HandValue::THREE_OF_A_KIND, ranks: hand_ranks };
+        }
+        if groups.first() == Some(&2) && groups.get(1) == Some(&2) {
+            let pair_ranks = [rank_counts[0].1, rank_counts[1].1];
+            let mut hand_ranks = pair_ranks.to_vec();
+            hand_ranks.extend(get_kickers(&pair_ranks, 1));
+            return HandValue { category: HandValue::TWO_PAIRS, ranks: hand_ranks };
+        }
+        if groups.first() == Some(&2) {
+            let pair_rank = rank_counts[0].1;
+            let mut hand_ranks = vec![pair_rank];
+            hand_ranks.extend(get_kickers(&[pair_rank], 3));
+            return HandValue { category: HandValue::ONE_PAIR, ranks: hand_ranks };
+        }
+        HandValue { category: HandValue::HIGH_CARD, ranks: get_kickers(&[], 5) }
+    }
+
     fn get_sorted_cards(&self, hand: &mut Vec<Card>) -> Vec<Card> {
         hand.sort_by_key(|card| String::from(card.get_rank()).clone());
         hand.to_vec()
     }
 
-    fn get_hand_rank(&self, player: Player, dealer: &Dealer, community_deck: &Vec<Card>) -> Player {
+    fn get_hand_rank(&self, player: Player, dealer: &Dealer, community_deck: &[Card]) -> Player {
         let mut current_player = player;
         let mut game_dealer = dealer.clone();
-        let community_cards = community_deck.clone();
+        let community_cards = community_deck.to_vec();
         let player_cards = game_dealer.request_player_hand(&current_player).unwrap_or_default();
         let mut collected_hand_cards = Vec::new();
 
-        if player_cards.len() > 0 {
+        if !player_cards.is_empty() {
             let player_cards_for_extend = player_cards.clone();
             collected_hand_cards.extend(player_cards_for_extend);
         }
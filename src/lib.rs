@@ -0,0 +1,15 @@
+mod player;
+mod implementations;
+mod dealer;
+mod card;
+mod poker_game;
+
+pub mod constants;
+pub mod features;
+pub mod prelude;
+
+pub use player::Player;
+pub use dealer::Dealer;
+pub use card::Card;
+pub use poker_game::PokerGame;
+pub use features::GameError;
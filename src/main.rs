@@ -1,43 +1,107 @@
-
-mod player;
-mod implementations;
-mod features;
-mod constants;
-mod dealer;
-mod card;
-mod poker_game;
+use poker::prelude::*;
+use std::time::Instant;
 
 
 
-use player::Player;
-use dealer::Dealer;
-use poker_game::PokerGame;
+const OPTIONS_WITH_VALUES: [&str; 5] = ["--train", "--variant", "--iterations", "--output", "--acpc"];
 
-use std::time::Instant;
+fn get_option<'a>(arguments: &'a [String], option: &str) -> Option<&'a str> {
+    arguments.iter().position(|argument| argument == option)
+        .and_then(|index| arguments.get(index + 1))
+        .map(|value| value.as_str())
+}
 
+fn train_game<G: ExtensiveGame>(game: G, known_value: Option<f64>, arguments: &[String]) -> Result<(), GameError> {
+    let variant = match get_option(arguments, "--variant").unwrap_or("cfr") {
+        "cfr" => CfrVariant::Vanilla,
+        "cfr_plus" => CfrVariant::CfrPlus,
+        "chance_sampling" => CfrVariant::ChanceSampling,
+        "external_sampling" => CfrVariant::ExternalSampling,
+        other => return Err(GameError::InvalidConfig { reason: format!("unknown variant {:?}, use cfr, cfr_plus, chance_sampling or external_sampling", other) }),
+    };
+    let iterations = match get_option(arguments, "--iterations") {
+        Some(iterations) => iterations.parse::<u64>()
+            .map_err(|_| GameError::InvalidConfig { reason: format!("--iterations needs a whole number, got {:?}", iterations) })?,
+        None => 1000,
+    };
+    let output_path = get_option(arguments, "--output").map(String::from)
+        .unwrap_or(format!("{}_strategy.json", game.get_game_name()));
 
+    let mut trainer = CfrTrainer::new(game, variant)?;
+    trainer.train_with_reports(iterations, (iterations / 10).max(1), |report| {
+        match report.exploitability {
+            Some(exploitability) => println!("Iteration {}: {} information sets, exploitability {:.5}", report.iteration, report.info_set_count, exploitability),
+            None => println!("Iteration {}: {} information sets", report.iteration, report.info_set_count),
+        }
+    });
+    if let Some(expected_value) = trainer.get_expected_value() {
+        match known_value {
+            Some(known_value) => println!("First player's expected value: {:.5} (game value {:.5})", expected_value, known_value),
+            None => println!("First player's expected value: {:.5}", expected_value),
+        }
+    }
+    trainer.get_strategy().to_json_file(&output_path)?;
+    println!("Strategy saved to {}", output_path);
+    Ok(())
+}
 
-fn main() {
+fn main() -> Result<(), GameError> {
     let start_time = Instant::now();
-    let dealer = Dealer::new("auto".to_string());
-    let player1 = Player::new("AutoPlayer", 1, String::from("Alice"), 100);
-    let player2 = Player::new("AutoPlayer", 2, String::from("Bob"), 100);
-    let player3 = Player::new("AutoPlayer", 3, String::from("Charlie"), 100);
-    let player4 = Player::new("AutoPlayer", 4, String::from("Dave"), 100);
-    let player5 = Player::new("AutoPlayer", 5, String::from("Eve"), 100);
-    let mut game = PokerGame::new(vec![player1, player2, player3, player4, player5], dealer);
-    //game.play();
-    game.request_generate_deck(1);
-    game.request_dealer_shuffle();
-    game.play_with_blinds(true);
-    //game.play_with_blinds(false);
-    game.post_blinds_current_game(5);
-    game.deal_cards();
-    game.play();
-    //game.post_blinds_current_game(5);
-    //game.show_status();
+    // Pass a JSON table config to play it instead of the default table,
+    // and --human to take the first player's seat yourself
+    let arguments: Vec<String> = std::env::args().skip(1).collect();
+    // --train <kuhn|leduc|limit_holdem> trains a CFR strategy instead of playing,
+    // with optional --variant, --iterations and --output
+    if let Some(game_name) = get_option(&arguments, "--train") {
+        return match game_name {
+            KuhnPoker::GAME_NAME => train_game(KuhnPoker::new(), Some(KuhnPoker::GAME_VALUE), &arguments),
+            LeducPoker::GAME_NAME => train_game(LeducPoker::new(), Some(LeducPoker::GAME_VALUE), &arguments),
+            LimitHoldem::GAME_NAME => train_game(LimitHoldem::new(), None, &arguments),
+            other => Err(GameError::InvalidConfig { reason: format!("unknown game {:?}, use kuhn, leduc or limit_holdem", other) }),
+        };
+    }
+    let play_as_human = arguments.iter().any(|argument| argument == "--human");
+    let config_path = arguments.iter().enumerate()
+        .find(|(index, argument)| !argument.starts_with("--") && (*index == 0 || !OPTIONS_WITH_VALUES.contains(&arguments[index - 1].as_str())))
+        .map(|(_, argument)| argument);
+    let mut table_config = match config_path {
+        Some(config_path) => TableConfig::from_json_file(config_path)?,
+        None => TableConfig::new()
+            .with_player(1, "Alice", 100)
+            .with_player(2, "Bob", 100)
+            .with_player(3, "Charlie", 100)
+            .with_player(4, "Dave", 100)
+            .with_player(5, "Eve", 100)
+            .with_blinds(5, 10)
+            .with_rake(RakeSettings { percentage: 5.0, cap: Some(3), no_flop_no_drop: true, player_count_caps: vec![(2, 1), (4, 3)] }),
+    };
+    // --acpc <hands> plays a match with ACPC clients in the config's "acpc" seats
+    if let Some(number_of_hands) = get_option(&arguments, "--acpc") {
+        let number_of_hands = number_of_hands.parse::<u32>()
+            .map_err(|_| GameError::InvalidConfig { reason: format!("--acpc needs a number of hands, got {:?}", number_of_hands) })?;
+        let mut acpc_dealer = AcpcDealer::new(table_config)?;
+        for port in acpc_dealer.get_ports() {
+            println!("Waiting for an ACPC client on 127.0.0.1:{}", port);
+        }
+        acpc_dealer.subscribe(Box::new(ConsoleObserver::new()));
+        let game = acpc_dealer.play_match(number_of_hands)?;
+        game.show_status();
+        return Ok(());
+    }
+    if play_as_human {
+        if let Some(seat_config) = table_config.players.first_mut() {
+            seat_config.agent = String::from(HumanAgent::AGENT_NAME);
+        }
+    }
+    let mut game = table_config.build()?;
+    game.subscribe(Box::new(ConsoleObserver::new()));
+    if let Err(error) = game.play_session(10) {
+        println!("Session ended early: {}", error);
+    }
+    game.show_status();
     let duration = start_time.elapsed();
     let milliseconds = duration.as_millis();
 
     println!("Execution Time: {}~ms", milliseconds);
+    Ok(())
 }
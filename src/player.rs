@@ -1,36 +1,106 @@
 
 use super::card::Card;
-use crate::features::{HandRank, ActionResult, StandardAutoPlayer};
-use crate::constants::PlayerVersion;
+use crate::features::HandRank;
+use std::fmt;
+use serde::{Serialize, Deserialize};
 
-#[derive(Debug, Clone)]
+/// Hole cards and what they make are hidden from outside the crate, and only the engine
+/// can fold a player:
+///
+/// ```
+/// let player = poker::Player::new(1, String::from("Alice"), 100);
+/// assert!(!player.get_folded());
+/// ```
+///
+/// ```compile_fail
+/// let player = poker::Player::new(1, String::from("Alice"), 100);
+/// let hole_cards = player.get_hole_cards();
+/// ```
+///
+/// ```compile_fail
+/// let player = poker::Player::new(1, String::from("Alice"), 100);
+/// let hand_rank = &player.hand_rank;
+/// ```
+///
+/// ```compile_fail
+/// use poker::features::StandardAutoPlayer;
+/// let mut player = poker::Player::new(1, String::from("Alice"), 100);
+/// player.fold();
+/// ```
+///
+/// ```compile_fail
+/// use poker::features::{StandardAutoPlayer, HandRank};
+/// let mut player = poker::Player::new(1, String::from("Alice"), 100);
+/// let ace = poker::Card::new(String::from("Ace"), String::from("Spades"));
+/// let hand_rank = HandRank { hand_rank: 0, hand_rank_name: String::from("High Card"), winning_cards: Vec::new(), high_card: ace.clone(), kicker: ace };
+/// player.set_handrank(hand_rank);
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Player {
-    player_type: PlayerVersion,
     id: u32,
+    seat: usize,
     username: String,
     coins: u32,
     folded: bool,
+    // Left out of serde so a serialized player never reveals them; snapshots carry them instead
+    #[serde(skip)]
     hand_cards: Vec<Card>,
     current_bet: u32,
-    pub hand_rank: Option<HandRank>
+    total_bet: u32,
+    acted: bool,
+    sitting_out: bool,
+    missed_small_blind: bool,
+    missed_big_blind: bool,
+    connected: bool,
+    // Milliseconds of the session time bank already spent
+    time_bank_used: u64,
+    // Worked out from the hole cards, so it is hidden like them
+    #[serde(skip)]
+    pub(crate) hand_rank: Option<HandRank>
 }
 
 
 
+// Hole cards are left out so a formatted player never reveals them
+impl fmt::Debug for Player {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Player")
+            .field("id", &self.id)
+            .field("seat", &self.seat)
+            .field("username", &self.username)
+            .field("coins", &self.coins)
+            .field("folded", &self.folded)
+            .field("hand_cards", &self.hand_cards.len())
+            .field("current_bet", &self.current_bet)
+            .field("total_bet", &self.total_bet)
+            .field("acted", &self.acted)
+            .field("sitting_out", &self.sitting_out)
+            .field("missed_small_blind", &self.missed_small_blind)
+            .field("missed_big_blind", &self.missed_big_blind)
+            .field("connected", &self.connected)
+            .field("time_bank_used", &self.time_bank_used)
+            .field("hand_rank", &self.hand_rank)
+            .finish()
+    }
+}
+
 impl Player {
-    pub fn new(player_type: &str, id: u32, username: String, coins: u32,) -> Self {
-        let converted_player_type:PlayerVersion = match player_type {
-            "AutoPlayer" => PlayerVersion::AutoPlayer,
-            _ => PlayerVersion::AutoPlayer
-        };
+    pub fn new(id: u32, username: String, coins: u32) -> Self {
         Player {
-            player_type: converted_player_type,
             id,
+            seat: 0,
             username,
             coins,
             folded: false,
             hand_cards: Vec::new(),
             current_bet: 0,
+            total_bet: 0,
+            acted: false,
+            sitting_out: false,
+            missed_small_blind: false,
+            missed_big_blind: false,
+            connected: true,
+            time_bank_used: 0,
             hand_rank: None
         }
     }
@@ -39,6 +109,14 @@ impl Player {
         self.id
     }
 
+    pub fn get_seat(&self) -> usize {
+        self.seat
+    }
+
+    pub(crate) fn set_seat(&mut self, seat: usize) {
+        self.seat = seat;
+    }
+
     pub fn get_player_username(&self) -> String {
         self.username.clone()
     }
@@ -47,36 +125,120 @@ impl Player {
         self.coins
     }
 
-    pub fn set_coins(&mut self, coins: u32) {
+    pub(crate) fn set_coins(&mut self, coins: u32) {
         self.coins = coins;
     }
 
-    pub fn increase_bet(&mut self, amount: u32) {
-        self.current_bet = self.current_bet + amount;
+    pub(crate) fn increase_bet(&mut self, amount: u32) {
+        self.current_bet += amount;
     }
 
     pub fn get_current_bet(&self) -> u32 {
-        self.current_bet.clone()
+        self.current_bet
+    }
+
+    pub(crate) fn reset_current_bet(&mut self) {
+        self.current_bet = 0;
+    }
+
+    pub(crate) fn increase_total_bet(&mut self, amount: u32) {
+        self.total_bet += amount;
+    }
+
+    pub(crate) fn decrease_total_bet(&mut self, amount: u32) {
+        self.total_bet = self.total_bet.saturating_sub(amount);
+    }
+
+    pub fn get_total_bet(&self) -> u32 {
+        self.total_bet
+    }
+
+    pub(crate) fn reset_for_next_hand(&mut self) {
+        self.folded = false;
+        self.hand_cards.clear();
+        self.current_bet = 0;
+        self.total_bet = 0;
+        self.acted = false;
+        self.hand_rank = None;
+    }
+
+    pub fn get_acted(&self) -> bool {
+        self.acted
+    }
+
+    pub(crate) fn set_acted(&mut self, acted: bool) {
+        self.acted = acted;
+    }
+
+    pub fn is_all_in(&self) -> bool {
+        !self.folded && self.coins == 0
+    }
+
+    pub fn can_act(&self) -> bool {
+        !self.folded && !self.is_all_in()
+    }
+
+    pub fn get_sitting_out(&self) -> bool {
+        self.sitting_out
+    }
+
+    pub(crate) fn set_sitting_out(&mut self, sitting_out: bool) {
+        self.sitting_out = sitting_out;
+    }
+
+    pub fn get_missed_blinds(&self) -> (bool, bool) {
+        (self.missed_small_blind, self.missed_big_blind)
+    }
+
+    pub(crate) fn set_missed_small_blind(&mut self, missed: bool) {
+        self.missed_small_blind = missed;
+    }
+
+    pub(crate) fn set_missed_big_blind(&mut self, missed: bool) {
+        self.missed_big_blind = missed;
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    pub(crate) fn set_connected(&mut self, connected: bool) {
+        self.connected = connected;
+    }
+
+    pub fn get_time_bank_used(&self) -> u64 {
+        self.time_bank_used
+    }
+
+    pub(crate) fn use_time_bank(&mut self, milliseconds: u64) {
+        self.time_bank_used += milliseconds;
     }
 
     pub fn get_folded(&self) -> bool {
         self.folded
     }
 
-    pub fn set_folded(&mut self, fold: bool) {
+    pub(crate) fn set_folded(&mut self, fold: bool) {
         self.folded = fold;
     }
 
-    pub fn get_current_hand(&mut self) -> &mut Vec<Card> {
+    pub(crate) fn fold(&mut self) {
+        self.set_folded(true);
+    }
+
+    pub(crate) fn set_handrank(&mut self, handrank: HandRank) {
+        self.hand_rank = Some(handrank);
+    }
+
+    pub(crate) fn get_current_hand(&mut self) -> &mut Vec<Card> {
         &mut self.hand_cards
     }
 
-    pub fn get_action(&self, minimum_bet: u32) -> ActionResult {
-        match self.player_type {
-            PlayerVersion::AutoPlayer => StandardAutoPlayer::get_action(self, Some(minimum_bet))
-        }
+    pub(crate) fn get_hole_cards(&self) -> &Vec<Card> {
+        &self.hand_cards
     }
 
+
 }
 
 
@@ -89,6 +251,7 @@ impl PartialEq for Player {
         self.folded == other.folded &&
         self.hand_cards.iter().all(|card| other.hand_cards.contains(card)) &&
         self.current_bet == other.current_bet &&
+        self.total_bet == other.total_bet &&
         self.hand_rank == other.hand_rank
     }
 }
\ No newline at end of file
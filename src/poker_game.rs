@@ -5,44 +5,123 @@ use super::card::Card;
 use crate::constants::{PlayerAction};
 use crate::features::StandardAutoPlayer;
 use crate::features::PokerRules;
+use crate::features::{ForcedBets, RakeSettings, HouseLedger, GameEvent, GameObserver, SeatSummary, GameError, ChipLedger};
+use crate::features::{PlayerView, SeatView, PotView, GameSnapshot, UndoPoint, TableConfig, TimeLimits, Clock, SystemClock};
+use crate::features::{PlayerAgent, RandomAgent, AgentRegistry, HandValue, StatsTracker, ActionResult};
+use std::path::PathBuf;
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, TryRecvError};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use crate::constants::{StraddleType, HandPhase, BlindType, GameErrorKind, ChipAccount, TransferReason, BettingStructure};
 use rand::Rng;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
 
 
+type BusyAgent = (Receiver<(Box<dyn PlayerAgent>, ActionResult)>, JoinHandle<()>);
+
 pub struct PokerGame {
     game_id: String,
     players: Vec<Player>,
     game_dealer: Dealer,
     pot: u32,
+    waiting_players: Vec<Player>,
+    max_seats: usize,
+    hand_number: u32,
     with_blinds: Option<bool>,
-    small_blind_index: usize,
-    big_blind_index: usize,
+    button_seat: usize,
+    small_blind_seat: usize,
+    big_blind_seat: usize,
+    straddle_seat: Option<usize>,
     blinds_initialized: bool,
     big_blind: u32,
-    game_state: String,
+    forced_bets: ForcedBets,
+    betting_structure: BettingStructure,
+    time_limits: TimeLimits,
+    clock: Box<dyn Clock>,
+    // The player whose decision is being timed and when their turn started
+    action_timer: Option<(usize, Duration)>,
+    rake_settings: RakeSettings,
+    house_ledger: HouseLedger,
+    chip_ledger: ChipLedger,
+    hand_phase: HandPhase,
+    action_index: Option<usize>,
+    observers: Vec<Box<dyn GameObserver>>,
+    // Decides for each player by id, seated or waiting
+    agents: HashMap<u32, Box<dyn PlayerAgent>>,
+    // Agents still deciding on another thread after their time ran out. Each comes back with
+    // its late decision, which is thrown away.
+    busy_agents: HashMap<u32, BusyAgent>,
+    // Each player's notes on the others by username, from the hands they were dealt into
+    stats_trackers: HashMap<String, StatsTracker>,
+    stats_directory: Option<PathBuf>,
+    hand_history: Vec<GameEvent>,
+    // The table as it was before each action this hand, newest last
+    undo_stack: Vec<UndoPoint>,
     highest_bet: u32,
+    last_raise_size: u32,
+    // Bets and raises made this street, which fixed limit caps
+    street_bet_count: u32,
     community_deck: Vec<Card>
 }
 
 
 impl PokerGame {
+    const MAX_SEATS: usize = 10;
+    // Illegal actions a player may choose in a row before the engine checks or folds for them
+    const MAX_ACTION_ATTEMPTS: u32 = 3;
+
     pub fn new(players: Vec<Player>, game_dealer: Dealer) -> Self {
         let game_id = String::from("pkrgme-")+&Self::generate_game_id(9);
-        let small_blind_index = rand::thread_rng().gen_range(0..players.len());
-        let big_blind_index = (small_blind_index + 1) % players.len();
-        PokerGame {
+        let mut players = players;
+        for (seat, player) in players.iter_mut().enumerate() {
+            player.set_seat(seat);
+        }
+        let button_seat = rand::thread_rng().gen_range(0..players.len());
+        let mut poker_game = PokerGame {
             game_id,
             players,
             game_dealer,
             pot: 0,
+            waiting_players: Vec::new(),
+            max_seats: Self::MAX_SEATS,
+            hand_number: 1,
             with_blinds: None,
-            small_blind_index,
-            big_blind_index,
+            button_seat,
+            small_blind_seat: button_seat,
+            big_blind_seat: button_seat,
+            straddle_seat: None,
             blinds_initialized: false,
             big_blind: 0,
-            game_state: String::from("initial"),
+            forced_bets: ForcedBets::default(),
+            betting_structure: BettingStructure::default(),
+            time_limits: TimeLimits::default(),
+            clock: Box::new(SystemClock::new()),
+            action_timer: None,
+            rake_settings: RakeSettings::default(),
+            house_ledger: HouseLedger::default(),
+            chip_ledger: ChipLedger::default(),
+            hand_phase: HandPhase::Initial,
+            action_index: None,
+            observers: Vec::new(),
+            agents: HashMap::new(),
+            busy_agents: HashMap::new(),
+            stats_trackers: HashMap::new(),
+            stats_directory: None,
+            hand_history: Vec::new(),
+            undo_stack: Vec::new(),
             highest_bet: 0,
+            last_raise_size: 0,
+            street_bet_count: 0,
             community_deck: Vec::new()
+        };
+        for player in poker_game.players.iter() {
+            poker_game.chip_ledger.record_transfer(0, ChipAccount::Cashier, ChipAccount::Player(player.get_player_id()), player.get_coins(), TransferReason::BuyIn);
         }
+        poker_game.install_default_agents();
+        poker_game.assign_blind_positions();
+        poker_game
     }
     
     fn generate_game_id(id_length: usize) -> String {
@@ -61,215 +140,691 @@ impl PokerGame {
         new_game_id
     }
 
-    fn are_players_ready(&self) -> bool {
-        let players_with_cards:&Vec<Player> = &self.players.iter().cloned().filter(|player| player.clone().get_hand_length() > 0).collect();
-        if self.players.len() == players_with_cards.len() {
-            true
+    // Players are kept in seat order, so the first match after a seat is the next one clockwise
+    fn first_index_after_seat<F: Fn(&Player) -> bool>(&self, seat: usize, predicate: F) -> Option<usize> {
+        self.players.iter()
+            .position(|player| player.get_seat() > seat && predicate(player))
+            .or_else(|| self.players.iter().position(&predicate))
+    }
+
+    fn next_seated_index(&self, seat: usize) -> usize {
+        self.first_index_after_seat(seat, |player| !player.get_sitting_out())
+            .unwrap_or_default()
+    }
+
+    fn get_seated_index(&self, seat: usize) -> Option<usize> {
+        self.players.iter()
+            .position(|player| player.get_seat() == seat && !player.get_sitting_out())
+    }
+
+    fn get_seated_player_count(&self) -> usize {
+        self.players.iter().filter(|player| !player.get_sitting_out()).count()
+    }
+
+    fn is_seat_between(seat: usize, from_seat: usize, to_seat: usize) -> bool {
+        if from_seat < to_seat {
+            seat > from_seat && seat < to_seat
         } else {
-            false
+            seat > from_seat || seat < to_seat
         }
     }
 
-    fn is_dealer_ready(&self) -> bool {
-        let deck_id = self.game_dealer.get_deck_id();
-        match deck_id {
-            "" => {
-                println!("Dealer has no deck yet. Generate a deck first.");
-                return false;
-            }
-            _ => {
-                let mut is_ready = false;
-                if let Some(with_blinds) = self.with_blinds {
-                    match with_blinds {
-                        true => {
-                            if self.blinds_initialized == false {
-                                println!("Please set blind amount if playing with blinds.");
-                                is_ready = false;
-                            } else {
-                                is_ready = true;
-                            }
-                        },
-                        false => {
-                            is_ready = true;
-                        },
-                    }
+    fn mark_missed_blinds(&mut self, from_seat: usize, to_seat: usize, is_big_blind: bool) {
+        for player in self.players.iter_mut() {
+            if player.get_sitting_out() && Self::is_seat_between(player.get_seat(), from_seat, to_seat) {
+                if is_big_blind {
+                    player.set_missed_big_blind(true);
                 } else {
-                    println!("Please specify if playing with blinds.");
+                    player.set_missed_small_blind(true);
                 }
-                is_ready
             }
         }
     }
+
+    fn assign_blind_positions(&mut self) {
+        if self.get_seated_index(self.button_seat).is_none() {
+            let button_index = self.next_seated_index(self.button_seat);
+            self.button_seat = self.players[button_index].get_seat();
+        }
+
+        if self.get_seated_player_count() == 2 {
+            // Heads-up: the button posts the small blind
+            self.small_blind_seat = self.button_seat;
+        } else {
+            let small_blind_index = self.next_seated_index(self.button_seat);
+            self.small_blind_seat = self.players[small_blind_index].get_seat();
+            self.mark_missed_blinds(self.button_seat, self.small_blind_seat, false);
+        }
+        let big_blind_index = self.next_seated_index(self.small_blind_seat);
+        self.big_blind_seat = self.players[big_blind_index].get_seat();
+        self.mark_missed_blinds(self.small_blind_seat, self.big_blind_seat, true);
+    }
+
+    // Dead button rule: the big blind always moves forward one player, the small blind
+    // takes the previous big blind's seat and the button takes the previous small blind's
+    // seat, even when those seats are now empty.
+    fn rotate_blind_positions(&mut self) {
+        // Coming out of heads-up the button was also the small blind, so it moves on one
+        // player and the blinds follow it instead of the button staying where it was
+        let was_heads_up = self.small_blind_seat == self.button_seat;
+        if was_heads_up && self.get_seated_player_count() > 2 {
+            let button_index = self.next_seated_index(self.button_seat);
+            self.button_seat = self.players[button_index].get_seat();
+            let small_blind_index = self.next_seated_index(self.button_seat);
+            self.small_blind_seat = self.players[small_blind_index].get_seat();
+            let big_blind_index = self.next_seated_index(self.small_blind_seat);
+            self.big_blind_seat = self.players[big_blind_index].get_seat();
+            return;
+        }
+
+        let previous_small_blind_seat = self.small_blind_seat;
+        let previous_big_blind_seat = self.big_blind_seat;
+        let big_blind_index = self.next_seated_index(previous_big_blind_seat);
+        self.big_blind_seat = self.players[big_blind_index].get_seat();
+        self.mark_missed_blinds(previous_big_blind_seat, self.big_blind_seat, true);
+
+        if self.get_seated_player_count() == 2 {
+            // Heads-up: the other player has the button and posts the small blind
+            let small_blind_index = self.next_seated_index(self.big_blind_seat);
+            self.small_blind_seat = self.players[small_blind_index].get_seat();
+            self.button_seat = self.small_blind_seat;
+        } else {
+            self.small_blind_seat = previous_big_blind_seat;
+            self.button_seat = previous_small_blind_seat;
+            if let Some(player) = self.players.iter_mut().find(|player| player.get_seat() == previous_big_blind_seat) {
+                if player.get_sitting_out() {
+                    player.set_missed_small_blind(true);
+                }
+            }
+        }
+    }
+
+    fn next_active_index(&self, from_index: usize) -> usize {
+        if let Some(action_order) = self.get_button_straddle_order() {
+            let position = action_order.iter().position(|&index| index == from_index).unwrap_or_default();
+            return (1..=action_order.len())
+                .map(|offset| action_order[(position + offset) % action_order.len()])
+                .find(|&index| self.players[index].can_act())
+                .unwrap_or(from_index);
+        }
+        self.first_index_after_seat(self.players[from_index].get_seat(), |player| player.can_act())
+            .unwrap_or(from_index)
+    }
+
+    // With a button straddle the button acts last preflop: action starts left of the big
+    // blind, skips the button and comes back to it after the blinds
+    fn get_button_straddle_order(&self) -> Option<Vec<usize>> {
+        if self.hand_phase != HandPhase::PreFlop || self.straddle_seat.is_none() || self.straddle_seat != Some(self.button_seat) {
+            return None;
+        }
+        let button_index = self.players.iter().position(|player| player.get_seat() == self.button_seat)?;
+        let big_blind_index = self.players.iter().position(|player| player.get_seat() == self.big_blind_seat)?;
+        let player_count = self.players.len();
+        let mut action_order: Vec<usize> = (1..=player_count)
+            .map(|offset| (big_blind_index + offset) % player_count)
+            .filter(|&index| index != button_index)
+            .collect();
+        action_order.push(button_index);
+        Some(action_order)
+    }
+
+    fn first_to_act_index(&self) -> usize {
+        let blinds_posted = self.with_blinds == Some(true) && self.blinds_initialized;
+        let first_active_after = |seat: usize| {
+            self.first_index_after_seat(seat, |player| player.can_act()).unwrap_or_default()
+        };
+        if self.hand_phase == HandPhase::PreFlop && blinds_posted {
+            if let Some(straddle_seat) = self.straddle_seat {
+                // The straddler posted the last live blind and acts last, so action starts
+                // with whoever follows them in the preflop order
+                match self.players.iter().position(|player| player.get_seat() == straddle_seat) {
+                    Some(straddle_index) if self.get_button_straddle_order().is_some() => self.next_active_index(straddle_index),
+                    _ => first_active_after(straddle_seat),
+                }
+            } else if self.get_seated_player_count() == 2 {
+                // Heads-up: the button is the small blind and acts first preflop
+                self.get_seated_index(self.small_blind_seat).unwrap_or_else(|| first_active_after(self.big_blind_seat))
+            } else {
+                first_active_after(self.big_blind_seat)
+            }
+        } else {
+            first_active_after(self.button_seat)
+        }
+    }
+
+    fn are_players_ready(&self) -> bool {
+        let players_with_cards:&Vec<Player> = &self.players.iter()
+            .filter(|&player| player.clone().get_hand_length() > 0)
+            .cloned()
+            .collect();
+        self.get_seated_player_count() == players_with_cards.len()
+    }
+
+    fn check_dealer_ready(&self) -> Result<(), GameError> {
+        if self.game_dealer.get_deck_id().is_empty() {
+            return Err(GameError::NoDeck);
+        }
+        match self.with_blinds {
+            Some(true) if !self.blinds_initialized => Err(GameError::BlindsNotPosted),
+            Some(_) => Ok(()),
+            None => Err(GameError::BlindsNotSpecified),
+        }
+    }
     
-    fn add_to_pot(&mut self, amount: u32) {
-        self.pot = self.pot + amount;
+    pub fn subscribe(&mut self, observer: Box<dyn GameObserver>) {
+        self.observers.push(observer);
+    }
+
+    fn emit(&mut self, event: GameEvent) {
+        for observer in self.observers.iter_mut() {
+            observer.on_event(&event);
+        }
+        self.hand_history.push(event);
+    }
+
+    fn add_to_pot(&mut self, player_index: usize, amount: u32, reason: TransferReason) {
+        let player_id = self.players[player_index].get_player_id();
+        self.chip_ledger.record_transfer(self.hand_number, ChipAccount::Player(player_id), ChipAccount::Pot, amount, reason);
+        self.pot += amount;
     }
 
-    fn set_game_state(&mut self, new_game_state: &str) {
-        self.game_state = String::from(new_game_state);
+    fn set_hand_phase(&mut self, new_hand_phase: HandPhase) {
+        self.hand_phase = new_hand_phase;
     }
     
     fn check_round_over(&self) -> bool {
         let remaining_players: Vec<&Player> = self.players.iter()
             .filter(|player| !player.has_player_folded())
             .collect();
-        
-        let all_bets_equal = remaining_players.iter().all(|player| player.get_current_bet() == self.highest_bet);
-        
-        remaining_players.len() == 1 || all_bets_equal
+        let players_able_to_act: Vec<&&Player> = remaining_players.iter()
+            .filter(|player| !player.is_all_in())
+            .collect();
+
+        if remaining_players.len() <= 1 || players_able_to_act.is_empty() {
+            return true;
+        }
+
+        // A lone player who already matches every all-in has nobody left to bet against
+        if players_able_to_act.len() == 1 && players_able_to_act[0].get_current_bet() >= self.highest_bet {
+            return true;
+        }
+
+        players_able_to_act.iter().all(|player| player.get_acted() && player.get_current_bet() == self.highest_bet)
     }
 
-    fn bet(&mut self, player_index: usize, bet_amount: u32) -> bool {
-        let player = &mut self.players[player_index];
-        let min_bet = self.big_blind;
-        let mut bet_successful = false;
-                
-        if bet_amount >= min_bet {
-            let requested_funds = self.game_dealer.player_bet(player, bet_amount);
-            if requested_funds > 0 {
-                self.highest_bet = self.highest_bet + requested_funds;
-                self.add_to_pot(requested_funds);
-                bet_successful = true;
+    fn reopen_action(&mut self, aggressor_index: usize) {
+        for (index, player) in self.players.iter_mut().enumerate() {
+            if index != aggressor_index {
+                player.set_acted(false);
             }
+        }
+    }
+
+    fn reset_street(&mut self) {
+        for player in self.players.iter_mut() {
+            player.reset_current_bet();
+            player.set_acted(false);
+        }
+        self.highest_bet = 0;
+        self.last_raise_size = self.big_blind;
+        self.street_bet_count = 0;
+    }
+
+    fn start_betting_round(&mut self) {
+        self.action_timer = None;
+        self.action_index = if self.check_round_over() {
+            None
         } else {
-            println!("Minimum bet is: {}", min_bet);
+            Some(self.first_to_act_index())
+        };
+    }
+
+    fn start_next_street(&mut self) {
+        let remaining_players = self.players.iter()
+            .filter(|player| !player.has_player_folded())
+            .count();
+
+        if remaining_players <= 1 {
+            self.set_hand_phase(HandPhase::Showdown);
+            return;
+        }
+
+        let (next_hand_phase, card_amount_to_open) = self.hand_phase.get_next_street();
+        self.reset_street();
+        self.set_hand_phase(next_hand_phase);
+        if card_amount_to_open > 0 {
+            let board_length = self.community_deck.len();
+            self.request_dealer_open_cards(card_amount_to_open);
+            self.emit(GameEvent::StreetDealt {
+                hand_phase: next_hand_phase,
+                cards: self.community_deck[board_length..].to_vec(),
+                board: self.community_deck.clone(),
+            });
+        }
+        if next_hand_phase.is_betting_round() {
+            self.start_betting_round();
         }
-        bet_successful
     }
 
-    fn raise(&mut self, player_index: usize, raise_amount: u32) -> bool {
-        let player = &mut self.players[player_index];
-        let min_raise = self.highest_bet*2;        
-        let mut raise_successful = false;
-
-        if raise_amount > min_raise {
-            let requested_funds = self.game_dealer.player_raise(player, raise_amount);
-            if requested_funds > 0 {
-                self.highest_bet = self.highest_bet + requested_funds;
-                self.add_to_pot(requested_funds);
-                raise_successful = true;
-            }
+    fn get_fixed_bet_size(&self) -> u32 {
+        match self.hand_phase {
+            HandPhase::Turn | HandPhase::River => self.big_blind * 2,
+            _ => self.big_blind,
+        }
+    }
+
+    // Smallest and largest street total a player may bet or raise to, capped at all-in
+    fn get_bet_limits(&self, player_index: usize) -> (u32, u32) {
+        let player = &self.players[player_index];
+        let all_in_to = player.get_current_bet() + player.get_coins();
+        let amount_to_call = self.highest_bet.saturating_sub(player.get_current_bet());
+        let no_limit_minimum = if self.highest_bet == 0 {
+            std::cmp::max(self.big_blind, 1)
         } else {
-            println!("Minimum raise is: {}", min_raise);
+            self.highest_bet + self.last_raise_size
+        };
+        let (minimum_to, maximum_to) = match self.betting_structure {
+            BettingStructure::NoLimit => (no_limit_minimum, all_in_to),
+            // A pot sized raise is a call followed by a raise of the whole pot
+            BettingStructure::PotLimit => (no_limit_minimum, self.highest_bet + self.pot + amount_to_call),
+            BettingStructure::FixedLimit => {
+                let fixed_to = self.highest_bet + self.get_fixed_bet_size();
+                (fixed_to, fixed_to)
+            },
+        };
+        let maximum_to = std::cmp::max(minimum_to, maximum_to);
+        (std::cmp::min(minimum_to, all_in_to), std::cmp::min(maximum_to, all_in_to))
+    }
+
+    fn is_betting_capped(&self) -> bool {
+        self.betting_structure == BettingStructure::FixedLimit
+            && self.street_bet_count >= BettingStructure::FIXED_LIMIT_BET_CAP
+    }
+
+    fn bet(&mut self, player_index: usize, bet_amount: u32) -> Result<(), GameError> {
+        let (minimum_bet, maximum_bet) = self.get_bet_limits(player_index);
+        let player = &mut self.players[player_index];
+        let player_coins = player.get_coins();
+
+        if self.highest_bet > 0 {
+            return Err(GameError::BetAlreadyOpen { highest_bet: self.highest_bet });
+        }
+        if bet_amount > player_coins {
+            return Err(GameError::InsufficientCoins { required: bet_amount, available: player_coins });
+        }
+        if bet_amount > maximum_bet {
+            return Err(GameError::AboveMaximumBet { maximum: maximum_bet });
+        }
+        if bet_amount < minimum_bet {
+            return Err(GameError::BelowMinimumBet { minimum: minimum_bet });
         }
-        raise_successful
-      }
 
-    fn call(&mut self, player_index: usize) -> bool {
+        let requested_funds = self.game_dealer.player_bet(player, bet_amount);
+        if requested_funds == 0 {
+            return Err(GameError::FundsNotTransferred { player_index, action: PlayerAction::Bet });
+        }
+        self.highest_bet = player.get_current_bet();
+        self.last_raise_size = std::cmp::max(requested_funds, std::cmp::max(self.big_blind, 1));
+        self.street_bet_count += 1;
+        self.add_to_pot(player_index, requested_funds, TransferReason::Bet);
+        self.reopen_action(player_index);
+        Ok(())
+    }
+
+    fn raise(&mut self, player_index: usize, raise_to: u32) -> Result<(), GameError> {
+        let (minimum_raise, maximum_raise) = self.get_bet_limits(player_index);
+        let is_betting_capped = self.is_betting_capped();
         let player = &mut self.players[player_index];
-        let current_player_bet =  player.get_current_bet().clone();
-        let current_player_coins = player.get_coins().clone();
-        let amount_to_call = self.highest_bet - current_player_bet;   
-        let mut call_successful = false;
-
-        if amount_to_call <= current_player_coins {
-            let requested_funds = self.game_dealer.player_call(player, amount_to_call);
-            if requested_funds > 0 {
-                self.highest_bet = self.highest_bet + requested_funds;
-                self.add_to_pot(requested_funds);
-                call_successful = true;
-            }
-        } else {
-            println!("Funds required to call");
+        let player_coins = player.get_coins();
+        let required_funds = raise_to.saturating_sub(player.get_current_bet());
+
+        if self.highest_bet == 0 {
+            return Err(GameError::NothingToRaise);
+        }
+        if is_betting_capped {
+            return Err(GameError::RaiseCapReached);
+        }
+        if raise_to <= self.highest_bet {
+            return Err(GameError::RaiseNotAboveBet { highest_bet: self.highest_bet });
+        }
+        if required_funds > player_coins {
+            return Err(GameError::InsufficientCoins { required: required_funds, available: player_coins });
+        }
+        if raise_to > maximum_raise {
+            return Err(GameError::AboveMaximumBet { maximum: maximum_raise });
+        }
+        if raise_to < minimum_raise {
+            return Err(GameError::BelowMinimumRaise { minimum: minimum_raise });
+        }
+
+        let requested_funds = self.game_dealer.player_raise(player, required_funds);
+        if requested_funds == 0 {
+            return Err(GameError::FundsNotTransferred { player_index, action: PlayerAction::Raise });
         }
-        call_successful
+        // An all-in below the minimum raise does not change the raise size
+        self.last_raise_size = std::cmp::max(raise_to - self.highest_bet, self.last_raise_size);
+        self.highest_bet = raise_to;
+        self.street_bet_count += 1;
+        self.add_to_pot(player_index, requested_funds, TransferReason::Raise);
+        self.reopen_action(player_index);
+        Ok(())
     }
 
+    fn call(&mut self, player_index: usize) -> Result<(), GameError> {
+        let player = &mut self.players[player_index];
+        let current_player_bet = player.get_current_bet();
+        let current_player_coins = player.get_coins();
+        let amount_to_call = self.highest_bet.saturating_sub(current_player_bet);
+
+        if amount_to_call == 0 {
+            return Err(GameError::NothingToCall);
+        }
+
+        // Calling with fewer coins than the bet puts the player all-in
+        let call_amount = std::cmp::min(amount_to_call, current_player_coins);
+        let requested_funds = self.game_dealer.player_call(player, call_amount);
+        if requested_funds == 0 {
+            return Err(GameError::FundsNotTransferred { player_index, action: PlayerAction::Call });
+        }
+        self.add_to_pot(player_index, requested_funds, TransferReason::Call);
+        Ok(())
+    }
 
-    fn fold(&mut self, player_index: usize) -> bool {
-        let remaining_players:&Vec<Player> = &self.players.iter()
+
+    fn fold(&mut self, player_index: usize) -> Result<(), GameError> {
+        let remaining_players = self.players.iter()
             .filter(|player| !player.has_player_folded())
-            .cloned()
-            .collect();
-        let has_everyone_else_folded:bool = remaining_players.len() == 1;
-        if !has_everyone_else_folded {
-            let player = &mut self.players[player_index];
-            player.fold();
-            true
-        } else {
-            false
+            .count();
+        if remaining_players == 1 {
+            return Err(GameError::LastPlayerCannotFold);
         }
+        self.players[player_index].fold();
+        Ok(())
     }
     
-    fn check(&mut self, player_index: usize) -> bool {
-        println!("player index: {}", player_index);
-        true
+    fn check(&mut self, player_index: usize) -> Result<(), GameError> {
+        let player = &self.players[player_index];
+        if player.get_current_bet() == self.highest_bet {
+            Ok(())
+        } else {
+            Err(GameError::CannotCheck { highest_bet: self.highest_bet })
+        }
     }
 
-    fn round_decisions(&mut self, current_player_index: Option<usize>) -> bool {
-        println!("highest bet: {:?}", self.highest_bet);
-    
-        let player_index = current_player_index.unwrap_or(0);
-        let mut next_player_index = (player_index + 1) % self.players.len();
-        let player = &mut self.players[player_index];
-    
-        if !player.has_player_folded() {
-            let player_decision = player.get_action(self.big_blind.clone());
-            let action = player_decision.action;
-            let action_success = player_decision.success;
-            let amount = player_decision.amount.unwrap_or_default();
-    
-            match action_success {
-                true => {
-                    match action {
-                        PlayerAction::Bet => {
-                            println!("{:?} chose to bet {}", player.get_player_username(), amount);
-                            let player_action_successful = self.bet(player_index, amount);
-                            if !player_action_successful {
-                                println!("Invalid bet amount. Try a different action.");
-                                return false;
-                            }
-                        },
-                        PlayerAction::Raise => {
-                            println!("{:?} chose to raise {}", player.get_player_username(), amount);
-                            let player_action_successful = self.raise(player_index, amount);
-                            if !player_action_successful {
-                                println!("Invalid raise amount. Try a different action.");
-                                return false;
-                            }
-                        },
-                        PlayerAction::Call => {
-                            println!("{:?} chose to call", player.get_player_username());
-                            let player_action_successful = self.call(player_index);
-                            if !player_action_successful {
-                                println!("Insufficient funds to call. Try a different action.");
-                                return false;
-                            }
-                        },
-                        PlayerAction::Check => {
-                            println!("{:?} chose to check", player.get_player_username());
-                            let player_action_successful = self.check(player_index);
-                            if !player_action_successful {
-                                println!("Invalid check. Try a different action.");
-                                return false;
-                            }
-                        },
-                        PlayerAction::Fold => {
-                            println!("{:?} chose to fold", player.get_player_username());
-                            let player_action_successful = self.fold(player_index);
-                            if !player_action_successful {
-                                println!("Failed to fold. Try a different action.");
-                                return false;
-                            }
-                        }
+    fn get_legal_actions(&self, player_index: usize) -> Vec<PlayerAction> {
+        let player = &self.players[player_index];
+        let amount_to_call = self.highest_bet.saturating_sub(player.get_current_bet());
+        let remaining_players = self.players.iter()
+            .filter(|player| !player.has_player_folded())
+            .count();
+        let mut legal_actions: Vec<PlayerAction> = Vec::new();
+
+        if amount_to_call == 0 {
+            legal_actions.push(PlayerAction::Check);
+        } else {
+            legal_actions.push(PlayerAction::Call);
+        }
+        if player.get_coins() > amount_to_call {
+            if self.highest_bet == 0 {
+                legal_actions.push(PlayerAction::Bet);
+            } else if !self.is_betting_capped() {
+                legal_actions.push(PlayerAction::Raise);
+            }
+        }
+        if remaining_players > 1 {
+            legal_actions.push(PlayerAction::Fold);
+        }
+        legal_actions
+    }
+
+    pub fn get_player_view(&self, seat: usize) -> Option<PlayerView> {
+        let player_index = self.players.iter().position(|player| player.get_seat() == seat)?;
+        let player = &self.players[player_index];
+        let is_to_act = self.action_index == Some(player_index);
+        let (minimum_to, maximum_to) = self.get_bet_limits(player_index);
+
+        // Other players are described through the viewing player's own notes
+        let stats_tracker = self.stats_trackers.get(&player.get_player_username());
+        let seats = self.players.iter()
+            .map(|player| SeatView {
+                player_id: player.get_player_id(),
+                username: player.get_player_username(),
+                seat: player.get_seat(),
+                coins: player.get_coins(),
+                current_bet: player.get_current_bet(),
+                total_bet: player.get_total_bet(),
+                folded: player.has_player_folded(),
+                all_in: player.is_all_in(),
+                sitting_out: player.get_sitting_out(),
+                stats: stats_tracker
+                    .and_then(|stats_tracker| stats_tracker.get_stats(&player.get_player_username()))
+                    .cloned()
+                    .unwrap_or_default(),
+            })
+            .collect();
+        let pots = if self.hand_phase == HandPhase::HandOver {
+            Vec::new()
+        } else {
+            self.build_pots().into_iter()
+                .map(|(amount, eligible_players)| PotView {
+                    amount,
+                    eligible_player_ids: eligible_players.iter().map(|&index| self.players[index].get_player_id()).collect(),
+                })
+                .collect()
+        };
+
+        Some(PlayerView {
+            hand_number: self.hand_number,
+            hand_phase: self.hand_phase,
+            player_id: player.get_player_id(),
+            seat,
+            hole_cards: player.get_hole_cards().clone(),
+            board: self.community_deck.clone(),
+            deck_count: self.game_dealer.get_deck_count(),
+            seats,
+            pots,
+            button_seat: self.button_seat,
+            small_blind_seat: self.small_blind_seat,
+            big_blind_seat: self.big_blind_seat,
+            big_blind: self.big_blind,
+            betting_structure: self.betting_structure,
+            highest_bet: self.highest_bet,
+            action_history: self.hand_history.clone(),
+            is_to_act,
+            legal_actions: if is_to_act { self.get_legal_actions(player_index) } else { Vec::new() },
+            amount_to_call: self.highest_bet.saturating_sub(player.get_current_bet()),
+            minimum_bet: minimum_to,
+            minimum_raise_to: minimum_to,
+            maximum_bet_to: maximum_to,
+            time_left_ms: if is_to_act { self.get_time_left(player_index).map(|time_left| u64::try_from(time_left.as_millis()).unwrap_or(u64::MAX)) } else { None },
+            time_bank_left_ms: self.get_time_bank_left(player_index),
+        })
+    }
+
+    fn request_player_action(&mut self, player_index: usize) -> Result<(), GameError> {
+        let player = &self.players[player_index];
+        let player_id = player.get_player_id();
+        let player_seat = player.get_seat();
+        // The clock starts before the view is built, so the view shows the time left
+        self.get_action_started_at(player_index);
+        for attempt in 1..=Self::MAX_ACTION_ATTEMPTS {
+            let player_view = self.get_player_view(player_seat).ok_or(GameError::PlayerNotFound { player_index })?;
+            let player_decision = match self.get_decision(player_id, player_view)? {
+                Some(player_decision) => player_decision,
+                None => {
+                    self.act_for_player(player_index)?;
+                    return Ok(());
+                },
+            };
+            if !player_decision.success {
+                return Err(GameError::PlayerDecisionFailed { player_index });
+            }
+            // A decision that arrives after the clock ran out is replaced by the timeout action
+            if self.check_action_timeout()? {
+                return Ok(());
+            }
+
+            // An illegal choice is reported and the player is asked again, until they run out of attempts
+            match self.apply_action(player_index, player_decision.action, player_decision.amount) {
+                Err(error) if error.get_kind() == GameErrorKind::IllegalAction => {
+                    let replaced_with = if attempt == Self::MAX_ACTION_ATTEMPTS { Some(self.get_default_action(player_index)) } else { None };
+                    self.emit(GameEvent::ActionRejected {
+                        player_id,
+                        action: player_decision.action,
+                        amount: player_decision.amount,
+                        reason: error.to_string(),
+                        replaced_with,
+                    });
+                    if let Some(default_action) = replaced_with {
+                        return self.apply_action(player_index, default_action, None);
                     }
                 },
-                false => {
-                    println!("Failed to get action from player");
-                    return false;
+                result => return result,
+            }
+        }
+        Ok(())
+    }
+
+    // Asks the player's agent to decide. With a time limit the agent decides on its own thread
+    // so a hung agent can't hold up the table: None means the time ran out first, or that the
+    // agent is still stuck on an earlier decision.
+    fn get_decision(&mut self, player_id: u32, player_view: PlayerView) -> Result<Option<ActionResult>, GameError> {
+        self.reclaim_busy_agent(player_id);
+        if self.busy_agents.contains_key(&player_id) {
+            return Ok(None);
+        }
+        let mut agent = match self.agents.remove(&player_id) {
+            Some(agent) => agent,
+            // The agent was dropped after panicking, so the disconnected player is acted for
+            None if self.is_disconnected(player_id) => return Ok(None),
+            None => return Err(GameError::AgentNotFound { player_id }),
+        };
+        let time_left = match player_view.time_left_ms {
+            Some(time_left_ms) => Duration::from_millis(time_left_ms),
+            None => {
+                let player_decision = agent.decide(&player_view);
+                self.return_agent(player_id, agent);
+                return Ok(Some(player_decision));
+            },
+        };
+
+        let (decision_sender, decisions) = mpsc::channel();
+        let decision_thread = std::thread::spawn(move || {
+            let player_decision = agent.decide(&player_view);
+            let _ = decision_sender.send((agent, player_decision));
+        });
+        match decisions.recv_timeout(time_left) {
+            Ok((agent, player_decision)) => {
+                let _ = decision_thread.join();
+                self.return_agent(player_id, agent);
+                Ok(Some(player_decision))
+            },
+            Err(RecvTimeoutError::Timeout) => {
+                self.busy_agents.insert(player_id, (decisions, decision_thread));
+                Ok(None)
+            },
+            Err(RecvTimeoutError::Disconnected) => {
+                self.drop_panicked_agent(player_id, decision_thread);
+                Ok(None)
+            },
+        }
+    }
+
+    // Takes back an agent whose late decision has finally come in
+    fn reclaim_busy_agent(&mut self, player_id: u32) {
+        let reclaimed = match self.busy_agents.get(&player_id) {
+            Some((decisions, _)) => decisions.try_recv(),
+            None => return,
+        };
+        match reclaimed {
+            Ok((agent, _)) => {
+                self.busy_agents.remove(&player_id);
+                self.return_agent(player_id, agent);
+            },
+            Err(TryRecvError::Empty) => {},
+            Err(TryRecvError::Disconnected) => {
+                if let Some((_, decision_thread)) = self.busy_agents.remove(&player_id) {
+                    self.drop_panicked_agent(player_id, decision_thread);
                 }
+            },
+        }
+    }
+
+    // Puts an agent back at the table and reports the problems it ran into while deciding
+    fn return_agent(&mut self, player_id: u32, mut agent: Box<dyn PlayerAgent>) {
+        for problem in agent.take_problems() {
+            self.emit(GameEvent::AgentProblem { player_id, problem });
+        }
+        self.agents.insert(player_id, agent);
+    }
+
+    // An agent that panicked on its decision thread, even long after its time ran out, is
+    // reported and dropped, and its player disconnected, so one bad bot can't end the session
+    fn drop_panicked_agent(&mut self, player_id: u32, decision_thread: JoinHandle<()>) {
+        let problem = match decision_thread.join() {
+            Err(panic) => match panic.downcast_ref::<&str>().map(|message| message.to_string()).or_else(|| panic.downcast_ref::<String>().cloned()) {
+                Some(message) => format!("the agent panicked: {}", message),
+                None => "the agent panicked".to_string(),
+            },
+            Ok(_) => "the agent stopped without sending a decision".to_string(),
+        };
+        self.emit(GameEvent::AgentProblem { player_id, problem });
+        if let Some(player_index) = self.players.iter().position(|player| player.get_player_id() == player_id) {
+            if self.players[player_index].is_connected() {
+                let _ = self.disconnect_player(player_index);
             }
-        } else {
-            next_player_index = (next_player_index + 1) % self.players.len();
         }
-    
-        println!("*************** is round over: {:?} ***************", self.check_round_over());
-        if !self.check_round_over() {
-            return self.round_decisions(Some(next_player_index));
+    }
+
+    fn is_disconnected(&self, player_id: u32) -> bool {
+        self.players.iter().any(|player| player.get_player_id() == player_id && !player.is_connected())
+    }
+
+    pub fn apply_action(&mut self, player_index: usize, action: PlayerAction, amount: Option<u32>) -> Result<(), GameError> {
+        if self.action_index != Some(player_index) {
+            return Err(GameError::NotPlayersTurn { player_index });
+        }
+
+        let undo_point = self.get_undo_point();
+        let time_overrun = self.get_time_overrun(player_index);
+        let amount = amount.unwrap_or_default();
+        let total_bet_before_action = self.players[player_index].get_total_bet();
+        match action {
+            PlayerAction::Bet => self.bet(player_index, amount),
+            PlayerAction::Raise => self.raise(player_index, amount),
+            PlayerAction::Call => self.call(player_index),
+            PlayerAction::Check => self.check(player_index),
+            PlayerAction::Fold => self.fold(player_index),
+        }?;
+
+        let time_bank_spent = std::cmp::min(time_overrun, self.get_time_bank_left(player_index));
+        self.action_timer = None;
+        let player = &mut self.players[player_index];
+        player.set_acted(true);
+        player.use_time_bank(time_bank_spent);
+        let player_acted_event = GameEvent::PlayerActed {
+            player_id: player.get_player_id(),
+            action,
+            amount: player.get_total_bet() - total_bet_before_action,
+            all_in: player.is_all_in(),
+        };
+        self.emit(player_acted_event);
+
+        if self.check_round_over() {
+            self.action_index = None;
         } else {
-            println!("===================================== Round Over ============================================");
-            return true;
+            self.action_index = Some(self.next_active_index(player_index));
         }
+        self.undo_stack.push(undo_point);
+        self.debug_audit_chips()
+    }
+
+    pub fn get_hand_phase(&self) -> HandPhase {
+        self.hand_phase
+    }
+
+    pub fn get_action_index(&self) -> Option<usize> {
+        self.action_index
     }
 
     fn request_dealer_open_cards(&mut self, card_amount_to_open: usize) {
@@ -279,10 +834,10 @@ impl PokerGame {
     }
 
     fn show_hands(&self) -> Vec<Player> {
-        let filter_check:usize = 0 as usize;
+        let filter_check:usize = 0;
         let eligible_players:Vec<Player> = self.players.iter()
+                                                            .filter(|&player| player.clone().get_hand_length() > filter_check && !player.has_player_folded())
                                                             .cloned()
-                                                            .filter(|player| player.clone().get_hand_length() > filter_check && !player.has_player_folded())
                                                             .map(|player| self.get_hand_rank(player, &self.game_dealer, &self.community_deck))
                                                             .collect();
 
@@ -290,66 +845,770 @@ impl PokerGame {
     }
 
 
-    fn determine_winner(&self) {
-        let players = self.show_hands();
-        let result = players.iter().fold(
-            (None, false, Vec::new()),
-            |(winner, tie_winners, mut tie_players), player| {
-                if winner.is_none() {
-                    (Some(player), false, Vec::new())
-                } else if player.hand_rank.as_ref().unwrap().hand_rank > winner.as_ref().unwrap().hand_rank.as_ref().unwrap().hand_rank {
-                    (Some(player), false, Vec::new())
-                } else if player.hand_rank.as_ref().unwrap().hand_rank == winner.as_ref().unwrap().hand_rank.as_ref().unwrap().hand_rank {
-                    let player_rank = player.hand_rank.as_ref().unwrap().high_card.get_rank().parse::<u32>();
-                    let winner_rank = winner.as_ref().unwrap().hand_rank.as_ref().unwrap().high_card.get_rank().parse::<u32>();
-        
-                    if player_rank.is_err() || winner_rank.is_err() {
-                        (winner, tie_winners, tie_players)
-                    } else {
-                        let player_rank = player_rank.unwrap();
-                        let winner_rank = winner_rank.unwrap();
-        
-                        if player_rank > winner_rank {
-                            (Some(player), false, Vec::new())
-                        } else if player_rank == winner_rank {
-                            if !tie_players.contains(winner.as_ref().unwrap()) {
-                                tie_players.push(winner.as_ref().unwrap().clone());
-                            }
-                            tie_players.push(player);
-                            (winner, true, tie_players)
-                        } else {
-                            (winner, tie_winners, tie_players)
-                        }
-                    }
-                } else {
-                    (winner, tie_winners, tie_players)
+    fn get_hand_strength(&self, player: &mut Player) -> HandValue {
+        let mut cards = player.get_current_hand().clone();
+        cards.extend(self.community_deck.iter().cloned());
+        Self::evaluate_hand(&cards)
+    }
+
+    // Splits everything put in this hand into a main pot and side pots, each with the
+    // indexes of the players still able to win it.
+    fn build_pots(&self) -> Vec<(u32, Vec<usize>)> {
+        let mut contribution_levels: Vec<u32> = self.players.iter()
+            .filter(|player| !player.has_player_folded() && player.get_total_bet() > 0)
+            .map(|player| player.get_total_bet())
+            .collect();
+        contribution_levels.sort();
+        contribution_levels.dedup();
+
+        let mut pots: Vec<(u32, Vec<usize>)> = Vec::new();
+        let mut previous_level = 0;
+        for level in contribution_levels {
+            let pot_amount: u32 = self.players.iter()
+                .map(|player| std::cmp::min(player.get_total_bet(), level) - std::cmp::min(player.get_total_bet(), previous_level))
+                .sum();
+            let eligible_players: Vec<usize> = self.players.iter()
+                .enumerate()
+                .filter(|(_, player)| !player.has_player_folded() && player.get_total_bet() >= level)
+                .map(|(index, _)| index)
+                .collect();
+            pots.push((pot_amount, eligible_players));
+            previous_level = level;
+        }
+
+        // Folded players can have put in more than anyone left in the hand
+        let unmatched_amount: u32 = self.players.iter()
+            .map(|player| player.get_total_bet().saturating_sub(previous_level))
+            .sum();
+        if let Some(last_pot) = pots.last_mut() {
+            last_pot.0 += unmatched_amount;
+        }
+        pots
+    }
+
+    fn return_uncalled_bet(&mut self) {
+        let mut total_bets: Vec<(usize, u32)> = self.players.iter()
+            .enumerate()
+            .map(|(index, player)| (index, player.get_total_bet()))
+            .collect();
+        total_bets.sort_by_key(|(_, total_bet)| std::cmp::Reverse(*total_bet));
+
+        if let [(top_index, top_bet), (_, second_bet), ..] = total_bets[..] {
+            let uncalled_amount = top_bet - second_bet;
+            if uncalled_amount > 0 && !self.players[top_index].has_player_folded() {
+                self.players[top_index].decrease_total_bet(uncalled_amount);
+                self.pay_from_pot(top_index, uncalled_amount, TransferReason::Refund);
+                let player_id = self.players[top_index].get_player_id();
+                self.emit(GameEvent::UncalledBetReturned { player_id, amount: uncalled_amount });
+            }
+        }
+    }
+
+    fn determine_winner(&mut self) {
+        self.return_uncalled_bet();
+        let players_dealt_in = self.get_seated_player_count();
+        let flop_dealt = !self.community_deck.is_empty();
+        let mut pot_rakes: Vec<u32> = Vec::new();
+        let remaining_players = self.players.iter()
+            .filter(|player| !player.has_player_folded())
+            .count();
+        let mut hand_strengths: Vec<(u32, HandValue)> = Vec::new();
+        if remaining_players > 1 {
+            let mut shown_players = self.show_hands();
+            hand_strengths = shown_players.iter_mut()
+                .map(|player| (player.get_player_id(), self.get_hand_strength(player)))
+                .collect();
+            let hands = shown_players.into_iter()
+                .zip(hand_strengths.iter())
+                .map(|(mut player, (player_id, strength))| (*player_id, player.get_current_hand().clone(), strength.clone()))
+                .collect();
+            self.emit(GameEvent::Showdown { hands });
+        }
+        let get_strength = |player: &Player| {
+            hand_strengths.iter()
+                .find(|(player_id, _)| *player_id == player.get_player_id())
+                .map(|(_, strength)| strength.clone())
+        };
+
+        for (pot_amount, eligible_players) in self.build_pots() {
+            let best_strength = eligible_players.iter().map(|&index| get_strength(&self.players[index])).max().flatten();
+            let mut winners: Vec<usize> = eligible_players.iter()
+                .filter(|&&index| get_strength(&self.players[index]) == best_strength)
+                .cloned()
+                .collect();
+            if winners.is_empty() {
+                continue;
+            }
+
+            let rake = self.rake_settings.calculate_rake(pot_amount, players_dealt_in, flop_dealt);
+            self.chip_ledger.record_transfer(self.hand_number, ChipAccount::Pot, ChipAccount::House, rake, TransferReason::Rake);
+            self.pot -= rake;
+            pot_rakes.push(rake);
+            let awarded_amount = pot_amount - rake;
+
+            // Odd chips go to the winner closest to the left of the button
+            let button_seat = self.button_seat;
+            winners.sort_by_key(|&index| (self.players[index].get_seat() + Self::MAX_SEATS - button_seat - 1) % Self::MAX_SEATS);
+            let share = awarded_amount / winners.len() as u32;
+            let odd_chips = awarded_amount % winners.len() as u32;
+            let mut pot_winners: Vec<(u32, u32)> = Vec::new();
+            for (position, &index) in winners.iter().enumerate() {
+                let winnings = share + if (position as u32) < odd_chips { 1 } else { 0 };
+                self.pay_from_pot(index, winnings, TransferReason::Award);
+                pot_winners.push((self.players[index].get_player_id(), winnings));
+            }
+            self.emit(GameEvent::PotAwarded { amount: pot_amount, rake, winners: pot_winners });
+        }
+
+        let total_rake = self.house_ledger.record_hand(self.hand_number, pot_rakes).total_rake;
+        self.emit(GameEvent::HandEnded { hand_number: self.hand_number, total_rake });
+
+        self.set_hand_phase(HandPhase::HandOver);
+        self.record_hand_stats();
+        self.notify_hand_end();
+    }
+
+    fn record_hand_stats(&mut self) {
+        let dealt_player_ids: Vec<u32> = self.hand_history.iter()
+            .filter_map(|event| match event {
+                GameEvent::HoleCardsDealt { player_id, .. } => Some(*player_id),
+                _ => None,
+            })
+            .collect();
+        let dealt_usernames: Vec<String> = self.players.iter()
+            .filter(|player| dealt_player_ids.contains(&player.get_player_id()))
+            .map(|player| player.get_player_username())
+            .collect();
+        if let Some(undo_point) = self.undo_stack.last_mut() {
+            undo_point.stats_trackers_before_hand = Some(self.stats_trackers.clone());
+        }
+        for username in dealt_usernames {
+            self.stats_trackers.entry(username).or_default().record_hand(&self.hand_history);
+        }
+    }
+
+    // Every agent sees how the hand ended, including any hands shown down
+    fn notify_hand_end(&mut self) {
+        let busy_player_ids: Vec<u32> = self.busy_agents.keys().cloned().collect();
+        for player_id in busy_player_ids {
+            self.reclaim_busy_agent(player_id);
+        }
+        let final_views: Vec<PlayerView> = self.players.iter()
+            .filter_map(|player| self.get_player_view(player.get_seat()))
+            .collect();
+        for final_view in final_views {
+            if let Some(mut agent) = self.agents.remove(&final_view.player_id) {
+                agent.on_hand_end(&final_view);
+                self.return_agent(final_view.player_id, agent);
+            }
+        }
+    }
+
+    fn pay_from_pot(&mut self, player_index: usize, amount: u32, reason: TransferReason) {
+        let player = &mut self.players[player_index];
+        self.game_dealer.pay_player(player, amount);
+        let player_id = player.get_player_id();
+        self.chip_ledger.record_transfer(self.hand_number, ChipAccount::Pot, ChipAccount::Player(player_id), amount, reason);
+        self.pot -= amount;
+    }
+
+    fn debug_audit_chips(&self) -> Result<(), GameError> {
+        if cfg!(debug_assertions) {
+            self.audit_chips()
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn from_table_config(table_config: TableConfig, registry: &AgentRegistry) -> Result<Self, GameError> {
+        let stacked_cards = table_config.get_stacked_cards()?;
+        let mut game_dealer = Dealer::new(table_config.dealer_type.clone());
+        if let Some(rng_seed) = table_config.rng_seed {
+            game_dealer.set_rng_seed(rng_seed);
+        }
+
+        let players: Vec<Player> = table_config.players.iter()
+            .map(|seat_config| Player::new(seat_config.id, seat_config.username.clone(), seat_config.coins))
+            .collect();
+        let mut poker_game = PokerGame::new(players, game_dealer);
+        poker_game.max_seats = table_config.max_seats;
+        for seat_config in table_config.players.iter() {
+            let agent = registry.create(&seat_config.agent, &seat_config.agent_params)?;
+            poker_game.set_agent(seat_config.id, agent);
+        }
+
+        // Requested seats first, then everyone else takes the first free seat
+        let mut taken_seats: Vec<usize> = table_config.players.iter().filter_map(|seat_config| seat_config.seat).collect();
+        for (player, seat_config) in poker_game.players.iter_mut().zip(table_config.players.iter()) {
+            let seat = match seat_config.seat {
+                Some(seat) => seat,
+                None => {
+                    let free_seat = (0..table_config.max_seats).find(|seat| !taken_seats.contains(seat)).unwrap_or_default();
+                    taken_seats.push(free_seat);
+                    free_seat
                 }
+            };
+            player.set_seat(seat);
+        }
+        poker_game.players.sort_by_key(|player| player.get_seat());
+
+        let button_index = match table_config.rng_seed {
+            Some(rng_seed) => StdRng::seed_from_u64(rng_seed).gen_range(0..poker_game.players.len()),
+            None => rand::thread_rng().gen_range(0..poker_game.players.len()),
+        };
+        poker_game.button_seat = poker_game.players[button_index].get_seat();
+        poker_game.assign_blind_positions();
+
+        poker_game.betting_structure = table_config.betting_structure;
+        poker_game.time_limits = table_config.time_limits;
+        poker_game.set_rake_settings(table_config.rake);
+        poker_game.play_with_blinds(table_config.stakes.big_blind > 0);
+        poker_game.set_forced_bets(table_config.stakes);
+        poker_game.request_generate_deck(table_config.deck_count)?;
+        poker_game.request_dealer_shuffle()?;
+        if let Some(stats_directory) = table_config.stats_directory {
+            poker_game.set_stats_directory(stats_directory);
+            poker_game.load_stats()?;
+        }
+        if !stacked_cards.is_empty() {
+            poker_game.stack_deck(stacked_cards)?;
+        }
+        Ok(poker_game)
+    }
+
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.clock = clock;
+        self.action_timer = None;
+    }
+
+    pub fn set_time_limits(&mut self, time_limits: TimeLimits) {
+        self.time_limits = time_limits;
+    }
+
+    // When the player's turn started, starting their timer the first time it is asked for
+    fn get_action_started_at(&mut self, player_index: usize) -> Duration {
+        match self.action_timer {
+            Some((timed_index, started_at)) if timed_index == player_index => started_at,
+            _ => {
+                let now = self.clock.now();
+                self.action_timer = Some((player_index, now));
+                now
+            }
+        }
+    }
+
+    // Milliseconds the player has spent beyond the per-action limit
+    fn get_time_overrun(&mut self, player_index: usize) -> u64 {
+        let started_at = self.get_action_started_at(player_index);
+        match self.time_limits.action_seconds {
+            Some(action_seconds) => {
+                let elapsed = self.clock.now().saturating_sub(started_at);
+                (elapsed.as_millis() as u64).saturating_sub(action_seconds.saturating_mul(1000))
             },
-        );
-    
-        let cloned_result = result.clone();
-        //let final_winner = cloned_result.0;
-        let mut tie_winners = cloned_result.1;
-        let mut tie_players = cloned_result.2;
-    
-        // Handle final winner and tie scenarios
-        if tie_players.len() == 1 {
-            let winner = tie_players[0];
-            tie_winners = false;
-            tie_players = Vec::new();
-            println!("Winner: {:?}", winner);
+            None => 0,
+        }
+    }
+
+    fn get_time_bank_left(&self, player_index: usize) -> u64 {
+        self.time_limits.time_bank_seconds.saturating_mul(1000).saturating_sub(self.players[player_index].get_time_bank_used())
+    }
+
+    // Time the player to act has left including their time bank, None without a limit
+    pub fn get_time_remaining(&mut self) -> Option<Duration> {
+        let player_index = self.action_index?;
+        self.get_action_started_at(player_index);
+        self.get_time_left(player_index)
+    }
+
+    // Time left for a player's decision, counted from when their timer started
+    fn get_time_left(&self, player_index: usize) -> Option<Duration> {
+        let action_seconds = self.time_limits.action_seconds?;
+        let elapsed = match self.action_timer {
+            Some((timed_index, started_at)) if timed_index == player_index => self.clock.now().saturating_sub(started_at),
+            _ => Duration::ZERO,
+        };
+        let allowed = Duration::from_secs(action_seconds).saturating_add(Duration::from_millis(self.get_time_bank_left(player_index)));
+        Some(allowed.saturating_sub(elapsed))
+    }
+
+    // What the engine does for a player who can't or won't act: check if possible, otherwise fold
+    fn get_default_action(&self, player_index: usize) -> PlayerAction {
+        if self.players[player_index].get_current_bet() == self.highest_bet { PlayerAction::Check } else { PlayerAction::Fold }
+    }
+
+    // Acts for a player who is disconnected or out of time
+    pub fn check_action_timeout(&mut self) -> Result<bool, GameError> {
+        let player_index = match self.action_index {
+            Some(player_index) => player_index,
+            None => return Ok(false),
+        };
+        let is_disconnected = !self.players[player_index].is_connected();
+        let is_out_of_time = self.get_time_overrun(player_index) > self.get_time_bank_left(player_index);
+        if !is_disconnected && !is_out_of_time {
+            return Ok(false);
+        }
+
+        self.act_for_player(player_index)?;
+        Ok(true)
+    }
+
+    fn act_for_player(&mut self, player_index: usize) -> Result<(), GameError> {
+        let action = self.get_default_action(player_index);
+        let player_id = self.players[player_index].get_player_id();
+        self.emit(GameEvent::PlayerTimedOut { player_id, action });
+        self.apply_action(player_index, action, None)
+    }
+
+    // The player's turns are acted for until the hand is over, and they sit out from the next
+    // hand, so the number of players in this one does not change under it
+    pub fn disconnect_player(&mut self, player_index: usize) -> Result<(), GameError> {
+        let player = self.players.get_mut(player_index).ok_or(GameError::PlayerNotFound { player_index })?;
+        player.set_connected(false);
+        let player_id = player.get_player_id();
+        self.emit(GameEvent::PlayerDisconnected { player_id });
+        Ok(())
+    }
+
+    // A player who reconnects before the next hand keeps playing; after that they stay
+    // sitting out until they return to the table
+    pub fn reconnect_player(&mut self, player_index: usize) -> Result<(), GameError> {
+        let player = self.players.get_mut(player_index).ok_or(GameError::PlayerNotFound { player_index })?;
+        player.set_connected(true);
+        Ok(())
+    }
+
+    pub fn get_betting_structure(&self) -> BettingStructure {
+        self.betting_structure
+    }
+
+    pub fn get_time_limits(&self) -> &TimeLimits {
+        &self.time_limits
+    }
+
+    // Starts a separate table from a snapshot, with no observers attached
+    pub fn from_snapshot(snapshot: GameSnapshot) -> Self {
+        let mut poker_game = PokerGame {
+            game_id: snapshot.game_id,
+            players: snapshot.players,
+            game_dealer: snapshot.game_dealer,
+            pot: snapshot.pot,
+            waiting_players: snapshot.waiting_players,
+            hand_number: snapshot.hand_number,
+            with_blinds: snapshot.with_blinds,
+            button_seat: snapshot.button_seat,
+            small_blind_seat: snapshot.small_blind_seat,
+            big_blind_seat: snapshot.big_blind_seat,
+            straddle_seat: snapshot.straddle_seat,
+            blinds_initialized: snapshot.blinds_initialized,
+            big_blind: snapshot.big_blind,
+            forced_bets: snapshot.forced_bets,
+            rake_settings: snapshot.rake_settings,
+            house_ledger: snapshot.house_ledger,
+            chip_ledger: snapshot.chip_ledger,
+            hand_phase: snapshot.hand_phase,
+            action_index: snapshot.action_index,
+            hand_history: snapshot.hand_history,
+            highest_bet: snapshot.highest_bet,
+            last_raise_size: snapshot.last_raise_size,
+            max_seats: snapshot.max_seats,
+            betting_structure: snapshot.betting_structure,
+            time_limits: snapshot.time_limits,
+            street_bet_count: snapshot.street_bet_count,
+            community_deck: snapshot.community_deck,
+            observers: Vec::new(),
+            agents: HashMap::new(),
+            busy_agents: HashMap::new(),
+            stats_trackers: snapshot.stats_trackers,
+            stats_directory: None,
+            undo_stack: Vec::new(),
+            clock: Box::new(SystemClock::new()),
+            action_timer: None
+        };
+        poker_game.restore_hidden_cards(snapshot.hole_cards, snapshot.deck, snapshot.stacked_decks);
+        poker_game.install_default_agents();
+        poker_game
+    }
+
+    pub fn set_agent(&mut self, player_id: u32, agent: Box<dyn PlayerAgent>) {
+        self.busy_agents.remove(&player_id);
+        self.agents.insert(player_id, agent);
+    }
+
+    pub fn get_agent_name(&self, player_id: u32) -> Option<&str> {
+        self.agents.get(&player_id).map(|agent| agent.get_agent_name())
+    }
+
+    // Players without an agent of their own act randomly
+    fn install_default_agents(&mut self) {
+        let player_ids: Vec<u32> = self.players.iter()
+            .chain(self.waiting_players.iter())
+            .map(|player| player.get_player_id())
+            .filter(|player_id| !self.busy_agents.contains_key(player_id))
+            .collect();
+        for player_id in player_ids {
+            self.agents.entry(player_id).or_insert_with(|| Box::new(RandomAgent::new()));
+        }
+    }
+
+    pub fn snapshot(&self) -> GameSnapshot {
+        GameSnapshot {
+            chip_ledger: self.chip_ledger.clone(),
+            house_ledger: self.house_ledger.clone(),
+            hand_history: self.hand_history.clone(),
+            stats_trackers: self.stats_trackers.clone(),
+            ..self.snapshot_without_history()
+        }
+    }
+
+    // Everything but the ledgers, the hand history and the stats, which grow over a session
+    fn snapshot_without_history(&self) -> GameSnapshot {
+        GameSnapshot {
+            game_id: self.game_id.clone(),
+            players: self.players.clone(),
+            game_dealer: self.game_dealer.clone(),
+            pot: self.pot,
+            waiting_players: self.waiting_players.clone(),
+            hand_number: self.hand_number,
+            with_blinds: self.with_blinds,
+            button_seat: self.button_seat,
+            small_blind_seat: self.small_blind_seat,
+            big_blind_seat: self.big_blind_seat,
+            straddle_seat: self.straddle_seat,
+            blinds_initialized: self.blinds_initialized,
+            big_blind: self.big_blind,
+            forced_bets: self.forced_bets.clone(),
+            rake_settings: self.rake_settings.clone(),
+            house_ledger: HouseLedger::default(),
+            chip_ledger: ChipLedger::default(),
+            hand_phase: self.hand_phase,
+            action_index: self.action_index,
+            hand_history: Vec::new(),
+            highest_bet: self.highest_bet,
+            last_raise_size: self.last_raise_size,
+            max_seats: self.max_seats,
+            betting_structure: self.betting_structure,
+            time_limits: self.time_limits.clone(),
+            street_bet_count: self.street_bet_count,
+            community_deck: self.community_deck.clone(),
+            hole_cards: self.players.iter()
+                .chain(self.waiting_players.iter())
+                .map(|player| (player.get_player_id(), player.get_hole_cards().clone()))
+                .collect(),
+            deck: self.game_dealer.get_deck().clone(),
+            stacked_decks: self.game_dealer.get_stacked_decks().clone(),
+            stats_trackers: HashMap::new()
+        }
+    }
+
+    fn restore_hidden_cards(&mut self, hole_cards: Vec<(u32, Vec<Card>)>, deck: Option<Vec<Card>>, stacked_decks: Vec<Vec<Card>>) {
+        for (player_id, cards) in hole_cards {
+            let player = self.players.iter_mut()
+                .chain(self.waiting_players.iter_mut())
+                .find(|player| player.get_player_id() == player_id);
+            if let Some(player) = player {
+                *player.get_current_hand() = cards;
+            }
+        }
+        self.game_dealer.set_cards(deck, stacked_decks);
+    }
+
+    fn load_snapshot(&mut self, snapshot: GameSnapshot) {
+        self.game_id = snapshot.game_id;
+        self.players = snapshot.players;
+        self.game_dealer = snapshot.game_dealer;
+        self.pot = snapshot.pot;
+        self.waiting_players = snapshot.waiting_players;
+        self.hand_number = snapshot.hand_number;
+        self.with_blinds = snapshot.with_blinds;
+        self.button_seat = snapshot.button_seat;
+        self.small_blind_seat = snapshot.small_blind_seat;
+        self.big_blind_seat = snapshot.big_blind_seat;
+        self.straddle_seat = snapshot.straddle_seat;
+        self.blinds_initialized = snapshot.blinds_initialized;
+        self.big_blind = snapshot.big_blind;
+        self.forced_bets = snapshot.forced_bets;
+        self.rake_settings = snapshot.rake_settings;
+        self.house_ledger = snapshot.house_ledger;
+        self.chip_ledger = snapshot.chip_ledger;
+        self.hand_phase = snapshot.hand_phase;
+        self.action_index = snapshot.action_index;
+        self.hand_history = snapshot.hand_history;
+        self.highest_bet = snapshot.highest_bet;
+        self.last_raise_size = snapshot.last_raise_size;
+        self.max_seats = snapshot.max_seats;
+        self.betting_structure = snapshot.betting_structure;
+        self.time_limits = snapshot.time_limits;
+        self.street_bet_count = snapshot.street_bet_count;
+        self.community_deck = snapshot.community_deck;
+        self.stats_trackers = snapshot.stats_trackers;
+        self.restore_hidden_cards(snapshot.hole_cards, snapshot.deck, snapshot.stacked_decks);
+        self.action_timer = None;
+        self.install_default_agents();
+    }
+
+    // The snapshot is checked before anything is restored, so a bad one leaves the table as it was
+    pub fn restore(&mut self, snapshot: GameSnapshot) -> Result<(), GameError> {
+        snapshot.validate()?;
+        self.load_snapshot(snapshot);
+        self.undo_stack.clear();
+        Ok(())
+    }
+
+    pub fn restore_from_bytes(&mut self, bytes: &[u8]) -> Result<(), GameError> {
+        self.restore(GameSnapshot::from_bytes(bytes)?)
+    }
+
+    pub fn undo(&mut self, number_of_actions: usize) -> Result<(), GameError> {
+        let available = self.undo_stack.len();
+        if number_of_actions > available {
+            return Err(GameError::NothingToUndo { requested: number_of_actions, available });
+        }
+        if number_of_actions == 0 {
+            return Ok(());
+        }
+        let mut undo_points = self.undo_stack.split_off(available - number_of_actions);
+        // Undoing the end of the hand takes its stats back out too
+        let stats_trackers = undo_points.iter_mut()
+            .find_map(|undo_point| undo_point.stats_trackers_before_hand.take())
+            .unwrap_or_else(|| std::mem::take(&mut self.stats_trackers));
+        let undo_point = undo_points.swap_remove(0);
+        let mut chip_ledger = std::mem::take(&mut self.chip_ledger);
+        let mut house_ledger = std::mem::take(&mut self.house_ledger);
+        let mut hand_history = std::mem::take(&mut self.hand_history);
+        chip_ledger.truncate(undo_point.chip_transfer_count);
+        house_ledger.truncate(undo_point.house_hand_count);
+        hand_history.truncate(undo_point.hand_history_length);
+        self.load_snapshot(undo_point.snapshot);
+        self.chip_ledger = chip_ledger;
+        self.house_ledger = house_ledger;
+        self.hand_history = hand_history;
+        self.stats_trackers = stats_trackers;
+        Ok(())
+    }
+
+    fn get_undo_point(&self) -> UndoPoint {
+        UndoPoint {
+            snapshot: self.snapshot_without_history(),
+            chip_transfer_count: self.chip_ledger.get_transfers().len(),
+            house_hand_count: self.house_ledger.get_hands().len(),
+            hand_history_length: self.hand_history.len(),
+            stats_trackers_before_hand: None
+        }
+    }
+
+    pub fn get_undo_count(&self) -> usize {
+        self.undo_stack.len()
+    }
+
+    pub fn get_chip_ledger(&self) -> &ChipLedger {
+        &self.chip_ledger
+    }
+
+    // Stacks, the pot and the house's rake must always add up to every chip bought in
+    pub fn audit_chips(&self) -> Result<(), GameError> {
+        let stacks: u32 = self.players.iter()
+            .chain(self.waiting_players.iter())
+            .map(|player| player.get_coins())
+            .sum();
+        self.chip_ledger.audit(stacks, self.pot, self.house_ledger.get_total_rake())
+    }
+
+    pub fn set_rake_settings(&mut self, rake_settings: RakeSettings) {
+        self.rake_settings = rake_settings;
+    }
+
+    pub fn get_house_ledger(&self) -> &HouseLedger {
+        &self.house_ledger
+    }
+
+    pub fn get_hand_number(&self) -> u32 {
+        self.hand_number
+    }
+
+    // Puts every player back to the same stack between hands, topping up from the cashier
+    // and cashing out anything above it, the way computer poker matches are played
+    pub fn reset_stacks(&mut self, coins: u32) -> Result<(), GameError> {
+        if self.hand_phase != HandPhase::Initial && self.hand_phase != HandPhase::HandOver {
+            return Err(GameError::HandInProgress);
+        }
+        for player in self.players.iter_mut() {
+            let player_id = player.get_player_id();
+            let stack = player.get_coins();
+            if stack < coins {
+                self.chip_ledger.record_transfer(self.hand_number, ChipAccount::Cashier, ChipAccount::Player(player_id), coins - stack, TransferReason::BuyIn);
+            } else if stack > coins {
+                self.chip_ledger.record_transfer(self.hand_number, ChipAccount::Player(player_id), ChipAccount::Cashier, stack - coins, TransferReason::CashOut);
+            }
+            player.set_coins(coins);
+        }
+        self.debug_audit_chips()
+    }
+
+    pub fn sit_down_player(&mut self, player: Player) -> Result<usize, GameError> {
+        let taken_seats: Vec<usize> = self.players.iter()
+            .chain(self.waiting_players.iter())
+            .map(|player| player.get_seat())
+            .collect();
+        match (0..self.max_seats).find(|seat| !taken_seats.contains(seat)) {
+            Some(seat) => {
+                let mut new_player = player;
+                new_player.set_seat(seat);
+                let player_id = new_player.get_player_id();
+                self.load_player_stats(new_player.get_player_username())?;
+                self.chip_ledger.record_transfer(self.hand_number, ChipAccount::Cashier, ChipAccount::Player(player_id), new_player.get_coins(), TransferReason::BuyIn);
+                self.waiting_players.push(new_player);
+                self.install_default_agents();
+                Ok(seat)
+            },
+            None => Err(GameError::TableFull),
+        }
+    }
+
+    fn reset_hand(&mut self) {
+        for player in self.players.iter_mut() {
+            player.reset_for_next_hand();
+        }
+        self.pot = 0;
+        self.community_deck.clear();
+        self.highest_bet = 0;
+        self.last_raise_size = 0;
+        self.street_bet_count = 0;
+        self.straddle_seat = None;
+        self.action_index = None;
+        self.blinds_initialized = false;
+    }
+
+    pub fn start_next_hand(&mut self) -> Result<(), GameError> {
+        let is_first_hand = self.hand_phase == HandPhase::Initial;
+        if !is_first_hand && self.hand_phase != HandPhase::HandOver {
+            return Err(GameError::HandInProgress);
+        }
+
+        if !is_first_hand {
+            let (remaining_players, busted_players): (Vec<Player>, Vec<Player>) = self.players.drain(..)
+                .partition(|player| player.get_coins() > 0);
+            self.players = remaining_players;
+            // A busted player's agent leaves with them, even one still stuck on a late decision
+            for player in busted_players {
+                let player_id = player.get_player_id();
+                self.agents.remove(&player_id);
+                self.busy_agents.remove(&player_id);
+                self.emit(GameEvent::PlayerLeft { player_id, username: player.get_player_username() });
+            }
+        }
+
+        for player in self.players.iter_mut() {
+            if !player.is_connected() {
+                player.set_sitting_out(true);
+            }
+        }
+
+        // New players post a big blind to enter unless they arrive in the big blind
+        for mut player in std::mem::take(&mut self.waiting_players) {
+            if !is_first_hand {
+                player.set_missed_big_blind(true);
+            }
+            self.players.push(player);
+        }
+        self.players.sort_by_key(|player| player.get_seat());
+
+        if self.get_seated_player_count() < 2 {
+            return Err(GameError::NotEnoughPlayers);
+        }
+
+        if is_first_hand {
+            self.assign_blind_positions();
         } else {
-            println!("result: {:?}", result);
+            // The deck comes first so a bad stacked deck fails before the table moves on
+            self.prepare_deck()?;
+            self.reset_hand();
+            self.rotate_blind_positions();
+            self.hand_number += 1;
+            self.set_hand_phase(HandPhase::Initial);
         }
 
-        println!("tie_winners: {:?}", tie_winners);
-        println!("tie_players: {:?}", tie_players);
-    
-        // Perform any additional actions with the winner, tie players, etc.
+        let players = self.players.iter()
+            .filter(|player| !player.get_sitting_out())
+            .map(|player| SeatSummary {
+                player_id: player.get_player_id(),
+                username: player.get_player_username(),
+                seat: player.get_seat(),
+                coins: player.get_coins(),
+            })
+            .collect();
+        self.hand_history.clear();
+        self.undo_stack.clear();
+        self.emit(GameEvent::HandStarted { hand_number: self.hand_number, button_seat: self.button_seat, players });
+
+        if self.with_blinds == Some(true) && !self.blinds_initialized {
+            self.post_forced_bets()?;
+        }
+        if !self.are_players_ready() {
+            self.deal_cards()?;
+        }
+        Ok(())
     }
 
+    pub fn play_session(&mut self, number_of_hands: u32) -> Result<(), GameError> {
+        let mut session_result = Ok(());
+        for _ in 0..number_of_hands {
+            session_result = self.start_next_hand().and_then(|_| self.play());
+            if session_result.is_err() {
+                break;
+            }
+        }
+        // Notes are kept even when the session ends early
+        self.save_stats()?;
+        session_result
+    }
+
+    pub fn get_stats_tracker(&self, username: &str) -> Option<&StatsTracker> {
+        self.stats_trackers.get(username)
+    }
+
+    pub fn set_stats_directory<P: Into<PathBuf>>(&mut self, stats_directory: P) {
+        self.stats_directory = Some(stats_directory.into());
+    }
+
+    fn get_stats_path(&self, username: &str) -> Option<PathBuf> {
+        let file_name: String = username.chars()
+            .map(|character| if character.is_ascii_alphanumeric() || character == '-' || character == '_' { character } else { '_' })
+            .collect();
+        self.stats_directory.as_ref().map(|stats_directory| stats_directory.join(format!("{}.json", file_name)))
+    }
+
+    // Loads the saved notes of every player who has a file in the stats directory
+    pub fn load_stats(&mut self) -> Result<(), GameError> {
+        let usernames: Vec<String> = self.players.iter()
+            .chain(self.waiting_players.iter())
+            .map(|player| player.get_player_username())
+            .collect();
+        for username in usernames {
+            self.load_player_stats(username)?;
+        }
+        Ok(())
+    }
 
+    fn load_player_stats(&mut self, username: String) -> Result<(), GameError> {
+        if let Some(stats_path) = self.get_stats_path(&username).filter(|stats_path| stats_path.exists()) {
+            self.stats_trackers.insert(username, StatsTracker::from_json_file(stats_path)?);
+        }
+        Ok(())
+    }
 
+    // Writes the notes of everyone who has played here to the stats directory, when one is set
+    pub fn save_stats(&self) -> Result<(), GameError> {
+        if let Some(stats_directory) = &self.stats_directory {
+            std::fs::create_dir_all(stats_directory)
+                .map_err(|error| GameError::StatsNotSaved { reason: format!("{}: {}", stats_directory.display(), error) })?;
+        }
+        for (username, stats_tracker) in self.stats_trackers.iter() {
+            if let Some(stats_path) = self.get_stats_path(username) {
+                stats_tracker.to_json_file(stats_path)?;
+            }
+        }
+        Ok(())
+    }
 
     pub fn play_with_blinds(&mut self, with_blinds: bool) {
         self.with_blinds = Some(with_blinds);
@@ -373,138 +1632,283 @@ impl PokerGame {
         println!("dealer_id: {}", dealer_id);
         println!("dealer_type: {}", dealer_type);
         //println!("dealer deck: {:?}", current_dealer.get_deck());
-        println!("dealer deck length: {:?}", current_dealer.get_deck().as_ref().map(|deck| deck.len()).unwrap_or(0));
+        println!("dealer deck length: {:?}", current_dealer.get_cards_left());
         println!("deck_id: {}", deck_id);
         println!("game_id: {}", game_id);
         println!("pot: {}", pot);
+        println!("house_rake: {}", self.house_ledger.get_total_rake());
+        println!("hand_number: {}", self.hand_number);
+        println!("button_seat: {}", self.button_seat);
         println!("community_deck: {:?}", community_deck);
     }
 
-    pub fn request_generate_deck(&mut self, deck_count: usize) {
-        self.game_dealer.generate_cards(deck_count);
+    pub fn request_generate_deck(&mut self, deck_count: usize) -> Result<(), GameError> {
+        if deck_count == 0 {
+            return Err(GameError::InvalidDeckCount);
+        }
+        if !self.game_dealer.generate_cards(deck_count) {
+            return Err(GameError::DeckAlreadyGenerated);
+        }
+        Ok(())
     }
 
-    pub fn request_dealer_shuffle(&mut self) {
+    pub fn request_dealer_shuffle(&mut self) -> Result<(), GameError> {
+        if self.game_dealer.get_deck().is_none() {
+            return Err(GameError::NoDeck);
+        }
         self.game_dealer.shuffle_cards();
+        self.arrange_stacked_deck()
     }
 
-    pub fn deal_cards(&mut self) {
-        if self.is_dealer_ready() {
-            let current_players = &mut self.players;
-            current_players.iter_mut().for_each(|player| self.game_dealer.deal_player(&mut Some(player)));
-        } else {
-            println!("Unable to deal cards. Dealer is not ready.");
+    // Stacks the deck for a scripted hand. Hole cards come off first, two at a time for each
+    // player in seat order, then the flop, turn and river. Before anything is dealt the current
+    // deck is stacked, otherwise the deck for the next hand is.
+    pub fn stack_deck(&mut self, stacked_cards: Vec<Card>) -> Result<(), GameError> {
+        let deck_count = self.game_dealer.get_deck_count();
+        Self::check_stacked_cards(deck_count, &stacked_cards)?;
+        let cards_left = self.game_dealer.get_cards_left();
+        if cards_left == deck_count * Card::get_standard_deck().len() && self.game_dealer.arrange_deck(&stacked_cards) {
+            return Ok(());
         }
+        self.game_dealer.queue_stacked_deck(stacked_cards);
+        Ok(())
     }
 
-    pub fn post_blinds_current_game(&mut self, amount: u32) {
-        println!("amount: {}", amount);
-        if let Some(with_blinds) = self.with_blinds {
-            if self.blinds_initialized == false && with_blinds == true {
-                let small_blind:u32 = amount;
-                let big_blind:u32 = amount*2;
-                self.big_blind = big_blind.clone();
+    // Stacked cards must all come out of a fresh shoe of deck_count decks
+    pub(crate) fn check_stacked_cards(deck_count: usize, stacked_cards: &[Card]) -> Result<(), GameError> {
+        let standard_deck = Card::get_standard_deck();
+        for card in stacked_cards.iter() {
+            if !standard_deck.contains(card) {
+                return Err(GameError::InvalidConfig { reason: format!("{:?} is not a playing card", card) });
+            }
+            let copies = stacked_cards.iter().filter(|stacked_card| *stacked_card == card).count();
+            if copies > deck_count {
+                return Err(GameError::InvalidConfig { reason: format!("{} is stacked {} times with {} decks", card.get_short_name(), copies, deck_count) });
+            }
+        }
+        let mut fresh_dealer = Dealer::new(String::from("auto"));
+        fresh_dealer.generate_cards(deck_count);
+        if !fresh_dealer.arrange_deck(stacked_cards) {
+            return Err(GameError::InvalidConfig { reason: format!("{} stacked cards do not fit in {} decks", stacked_cards.len(), deck_count) });
+        }
+        Ok(())
+    }
 
-                {
-                    let small_blind_player = &mut self.players[self.small_blind_index];
-                    let requested_fund = self.game_dealer.post_blinds(small_blind_player, small_blind);
-                    self.add_to_pot(requested_fund);
-                }
-        
-                {
-                    let big_blind_player:&mut Player = &mut self.players[self.big_blind_index];
-                    let requested_fund = self.game_dealer.post_blinds(big_blind_player, big_blind);
-                    self.add_to_pot(requested_fund);
-                    self.highest_bet = requested_fund;
-                }
-    
-                self.blinds_initialized = true;
+    // A fresh shuffled deck for the next hand, with the next stacked deck on top if one is queued
+    fn prepare_deck(&mut self) -> Result<(), GameError> {
+        let deck_count = self.game_dealer.get_deck_count();
+        self.game_dealer.collect_cards();
+        self.game_dealer.generate_cards(deck_count);
+        self.game_dealer.shuffle_cards();
+        self.arrange_stacked_deck()
+    }
+
+    fn arrange_stacked_deck(&mut self) -> Result<(), GameError> {
+        match self.game_dealer.take_stacked_deck() {
+            Some(stacked_cards) if !self.game_dealer.arrange_deck(&stacked_cards) => {
+                let short_names: Vec<String> = stacked_cards.iter().map(|card| card.get_short_name()).collect();
+                Err(GameError::InvalidConfig { reason: format!("the stacked cards [{}] could not be put on top of the deck", short_names.join(" ")) })
+            },
+            _ => Ok(()),
+        }
+    }
+
+    pub fn deal_cards(&mut self) -> Result<(), GameError> {
+        self.check_dealer_ready()?;
+        let cards_needed = self.get_seated_player_count() * 2;
+        let cards_left = self.game_dealer.get_cards_left();
+        if cards_left < cards_needed {
+            return Err(GameError::DeckExhausted);
+        }
+
+        let mut dealt_hands: Vec<(u32, usize)> = Vec::new();
+        for player in self.players.iter_mut() {
+            if player.get_sitting_out() {
+                // Players sitting out are not dealt in
+                player.set_folded(true);
             } else {
-                println!("Blinds has been set already set for this game.");
+                self.game_dealer.deal_player(&mut Some(player));
+                dealt_hands.push((player.get_player_id(), player.get_hand_length()));
+            }
+        }
+        for (player_id, card_count) in dealt_hands {
+            self.emit(GameEvent::HoleCardsDealt { player_id, card_count });
+        }
+        Ok(())
+    }
+
+    pub fn set_forced_bets(&mut self, forced_bets: ForcedBets) {
+        self.forced_bets = forced_bets;
+    }
+
+    pub fn sit_out_player(&mut self, player_index: usize) -> Result<(), GameError> {
+        let player = self.players.get_mut(player_index).ok_or(GameError::PlayerNotFound { player_index })?;
+        player.set_sitting_out(true);
+        Ok(())
+    }
+
+    pub fn return_player_to_table(&mut self, player_index: usize) -> Result<(), GameError> {
+        let player = self.players.get_mut(player_index).ok_or(GameError::PlayerNotFound { player_index })?;
+        player.set_sitting_out(false);
+        Ok(())
+    }
+
+    fn post_live_blind(&mut self, player_index: usize, amount: u32, blind_type: BlindType) -> u32 {
+        let player = &mut self.players[player_index];
+        let blind_amount = std::cmp::min(amount, player.get_coins());
+        let requested_fund = self.game_dealer.post_blinds(player, blind_amount);
+        let player_bet = player.get_current_bet();
+        let player_id = player.get_player_id();
+        self.add_to_pot(player_index, requested_fund, TransferReason::Blind(blind_type));
+        self.highest_bet = std::cmp::max(self.highest_bet, player_bet);
+        self.emit(GameEvent::BlindsPosted { player_id, blind_type, amount: requested_fund });
+        requested_fund
+    }
+
+    fn post_dead_money(&mut self, player_index: usize, amount: u32, blind_type: BlindType) -> u32 {
+        let player = &mut self.players[player_index];
+        let dead_amount = std::cmp::min(amount, player.get_coins());
+        let requested_fund = match blind_type {
+            BlindType::Ante | BlindType::BigBlindAnte => self.game_dealer.post_ante(player, dead_amount),
+            _ => self.game_dealer.post_dead_blind(player, dead_amount),
+        };
+        let player_id = player.get_player_id();
+        self.add_to_pot(player_index, requested_fund, TransferReason::Blind(blind_type));
+        self.emit(GameEvent::BlindsPosted { player_id, blind_type, amount: requested_fund });
+        requested_fund
+    }
+
+    fn get_straddle_position(&self) -> Option<usize> {
+        let seated_players = self.get_seated_player_count();
+        match self.forced_bets.straddle {
+            Some(StraddleType::UnderTheGun) if seated_players > 2 => Some(self.next_seated_index(self.big_blind_seat)),
+            Some(StraddleType::Button) if seated_players > 2 => self.get_seated_index(self.button_seat),
+            _ => None,
+        }
+    }
+
+    pub fn post_blinds_current_game(&mut self, amount: u32) -> Result<(), GameError> {
+        self.forced_bets.small_blind = amount;
+        self.forced_bets.big_blind = amount*2;
+        self.post_forced_bets()
+    }
+
+    pub fn post_forced_bets(&mut self) -> Result<(), GameError> {
+        match self.with_blinds {
+            None => return Err(GameError::BlindsNotSpecified),
+            Some(false) => return Err(GameError::BlindsDisabled),
+            Some(true) if self.blinds_initialized => return Err(GameError::BlindsAlreadyPosted),
+            Some(true) => {},
+        }
+        if self.get_seated_player_count() < 2 {
+            return Err(GameError::NotEnoughPlayers);
+        }
+        let forced_bets = self.forced_bets.clone();
+        self.big_blind = forced_bets.big_blind;
+        if self.get_seated_index(self.big_blind_seat).is_none() {
+            self.assign_blind_positions();
+        }
+
+        if forced_bets.ante > 0 {
+            for player_index in 0..self.players.len() {
+                if !self.players[player_index].get_sitting_out() {
+                    self.post_dead_money(player_index, forced_bets.ante, BlindType::Ante);
+                }
             }
-        } else {
-            println!("Blinds not enabled for this game");
         }
+
+        let small_blind_index = self.get_seated_index(self.small_blind_seat);
+        let big_blind_index = self.players.iter()
+            .position(|player| player.get_seat() == self.big_blind_seat)
+            .unwrap_or_default();
+        // No small blind index means a dead small blind this hand
+        if let Some(small_blind_index) = small_blind_index {
+            self.post_live_blind(small_blind_index, forced_bets.small_blind, BlindType::SmallBlind);
+        }
+        self.post_live_blind(big_blind_index, forced_bets.big_blind, BlindType::BigBlind);
+
+        // The big blind is posted first so a short stack pays the ante from what is left
+        if forced_bets.big_blind_ante > 0 {
+            self.post_dead_money(big_blind_index, forced_bets.big_blind_ante, BlindType::BigBlindAnte);
+        }
+
+        let straddle_index = self.get_straddle_position();
+        self.straddle_seat = straddle_index.map(|index| self.players[index].get_seat());
+        if let Some(straddle_index) = straddle_index {
+            self.post_live_blind(straddle_index, forced_bets.get_straddle_amount(), BlindType::Straddle);
+        }
+
+        // Returning players make up missed blinds: the big blind plays, the small blind is dead
+        for player_index in 0..self.players.len() {
+            let player = &mut self.players[player_index];
+            let (missed_small_blind, missed_big_blind) = player.get_missed_blinds();
+            if player.get_sitting_out() || !(missed_small_blind || missed_big_blind) {
+                continue;
+            }
+            player.set_missed_small_blind(false);
+            player.set_missed_big_blind(false);
+
+            let player_seat = player.get_seat();
+            let posts_in_position = player_seat == self.small_blind_seat
+                || player_seat == self.big_blind_seat
+                || Some(player_seat) == self.straddle_seat;
+            if posts_in_position {
+                continue;
+            }
+            if missed_big_blind {
+                self.post_live_blind(player_index, forced_bets.big_blind, BlindType::MissedBigBlind);
+            }
+            if missed_small_blind {
+                self.post_dead_money(player_index, forced_bets.small_blind, BlindType::DeadSmallBlind);
+            }
+        }
+
+        self.last_raise_size = forced_bets.big_blind;
+        // The big blind opens the betting and a straddle is the first raise
+        self.street_bet_count = if self.straddle_seat.is_some() { 2 } else { 1 };
+    
+        self.blinds_initialized = true;
+        self.debug_audit_chips()
     }
 
     
 
     
-    pub fn play(&mut self) {
-        let game_id:&str = &self.game_id;
-        match game_id {
-            "" => {
-                // game_id is empty
-                println!("You need to create a game first or load an existing game");
-                return;
-            }
-            _ => {
-                // game_id has a value
-                let dealer_ready:bool = self.is_dealer_ready();
-                println!("Dealer is ready: {}", dealer_ready);
-                if dealer_ready {
-                    match self.game_state.as_str() {
-                        "initial" => {
-                            println!("Game at initial state.");
-                            match self.are_players_ready() {
-                                false => {
-                                    println!("Please deal players with cards.")
-                                },
-                                _ => {
-                                    self.set_game_state("Pre-flop");
-                                    self.play();
-                                }
-                            }
-                        },
-                        "Pre-flop" => {
-                            println!("Pre-flop");
-                            let round_ended = self.round_decisions(None);
-                            println!("pre-flop round ended: {}", round_ended);
-                            if round_ended {
-                                self.set_game_state("Flop");
-                            }
-                            self.play();
-                        },
-                        "Flop" => {
-                            println!("Flop");
-                            self.request_dealer_open_cards(3);
-                            let round_ended = self.round_decisions(None);
-                            if round_ended {
-                                self.set_game_state("Turn");
-                            }
-                            self.play();
-                        },
-                        "Turn" => {
-                            println!("Turn");
-                            self.request_dealer_open_cards(1);
-                            let round_ended = self.round_decisions(None);
-                            if round_ended {
-                                self.set_game_state("River");
-                            }
-                            self.play();
-                        },
-                        "River" => {
-                            println!("River");
-                            self.request_dealer_open_cards(1);
-                            let round_ended = self.round_decisions(None);
-                            if round_ended {
-                                self.set_game_state("ShowHands");
-                            }
-                            self.play();
-                        },
-                        "ShowHands" => {
-                            println!("ShowHands");
-                            self.determine_winner();
-                            //self.round_decisions(None);
-                            //self.set_game_state("River");
-                            //self.play();
-                        },
-                        _ => {
-                            println!("Game state invalid!");
+    pub fn step(&mut self) -> Result<bool, GameError> {
+        if self.game_id.is_empty() {
+            return Err(GameError::NoGame);
+        }
+        self.check_dealer_ready()?;
+
+        match self.hand_phase {
+            HandPhase::Initial => {
+                if !self.are_players_ready() {
+                    return Err(GameError::CardsNotDealt);
+                }
+                self.set_hand_phase(HandPhase::PreFlop);
+                self.start_betting_round();
+            },
+            HandPhase::PreFlop | HandPhase::Flop | HandPhase::Turn | HandPhase::River => {
+                match self.action_index {
+                    Some(player_index) => {
+                        if !self.check_action_timeout()? {
+                            self.request_player_action(player_index)?;
                         }
-                    }
+                    },
+                    None => self.start_next_street(),
                 }
-            }
+            },
+            HandPhase::Showdown => {
+                self.determine_winner();
+                self.debug_audit_chips()?;
+            },
+            HandPhase::HandOver => return Ok(false),
         }
+        Ok(true)
     }
-}
\ No newline at end of file
+
+    pub fn play(&mut self) -> Result<(), GameError> {
+        while self.step()? {}
+        Ok(())
+    }
+}
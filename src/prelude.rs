@@ -0,0 +1,7 @@
+// Everything needed to set up a table, play hands and follow along
+pub use crate::{Player, Dealer, Card, PokerGame, GameError};
+pub use crate::features::PokerRules;
+pub use crate::constants::{PlayerAction, HandPhase, BlindType, StraddleType, GameErrorKind, ChipAccount, TransferReason, BettingStructure, GameVariant, TablePosition, CfrVariant};
+pub use crate::features::{ForcedBets, RakeSettings, HouseLedger, GameEvent, SeatSummary, GameObserver, ConsoleObserver, HandRank, HandValue, ChipLedger, ChipTransfer, PlayerView, SeatView, PotView, GameSnapshot, TimeLimits, TableConfig, SeatConfig, PlayerStats, StatsTracker, Clock, SystemClock, ManualClock};
+pub use crate::features::{PlayerAgent, RandomAgent, ScriptedAgent, ScriptedStep, SubprocessAgent, BotRequest, BotReply, AcpcAgent, AcpcMatchState, AcpcDealer, HumanAgent, RuleBasedAgent, EquityAgent, EquitySettings, Personality, PersonalityState, TiltSettings, AgentRegistry, AgentFactory, ActionResult};
+pub use crate::features::{CfrAgent, ExtensiveGame, CfrTrainer, InfoSetNode, TrainingReport, CfrStrategy, KuhnPoker, KuhnState, LeducPoker, LeducState, LimitHoldem, LimitHoldemState};
@@ -0,0 +1,95 @@
+mod common;
+
+use common::EventLog;
+use poker::prelude::*;
+use std::net::TcpListener;
+use std::process::{Child, Command};
+
+
+
+const CLIENT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/acpc_client.py");
+
+// Starts the example client and takes its connection on a free port
+fn connect_client() -> (AcpcAgent, Child) {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let client = Command::new("python3")
+        .args([CLIENT_PATH, "127.0.0.1", &port.to_string()])
+        .spawn()
+        .unwrap();
+    let (connection, client_address) = listener.accept().unwrap();
+    let acpc_agent = AcpcAgent::from_connection(connection, client_address.to_string(), true).unwrap();
+    (acpc_agent, client)
+}
+
+fn stop_client(mut client: Child) {
+    let _ = client.kill();
+    let _ = client.wait();
+}
+
+// Heads-up limit hold'em like configs/acpc_table.json, with the client in the first seat
+fn build_match_table(acpc_agent: AcpcAgent) -> (PokerGame, EventLog) {
+    let mut game = TableConfig::new()
+        .with_player(1, "AcpcBot", 400)
+        .with_player(2, "Bob", 400)
+        .with_blinds(5, 10)
+        .with_betting_structure(BettingStructure::FixedLimit)
+        .with_time_limits(TimeLimits { action_seconds: Some(5), time_bank_seconds: 0 })
+        .with_rng_seed(11)
+        .build()
+        .unwrap();
+    game.set_agent(1, Box::new(acpc_agent));
+    game.set_agent(2, Box::new(RuleBasedAgent::new()));
+    let event_log = EventLog::new();
+    game.subscribe(Box::new(event_log.clone()));
+    (game, event_log)
+}
+
+fn get_problems(events: &[GameEvent]) -> Vec<String> {
+    events.iter()
+        .filter_map(|event| match event {
+            GameEvent::AgentProblem { problem, .. } => Some(problem.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn a_short_match_against_the_example_client() {
+    let (acpc_agent, client) = connect_client();
+    let (mut game, event_log) = build_match_table(acpc_agent);
+    for _ in 0..5 {
+        game.reset_stacks(400).unwrap();
+        game.start_next_hand().unwrap();
+        game.play().unwrap();
+    }
+    drop(game);
+    stop_client(client);
+
+    let events = event_log.get_events();
+    assert_eq!(get_problems(&events), Vec::<String>::new());
+    assert!(!events.iter().any(|event| matches!(event, GameEvent::PlayerTimedOut { .. } | GameEvent::ActionRejected { .. })));
+    let client_actions: Vec<PlayerAction> = events.iter()
+        .filter_map(|event| match event {
+            GameEvent::PlayerActed { player_id: 1, action, .. } => Some(*action),
+            _ => None,
+        })
+        .collect();
+    // The client never folds
+    assert!(!client_actions.is_empty());
+    assert!(!client_actions.contains(&PlayerAction::Fold));
+    let hands_ended = events.iter().filter(|event| matches!(event, GameEvent::HandEnded { .. })).count();
+    assert_eq!(hands_ended, 5);
+}
+
+#[test]
+fn a_client_that_hangs_up_folds_and_is_reported() {
+    let (acpc_agent, client) = connect_client();
+    stop_client(client);
+    let (mut game, event_log) = build_match_table(acpc_agent);
+    game.play_session(2).unwrap();
+
+    let problems = get_problems(&event_log.get_events());
+    assert_eq!(problems.len(), 1, "{:?}", problems);
+    assert!(problems[0].contains("folding its hands from now on"));
+}
@@ -0,0 +1,93 @@
+use poker::prelude::*;
+
+
+
+// Alice on the button, Bob in the small blind and Charlie in the big blind, dealt up to the
+// first preflop decision. Everyone is seated in order, so a player's index is their seat.
+fn build_three_handed_game() -> PokerGame {
+    let mut game = TableConfig::new()
+        .with_player(1, "Alice", 100)
+        .with_player(2, "Bob", 100)
+        .with_player(3, "Charlie", 100)
+        .with_blinds(1, 2)
+        .with_rng_seed(2)
+        .build()
+        .unwrap();
+    game.start_next_hand().unwrap();
+    game.step().unwrap();
+    game
+}
+
+// Plays one action for whoever is to act and checks it was the expected player
+fn act(game: &mut PokerGame, seat: usize, action: PlayerAction, amount: Option<u32>) {
+    assert_eq!(game.get_action_index(), Some(seat));
+    game.apply_action(seat, action, amount).unwrap();
+}
+
+fn get_view_to_act(game: &PokerGame) -> PlayerView {
+    game.get_player_view(game.get_action_index().unwrap()).unwrap()
+}
+
+#[test]
+fn the_big_blind_gets_an_option_in_a_limped_pot() {
+    let mut game = build_three_handed_game();
+    act(&mut game, 0, PlayerAction::Call, None);
+    act(&mut game, 1, PlayerAction::Call, None);
+
+    // Everyone has matched the big blind, but Charlie has not acted yet
+    let player_view = get_view_to_act(&game);
+    assert_eq!(player_view.player_id, 3);
+    assert_eq!(player_view.amount_to_call, 0);
+    assert!(player_view.is_legal(PlayerAction::Check));
+    assert!(player_view.is_legal(PlayerAction::Raise));
+
+    act(&mut game, 2, PlayerAction::Check, None);
+    assert_eq!(game.get_action_index(), None);
+    game.step().unwrap();
+    assert_eq!(game.get_hand_phase(), HandPhase::Flop);
+}
+
+#[test]
+fn a_street_ends_once_a_bet_is_called_around() {
+    let mut game = build_three_handed_game();
+    act(&mut game, 0, PlayerAction::Call, None);
+    act(&mut game, 1, PlayerAction::Call, None);
+    act(&mut game, 2, PlayerAction::Check, None);
+    game.step().unwrap();
+
+    // After the flop the small blind acts first
+    act(&mut game, 1, PlayerAction::Bet, Some(4));
+    act(&mut game, 2, PlayerAction::Call, None);
+    assert_eq!(game.get_action_index(), Some(0));
+    act(&mut game, 0, PlayerAction::Call, None);
+
+    // The bettor is not asked to act on the bet again
+    assert_eq!(game.get_action_index(), None);
+    assert_eq!(game.get_hand_phase(), HandPhase::Flop);
+    game.step().unwrap();
+    assert_eq!(game.get_hand_phase(), HandPhase::Turn);
+    assert_eq!(get_view_to_act(&game).player_id, 2);
+}
+
+#[test]
+fn the_highest_bet_and_raise_size_start_over_each_street() {
+    let mut game = build_three_handed_game();
+    act(&mut game, 0, PlayerAction::Raise, Some(10));
+    assert_eq!(get_view_to_act(&game).minimum_raise_to, 18);
+    act(&mut game, 1, PlayerAction::Call, None);
+    act(&mut game, 2, PlayerAction::Call, None);
+    game.step().unwrap();
+
+    // The preflop raise of 8 does not carry over: the flop opens with nothing bet and a
+    // minimum bet of the big blind
+    let player_view = get_view_to_act(&game);
+    assert_eq!(player_view.highest_bet, 0);
+    assert_eq!(player_view.amount_to_call, 0);
+    assert_eq!(player_view.minimum_bet, 2);
+    assert!(player_view.seats.iter().all(|seat_view| seat_view.current_bet == 0));
+
+    act(&mut game, 1, PlayerAction::Bet, Some(3));
+    let player_view = get_view_to_act(&game);
+    assert_eq!(player_view.highest_bet, 3);
+    assert_eq!(player_view.minimum_raise_to, 6);
+}
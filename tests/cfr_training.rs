@@ -0,0 +1,142 @@
+use poker::prelude::*;
+use std::sync::{Arc, Mutex};
+
+
+
+fn train_leduc(variant: CfrVariant, iterations: u64) -> (f64, f64) {
+    let mut trainer = CfrTrainer::new(LeducPoker::new(), variant).unwrap().with_rng_seed(7);
+    let training_reports = trainer.train_with_reports(iterations, iterations / 10, |_| {});
+    let first_exploitability = training_reports.first().and_then(|training_report| training_report.exploitability).unwrap();
+    let last_exploitability = training_reports.last().and_then(|training_report| training_report.exploitability).unwrap();
+    (first_exploitability, last_exploitability)
+}
+
+fn get_card_indexes(cards: &[Card]) -> Vec<usize> {
+    let deck = Card::get_standard_deck();
+    cards.iter().map(|card| deck.iter().position(|deck_card| deck_card == card).unwrap()).collect()
+}
+
+#[test]
+fn kuhn_poker_converges_to_the_known_game_value() {
+    let mut trainer = CfrTrainer::new(KuhnPoker::new(), CfrVariant::Vanilla).unwrap();
+    trainer.train(3000);
+
+    // The first player loses 1/18 of a chip a hand at equilibrium
+    let expected_value = trainer.get_expected_value().unwrap();
+    assert!((expected_value + 1.0 / 18.0).abs() < 0.005, "expected value {}", expected_value);
+    assert!(trainer.get_exploitability().unwrap() < 0.01);
+}
+
+#[test]
+fn leduc_exploitability_drops_under_vanilla_cfr() {
+    let (first_exploitability, last_exploitability) = train_leduc(CfrVariant::Vanilla, 250);
+    assert!(last_exploitability < first_exploitability);
+    assert!(last_exploitability < 0.1, "exploitability {}", last_exploitability);
+}
+
+#[test]
+fn leduc_exploitability_drops_under_cfr_plus() {
+    let (first_exploitability, last_exploitability) = train_leduc(CfrVariant::CfrPlus, 100);
+    assert!(last_exploitability < first_exploitability);
+    assert!(last_exploitability < 0.02, "exploitability {}", last_exploitability);
+}
+
+#[test]
+fn leduc_exploitability_drops_under_chance_sampling() {
+    let (first_exploitability, last_exploitability) = train_leduc(CfrVariant::ChanceSampling, 2000);
+    assert!(last_exploitability < first_exploitability);
+    assert!(last_exploitability < 0.4, "exploitability {}", last_exploitability);
+}
+
+#[test]
+fn leduc_exploitability_drops_under_external_sampling() {
+    let (first_exploitability, last_exploitability) = train_leduc(CfrVariant::ExternalSampling, 2000);
+    assert!(last_exploitability < first_exploitability);
+    assert!(last_exploitability < 0.6, "exploitability {}", last_exploitability);
+}
+
+#[test]
+fn full_tree_variants_refuse_limit_holdem() {
+    for variant in [CfrVariant::Vanilla, CfrVariant::CfrPlus] {
+        let error = CfrTrainer::new(LimitHoldem::new(), variant).err().unwrap();
+        assert!(matches!(error, GameError::TrainingUnsupported { .. }));
+    }
+    for variant in [CfrVariant::ChanceSampling, CfrVariant::ExternalSampling] {
+        let mut trainer = CfrTrainer::new(LimitHoldem::new(), variant).unwrap().with_rng_seed(7);
+        trainer.train(10);
+        assert!(!trainer.get_info_sets().is_empty());
+        assert_eq!(trainer.get_exploitability(), None);
+    }
+}
+
+// Hole cards, board, betting rounds and strategy key from each view
+type ViewsSeen = Arc<Mutex<Vec<(Vec<Card>, Vec<Card>, Vec<String>, String)>>>;
+
+// Notes the betting and the strategy key from each view before playing its scripted step
+struct KeyRecorder {
+    scripted_agent: ScriptedAgent,
+    views_seen: ViewsSeen
+}
+
+impl PlayerAgent for KeyRecorder {
+    fn get_agent_name(&self) -> &str {
+        "key_recorder"
+    }
+
+    fn decide(&mut self, player_view: &PlayerView) -> ActionResult {
+        self.views_seen.lock().unwrap().push((
+            player_view.hole_cards.clone(),
+            player_view.board.clone(),
+            CfrAgent::get_betting_rounds(player_view),
+            CfrAgent::get_info_set_key(player_view),
+        ));
+        self.scripted_agent.decide(player_view)
+    }
+}
+
+#[test]
+fn table_betting_maps_onto_the_trained_game_keys() {
+    let views_seen = Arc::new(Mutex::new(Vec::new()));
+    let mut game = TableConfig::new()
+        .with_player(1, "Button", 100)
+        .with_player(2, "BigBlind", 100)
+        .with_blinds(1, 2)
+        .with_betting_structure(BettingStructure::FixedLimit)
+        .with_rng_seed(5)
+        .build()
+        .unwrap();
+    let scripts: [(u32, &[&str]); 2] = [
+        (1, &["raise to 4", "raise to 4", "fold"]),
+        (2, &["call", "bet 2", "call", "bet 4"]),
+    ];
+    for (player_id, script) in scripts {
+        let scripted_agent = ScriptedAgent::from_script(script).unwrap();
+        game.set_agent(player_id, Box::new(KeyRecorder { scripted_agent, views_seen: views_seen.clone() }));
+    }
+    game.start_next_hand().unwrap();
+    game.play().unwrap();
+
+    let expected_rounds: [&[&str]; 7] = [
+        &[""],
+        &["r"],
+        &["rc", ""],
+        &["rc", "r"],
+        &["rc", "rr"],
+        &["rc", "rrc", ""],
+        &["rc", "rrc", "r"],
+    ];
+    let views_seen = views_seen.lock().unwrap().clone();
+    assert_eq!(views_seen.len(), expected_rounds.len());
+    let limit_holdem = LimitHoldem::new();
+    for ((hole_cards, board, betting_rounds, info_set_key), expected_rounds) in views_seen.iter().zip(expected_rounds) {
+        assert_eq!(betting_rounds, expected_rounds);
+        // Only the player to act's own cards go into the key, so both seats get them here
+        let hole_cards = get_card_indexes(hole_cards);
+        let state = LimitHoldemState {
+            hole_cards: vec![hole_cards.clone(), hole_cards],
+            board: get_card_indexes(board),
+            rounds: betting_rounds.clone(),
+        };
+        assert_eq!(&limit_holdem.get_info_set_key(&state), info_set_key);
+    }
+}
@@ -0,0 +1,29 @@
+#![allow(dead_code)]
+
+use poker::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+
+
+// Keeps every event a table publishes so a test can look through them afterwards
+#[derive(Clone, Default)]
+pub struct EventLog {
+    events: Rc<RefCell<Vec<GameEvent>>>
+}
+
+impl EventLog {
+    pub fn new() -> Self {
+        EventLog::default()
+    }
+
+    pub fn get_events(&self) -> Vec<GameEvent> {
+        self.events.borrow().clone()
+    }
+}
+
+impl GameObserver for EventLog {
+    fn on_event(&mut self, event: &GameEvent) {
+        self.events.borrow_mut().push(event.clone());
+    }
+}
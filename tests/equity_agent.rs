@@ -0,0 +1,35 @@
+use poker::prelude::*;
+
+
+
+fn to_cards(short_names: &[&str]) -> Vec<Card> {
+    short_names.iter().map(|short_name| Card::from_short_name(short_name).unwrap()).collect()
+}
+
+fn build_equity_agent(simulations: u32) -> EquityAgent {
+    // Opponents may hold any two cards
+    EquityAgent::new(EquitySettings { simulations, rng_seed: Some(7), opponent_range_percent: 100.0, ..Default::default() })
+}
+
+#[test]
+fn aces_win_about_85_percent_against_a_random_hand() {
+    let mut equity_agent = build_equity_agent(5000);
+    let equity = equity_agent.estimate_equity(&to_cards(&["Ah", "Ad"]), &[], 1, 1);
+    assert!((equity - 0.85).abs() < 0.02, "aces had {} equity", equity);
+}
+
+#[test]
+fn the_nuts_on_the_river_always_win() {
+    let mut equity_agent = build_equity_agent(1000);
+    let equity = equity_agent.estimate_equity(&to_cards(&["As", "Ks"]), &to_cards(&["Qs", "Js", "Ts", "2c", "3d"]), 3, 1);
+    assert_eq!(equity, 1.0);
+}
+
+#[test]
+fn a_second_deck_lets_an_opponent_hold_the_same_cards() {
+    // With two decks the other ace and king of spades are still out there, so the royal
+    // flush is now and then split
+    let mut equity_agent = build_equity_agent(20000);
+    let equity = equity_agent.estimate_equity(&to_cards(&["As", "Ks"]), &to_cards(&["Qs", "Js", "Ts", "2c", "3d"]), 1, 2);
+    assert!(equity < 1.0 && equity > 0.99, "the royal flush had {} equity", equity);
+}
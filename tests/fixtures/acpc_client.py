@@ -0,0 +1,55 @@
+#!/usr/bin/env python3
+# Minimal heads-up ACPC client for trying the dealer mode: python3 acpc_client.py <host> <port>
+# It sends VERSION:2.0.0, then answers each MATCHSTATE where it is to act. It raises pairs and
+# some high cards, calls everything else and never folds. Real ACPC bots connect the same way.
+import random
+import socket
+import sys
+
+
+def split_moves(round_betting):
+    moves = []
+    index = 0
+    while index < len(round_betting):
+        end = index + 1
+        if round_betting[index] == "r":
+            while end < len(round_betting) and round_betting[end].isdigit():
+                end += 1
+        moves.append(round_betting[index:end])
+        index = end
+    return moves
+
+
+def get_player_to_act(betting):
+    # Reversed blinds: the button (position 1) acts first before the flop, the big blind
+    # (position 0) first after it. A round closes on a call once both players have acted.
+    if "f" in betting:
+        return None
+    rounds = betting.split("/")
+    moves = split_moves(rounds[-1])
+    if len(moves) >= 2 and moves[-1] == "c":
+        return None
+    first_player = 1 if len(rounds) == 1 else 0
+    return (first_player + len(moves)) % 2
+
+
+def main():
+    host, port = sys.argv[1], int(sys.argv[2])
+    connection = socket.create_connection((host, port))
+    connection.sendall(b"VERSION:2.0.0\r\n")
+    for line in connection.makefile("r"):
+        line = line.strip()
+        if not line.startswith("MATCHSTATE:"):
+            continue
+        _, position, _, betting, cards = line.split(":", 4)
+        if get_player_to_act(betting) != int(position):
+            continue
+        hole_cards = cards.split("/")[0].split("|")[int(position)]
+        paired = len(hole_cards) == 4 and hole_cards[0] == hole_cards[2]
+        high_card = hole_cards[:1] in ("A", "K") and random.random() < 0.3
+        action = "r" if paired or high_card else "c"
+        connection.sendall(("%s:%s\r\n" % (line, action)).encode())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,62 @@
+#!/usr/bin/env python3
+# Example bot for the "subprocess" agent. Each line on stdin is a JSON request holding the
+# player's view, and each reply is one JSON line like {"action": "raise", "amount": 60}.
+#
+# It raises pairs and high cards before the flop, bets when it has paired the board, and
+# otherwise checks or calls small bets. Pass --misbehave slow|crash|garbage|illegal to try
+# how the engine copes with a bad bot.
+import json
+import sys
+import time
+
+HIGH_RANKS = {"10", "Jack", "Queen", "King", "Ace"}
+
+
+def has_strong_cards(view):
+    ranks = [card["rank"] for card in view["hole_cards"]]
+    if len(ranks) == 2 and ranks[0] == ranks[1]:
+        return True
+    board_ranks = {card["rank"] for card in view["board"]}
+    if board_ranks:
+        return any(rank in board_ranks for rank in ranks)
+    return all(rank in HIGH_RANKS for rank in ranks)
+
+
+def choose(view):
+    legal = view["legal_actions"]
+    if has_strong_cards(view):
+        if "Raise" in legal:
+            return {"action": "raise", "amount": view["minimum_raise_to"]}
+        if "Bet" in legal:
+            return {"action": "bet", "amount": view["minimum_bet"]}
+    if "Check" in legal:
+        return {"action": "check"}
+    if "Call" in legal and view["amount_to_call"] <= 2 * view["big_blind"]:
+        return {"action": "call"}
+    return {"action": "fold"}
+
+
+def main():
+    misbehave = sys.argv[sys.argv.index("--misbehave") + 1] if "--misbehave" in sys.argv else None
+    for line in sys.stdin:
+        if not line.strip():
+            continue
+        request = json.loads(line)
+        view = request["view"]
+        if misbehave == "slow":
+            time.sleep(10)
+        elif misbehave == "crash":
+            sys.exit(3)
+        elif misbehave == "garbage":
+            print("I think I will call", flush=True)
+            continue
+        elif misbehave == "illegal":
+            print(json.dumps({"request_id": request["request_id"], "action": "raise", "amount": 1}), flush=True)
+            continue
+        reply = choose(view)
+        reply["request_id"] = request["request_id"]
+        print(json.dumps(reply), flush=True)
+
+
+if __name__ == "__main__":
+    main()
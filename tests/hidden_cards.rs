@@ -0,0 +1,56 @@
+use poker::prelude::*;
+
+
+
+fn build_dealt_game() -> PokerGame {
+    let mut game = TableConfig::new()
+        .with_player(1, "Alice", 100)
+        .with_player(2, "Bob", 100)
+        .with_rng_seed(5)
+        .build()
+        .unwrap();
+    game.start_next_hand().unwrap();
+    game
+}
+
+#[test]
+fn a_serialized_dealer_does_not_reveal_the_deck() {
+    let mut dealer = Dealer::new(String::from("auto"));
+    dealer.generate_cards(1);
+    dealer.shuffle_cards();
+
+    let json = serde_json::to_string(&dealer).unwrap();
+    assert!(!json.contains("Spades"));
+    assert!(!format!("{:?}", dealer).contains("Spades"));
+}
+
+#[test]
+fn a_serialized_player_does_not_reveal_hole_cards() {
+    let player = Player::new(1, String::from("Alice"), 100);
+    let json = serde_json::to_value(&player).unwrap();
+    assert!(json.get("hand_cards").is_none());
+}
+
+#[test]
+fn snapshots_keep_hole_cards_and_the_deck_order() {
+    let game = build_dealt_game();
+    let bytes = game.snapshot().to_bytes().unwrap();
+    let mut restored = PokerGame::from_snapshot(GameSnapshot::from_bytes(&bytes).unwrap());
+
+    for seat in 0..2 {
+        let original_view = game.get_player_view(seat).unwrap();
+        let restored_view = restored.get_player_view(seat).unwrap();
+        assert_eq!(original_view.hole_cards.len(), 2);
+        assert_eq!(original_view.hole_cards, restored_view.hole_cards);
+    }
+
+    // Both tables deal the same board from the restored deck
+    let mut original = PokerGame::from_snapshot(game.snapshot());
+    for table in [&mut original, &mut restored] {
+        table.set_agent(1, Box::new(ScriptedAgent::from_script(&["call", "check", "check", "check", "check", "check", "check"]).unwrap()));
+        table.set_agent(2, Box::new(ScriptedAgent::from_script(&["check", "check", "check", "check"]).unwrap()));
+        table.play().unwrap();
+    }
+    assert_eq!(original.get_player_view(0).unwrap().board, restored.get_player_view(0).unwrap().board);
+    assert_eq!(restored.get_player_view(0).unwrap().board.len(), 5);
+}
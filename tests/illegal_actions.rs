@@ -0,0 +1,48 @@
+mod common;
+
+use common::EventLog;
+use poker::prelude::*;
+
+
+
+// Always answers with a raise far below the minimum
+struct StubbornAgent;
+
+impl PlayerAgent for StubbornAgent {
+    fn get_agent_name(&self) -> &str {
+        "stubborn"
+    }
+
+    fn decide(&mut self, _player_view: &PlayerView) -> ActionResult {
+        ActionResult { action: PlayerAction::Raise, success: true, amount: Some(1) }
+    }
+}
+
+#[test]
+fn illegal_actions_are_reported_and_replaced_after_a_few_attempts() {
+    let mut game = TableConfig::new()
+        .with_player(1, "Stubborn", 100)
+        .with_player(2, "Other", 100)
+        .with_rng_seed(3)
+        .build()
+        .unwrap();
+    game.set_agent(1, Box::new(StubbornAgent));
+    game.set_agent(2, Box::new(StubbornAgent));
+    let event_log = EventLog::new();
+    game.subscribe(Box::new(event_log.clone()));
+
+    game.play_session(1).unwrap();
+
+    let events = event_log.get_events();
+    let rejections: Vec<&GameEvent> = events.iter().filter(|event| matches!(event, GameEvent::ActionRejected { .. })).collect();
+    assert!(!rejections.is_empty());
+    assert!(rejections.iter().all(|event| matches!(event, GameEvent::ActionRejected { action: PlayerAction::Raise, amount: Some(1), .. })));
+    // The third illegal choice in a row is replaced with a fold, since the first player faces the big blind
+    match rejections[2] {
+        GameEvent::ActionRejected { replaced_with, .. } => assert_eq!(*replaced_with, Some(PlayerAction::Fold)),
+        _ => unreachable!(),
+    }
+    assert!(rejections[..2].iter().all(|event| matches!(event, GameEvent::ActionRejected { replaced_with: None, .. })));
+    assert!(events.iter().any(|event| matches!(event, GameEvent::PlayerActed { action: PlayerAction::Fold, .. })));
+    assert_eq!(game.get_hand_phase(), HandPhase::HandOver);
+}
@@ -0,0 +1,98 @@
+use poker::prelude::*;
+
+
+
+fn set_scripts(game: &mut PokerGame, scripts: &[(u32, &[&str])]) {
+    for (player_id, script) in scripts {
+        game.set_agent(*player_id, Box::new(ScriptedAgent::from_script(script).unwrap()));
+    }
+}
+
+// Alice, on the button, loses a pot of 100 with seven-deuce to Bob's aces
+fn build_losing_game() -> PokerGame {
+    let mut game = TableConfig::new()
+        .with_player(1, "Alice", 100)
+        .with_player(2, "Bob", 100)
+        .with_blinds(1, 2)
+        .with_rng_seed(5)
+        .with_stacked_deck(&["7c", "2d", "Ah", "Ad", "Kh", "Qs", "Js", "5c", "9d"])
+        .build()
+        .unwrap();
+    set_scripts(&mut game, &[
+        (1, &["raise to 50", "check", "check", "check"]),
+        (2, &["call", "check", "check", "check"]),
+    ]);
+    game
+}
+
+// Plays a hand where whoever acts first folds, and gives Alice's final view of it
+fn play_folded_hand(game: &mut PokerGame) -> PlayerView {
+    set_scripts(game, &[(1, &["fold"]), (2, &["fold"])]);
+    game.start_next_hand().unwrap();
+    game.play().unwrap();
+    game.get_player_view(0).unwrap()
+}
+
+#[test]
+fn every_preset_loads_and_validates() {
+    for preset_name in Personality::PRESET_NAMES {
+        let personality = Personality::from_preset(preset_name).unwrap();
+        assert_eq!(personality.name, preset_name);
+        personality.validate().unwrap();
+        let from_params = Personality::from_params(&serde_json::json!({ "personality": preset_name })).unwrap();
+        assert_eq!(from_params, Some(personality));
+    }
+    assert!(Personality::from_preset("shark").is_none());
+    assert!(matches!(Personality::from_params(&serde_json::json!({ "personality": "shark" })), Err(GameError::InvalidConfig { .. })));
+}
+
+#[test]
+fn raising_more_hands_than_are_played_is_rejected() {
+    let personality = Personality { vpip: 10.0, pfr: 20.0, ..Personality::default() };
+    assert!(matches!(personality.validate(), Err(GameError::InvalidConfig { .. })));
+    let json = serde_json::json!({ "name": "backwards", "vpip": 10.0, "pfr": 20.0 }).to_string();
+    assert!(matches!(Personality::from_json_str(&json), Err(GameError::InvalidConfig { .. })));
+}
+
+#[test]
+fn a_big_loss_tilts_for_the_set_number_of_hands() {
+    let personality = Personality {
+        tilt: TiltSettings { trigger_big_blinds: 10, tilt_hands: 2, vpip_increase: 20.0, pfr_increase: 10.0, aggression_multiplier: 2.0 },
+        ..Personality::default()
+    };
+    let mut random_agent = RandomAgent::new().with_personality(personality);
+    let mut game = build_losing_game();
+
+    // Losing 50 chips at 2 a big blind is well past the trigger, and tilt starts as the hand
+    // ends even though Alice had no decision left to make
+    game.start_next_hand().unwrap();
+    game.play().unwrap();
+    let final_view = game.get_player_view(0).unwrap();
+    assert_eq!(final_view.get_own_seat().unwrap().coins, 50);
+    random_agent.on_hand_end(&final_view);
+    let tilted = random_agent.get_current_personality().unwrap();
+    assert_eq!(tilted.name, "balanced (tilted)");
+    assert_eq!(tilted.vpip, 45.0);
+
+    // Seeing the same hand end again changes nothing
+    random_agent.on_hand_end(&final_view);
+    random_agent.on_hand_end(&play_folded_hand(&mut game));
+    assert_eq!(random_agent.get_current_personality().unwrap().name, "balanced (tilted)");
+    random_agent.on_hand_end(&play_folded_hand(&mut game));
+    assert_eq!(random_agent.get_current_personality().unwrap().name, "balanced");
+}
+
+#[test]
+fn small_losses_do_not_tilt() {
+    let personality = Personality {
+        tilt: TiltSettings { trigger_big_blinds: 10, tilt_hands: 2, ..TiltSettings::default() },
+        ..Personality::default()
+    };
+    let mut personality_state = PersonalityState::new(personality);
+    let mut game = build_losing_game();
+    for _ in 0..3 {
+        // Folding a blind costs at most one big blind
+        personality_state.on_hand_end(&play_folded_hand(&mut game));
+        assert!(!personality_state.is_tilted());
+    }
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use common::EventLog;
+use poker::prelude::*;
+
+
+
+// Alice holds aces on the button against Bob's seven-deuce, with a board that helps neither
+const ALICE_WINS_HEADS_UP: [&str; 9] = ["Ah", "Ad", "7c", "2d", "Kh", "Qs", "Js", "5c", "9d"];
+
+fn set_scripts(game: &mut PokerGame, scripts: &[(u32, &[&str])]) {
+    for (player_id, script) in scripts {
+        game.set_agent(*player_id, Box::new(ScriptedAgent::from_script(script).unwrap()));
+    }
+}
+
+// A pot, the rake taken from it and what each winner got
+type PotRaked = (u32, u32, Vec<(u32, u32)>);
+
+fn get_pots_raked(events: &[GameEvent]) -> Vec<PotRaked> {
+    events.iter()
+        .filter_map(|event| match event {
+            GameEvent::PotAwarded { amount, rake, winners } => Some((*amount, *rake, winners.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+// Bob raises to 50 and Alice calls, then both check it down for a pot of 100
+fn play_heads_up_pot(rake_settings: RakeSettings) -> (PokerGame, Vec<GameEvent>) {
+    let mut game = TableConfig::new()
+        .with_player(1, "Alice", 100)
+        .with_player(2, "Bob", 100)
+        .with_blinds(5, 10)
+        .with_rake(rake_settings)
+        .with_rng_seed(5)
+        .with_stacked_deck(&ALICE_WINS_HEADS_UP)
+        .build()
+        .unwrap();
+    set_scripts(&mut game, &[
+        (1, &["call", "call", "check", "check", "check"]),
+        (2, &["raise to 50", "check", "check", "check"]),
+    ]);
+    let event_log = EventLog::new();
+    game.subscribe(Box::new(event_log.clone()));
+    game.start_next_hand().unwrap();
+    game.play().unwrap();
+    (game, event_log.get_events())
+}
+
+#[test]
+fn the_rake_is_a_percentage_of_the_pot_rounded_down() {
+    let (game, events) = play_heads_up_pot(RakeSettings { percentage: 4.5, ..Default::default() });
+
+    assert_eq!(get_pots_raked(&events), vec![(100, 4, vec![(1, 96)])]);
+    assert_eq!(game.get_house_ledger().get_total_rake(), 4);
+    game.audit_chips().unwrap();
+}
+
+#[test]
+fn the_rake_stops_at_the_cap() {
+    let (game, events) = play_heads_up_pot(RakeSettings { percentage: 10.0, cap: Some(3), ..Default::default() });
+
+    assert_eq!(get_pots_raked(&events), vec![(100, 3, vec![(1, 97)])]);
+    assert_eq!(game.get_house_ledger().get_total_rake(), 3);
+}
+
+#[test]
+fn fuller_tables_use_their_own_cap() {
+    let rake_settings = RakeSettings { percentage: 10.0, cap: Some(4), no_flop_no_drop: false, player_count_caps: vec![(3, 6), (6, 8)] };
+    assert_eq!(rake_settings.calculate_rake(100, 2, true), 4);
+    assert_eq!(rake_settings.calculate_rake(100, 3, true), 6);
+    assert_eq!(rake_settings.calculate_rake(100, 9, true), 8);
+
+    // Heads-up is below every player count, so the plain cap applies
+    let (_, events) = play_heads_up_pot(rake_settings);
+    assert_eq!(get_pots_raked(&events), vec![(100, 4, vec![(1, 96)])]);
+}
+
+#[test]
+fn no_flop_no_drop_leaves_pots_won_before_the_flop_alone() {
+    for (no_flop_no_drop, expected_rake) in [(true, 0), (false, 2)] {
+        let mut game = TableConfig::new()
+            .with_player(1, "Alice", 100)
+            .with_player(2, "Bob", 100)
+            .with_blinds(5, 10)
+            .with_rake(RakeSettings { percentage: 10.0, no_flop_no_drop, ..Default::default() })
+            .with_rng_seed(5)
+            .build()
+            .unwrap();
+        set_scripts(&mut game, &[(1, &["raise to 30"]), (2, &["fold"])]);
+        let event_log = EventLog::new();
+        game.subscribe(Box::new(event_log.clone()));
+        game.start_next_hand().unwrap();
+        game.play().unwrap();
+
+        // Alice's uncalled raise comes back before the pot of both blinds is raked
+        assert_eq!(get_pots_raked(&event_log.get_events()), vec![(20, expected_rake, vec![(1, 20 - expected_rake)])]);
+        assert_eq!(game.get_house_ledger().get_total_rake(), expected_rake);
+    }
+}
+
+#[test]
+fn odd_chips_of_each_raked_pot_go_left_of_the_button() {
+    // Everyone plays the royal flush on the board. Alice on the button is all in for 25, and
+    // Bob in the small blind and Charlie in the big blind build a side pot behind her.
+    let mut game = TableConfig::new()
+        .with_player(1, "Alice", 25)
+        .with_player(2, "Bob", 100)
+        .with_player(3, "Charlie", 100)
+        .with_blinds(1, 2)
+        .with_rake(RakeSettings { percentage: 10.0, ..Default::default() })
+        .with_rng_seed(2)
+        .with_stacked_deck(&["2c", "3d", "4c", "5d", "6c", "7d", "Ts", "Js", "Qs", "Ks", "As"])
+        .build()
+        .unwrap();
+    set_scripts(&mut game, &[
+        (1, &["raise to 25"]),
+        (2, &["call", "bet 25", "check", "check"]),
+        (3, &["call", "call", "check", "check"]),
+    ]);
+    let event_log = EventLog::new();
+    game.subscribe(Box::new(event_log.clone()));
+    game.start_next_hand().unwrap();
+    game.play().unwrap();
+
+    // 75 less 7 rake splits three ways with two odd chips, and 50 less 5 splits two ways
+    // with one. Bob sits first left of the button, then Charlie, then Alice.
+    assert_eq!(get_pots_raked(&event_log.get_events()), vec![
+        (75, 7, vec![(2, 23), (3, 23), (1, 22)]),
+        (50, 5, vec![(2, 23), (3, 22)]),
+    ]);
+    assert_eq!(game.get_house_ledger().get_total_rake(), 12);
+    game.audit_chips().unwrap();
+}
@@ -0,0 +1,63 @@
+use poker::prelude::*;
+
+
+
+fn to_cards(short_names: &[&str]) -> Vec<Card> {
+    short_names.iter().map(|short_name| Card::from_short_name(short_name).unwrap()).collect()
+}
+
+// The view of whoever acts first in a fresh heads-up hand, after the given actions
+fn get_view_to_act(actions: &[PlayerAction]) -> PlayerView {
+    let mut game = TableConfig::new()
+        .with_player(1, "Alice", 100)
+        .with_player(2, "Bob", 100)
+        .with_blinds(1, 2)
+        .with_rng_seed(5)
+        .build()
+        .unwrap();
+    game.start_next_hand().unwrap();
+    game.step().unwrap();
+    for &action in actions {
+        game.apply_action(game.get_action_index().unwrap(), action, None).unwrap();
+    }
+    (0..2).filter_map(|seat| game.get_player_view(seat)).find(|player_view| player_view.is_to_act).unwrap()
+}
+
+#[test]
+fn chen_scores_match_the_published_examples() {
+    assert_eq!(RuleBasedAgent::get_chen_score(&to_cards(&["Ah", "As"])), 20);
+    assert_eq!(RuleBasedAgent::get_chen_score(&to_cards(&["Ah", "Kh"])), 12);
+    assert_eq!(RuleBasedAgent::get_chen_score(&to_cards(&["7c", "2d"])), -1);
+}
+
+#[test]
+fn a_flush_draw_has_nine_outs_and_an_open_ended_straight_draw_eight() {
+    assert_eq!(RuleBasedAgent::count_outs(&to_cards(&["Ah", "7h"]), &to_cards(&["Kh", "2h", "9c"])), 9);
+    assert_eq!(RuleBasedAgent::count_outs(&to_cards(&["9c", "8d"]), &to_cards(&["7h", "6s", "2c"])), 8);
+    // Both draws at once share the two straight cards of the flush suit
+    assert_eq!(RuleBasedAgent::count_outs(&to_cards(&["9h", "8h"]), &to_cards(&["7h", "6s", "2h"])), 15);
+    // Nothing is left to come on the river
+    assert_eq!(RuleBasedAgent::count_outs(&to_cards(&["Ah", "7h"]), &to_cards(&["Kh", "2h", "9c", "3s", "4d"])), 0);
+}
+
+#[test]
+fn make_legal_swaps_an_illegal_choice_for_the_nearest_legal_one() {
+    // The small blind faces the big blind: call, raise or fold
+    let player_view = get_view_to_act(&[]);
+    let checked = RuleBasedAgent::make_legal(&player_view, PlayerAction::Check, None);
+    assert_eq!((checked.action, checked.amount), (PlayerAction::Fold, None));
+    let bet = RuleBasedAgent::make_legal(&player_view, PlayerAction::Bet, None);
+    assert_eq!((bet.action, bet.amount), (PlayerAction::Raise, Some(player_view.minimum_raise_to)));
+    let short_raise = RuleBasedAgent::make_legal(&player_view, PlayerAction::Raise, Some(3));
+    assert_eq!((short_raise.action, short_raise.amount), (PlayerAction::Raise, Some(4)));
+    let huge_raise = RuleBasedAgent::make_legal(&player_view, PlayerAction::Raise, Some(10_000));
+    assert_eq!((huge_raise.action, huge_raise.amount), (PlayerAction::Raise, Some(100)));
+
+    // The big blind's option after a limp: nothing to call, so a fold or call becomes a check
+    let player_view = get_view_to_act(&[PlayerAction::Call]);
+    let folded = RuleBasedAgent::make_legal(&player_view, PlayerAction::Fold, None);
+    assert_eq!((folded.action, folded.amount), (PlayerAction::Check, None));
+    let called = RuleBasedAgent::make_legal(&player_view, PlayerAction::Call, None);
+    assert_eq!((called.action, called.amount), (PlayerAction::Check, None));
+    assert!(called.success);
+}
@@ -0,0 +1,428 @@
+mod common;
+
+use common::EventLog;
+use poker::prelude::*;
+use std::sync::{Arc, Mutex};
+
+
+
+// Plays a script and notes the smallest raise each view offered
+struct RaiseRecorder {
+    scripted_agent: ScriptedAgent,
+    minimum_raises: Arc<Mutex<Vec<(u32, u32)>>>
+}
+
+impl PlayerAgent for RaiseRecorder {
+    fn get_agent_name(&self) -> &str {
+        "raise_recorder"
+    }
+
+    fn decide(&mut self, player_view: &PlayerView) -> ActionResult {
+        self.minimum_raises.lock().unwrap().push((player_view.player_id, player_view.minimum_raise_to));
+        self.scripted_agent.decide(player_view)
+    }
+}
+
+fn set_scripts(game: &mut PokerGame, scripts: &[(u32, &[&str])]) {
+    for (player_id, script) in scripts {
+        game.set_agent(*player_id, Box::new(ScriptedAgent::from_script(script).unwrap()));
+    }
+}
+
+fn get_acting_order(events: &[GameEvent]) -> Vec<u32> {
+    events.iter()
+        .filter_map(|event| match event {
+            GameEvent::PlayerActed { player_id, .. } => Some(*player_id),
+            _ => None,
+        })
+        .collect()
+}
+
+fn get_pots_awarded(events: &[GameEvent]) -> Vec<(u32, Vec<(u32, u32)>)> {
+    events.iter()
+        .filter_map(|event| match event {
+            GameEvent::PotAwarded { amount, winners, .. } => Some((*amount, winners.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+// The forced bets of the last hand started, in the order they were posted
+fn get_blinds_posted(events: &[GameEvent]) -> Vec<(u32, BlindType, u32)> {
+    let hand_start = events.iter().rposition(|event| matches!(event, GameEvent::HandStarted { .. })).unwrap_or_default();
+    events[hand_start..].iter()
+        .filter_map(|event| match event {
+            GameEvent::BlindsPosted { player_id, blind_type, amount } => Some((*player_id, *blind_type, *amount)),
+            _ => None,
+        })
+        .collect()
+}
+
+// Starts the next hand and deals it up to the first preflop decision, giving who is to act
+// and the pot they face
+fn deal_to_first_action(game: &mut PokerGame, seat_count: usize) -> (u32, u32) {
+    game.start_next_hand().unwrap();
+    game.step().unwrap();
+    let player_views: Vec<PlayerView> = (0..seat_count).filter_map(|seat| game.get_player_view(seat)).collect();
+    let first_to_act = player_views.iter().find(|player_view| player_view.is_to_act).unwrap().player_id;
+    (first_to_act, player_views[0].get_total_pot())
+}
+
+// Four players with Alice on the button, Bob in the small blind, Charlie in the big blind
+// and Dave under the gun
+fn build_four_handed_game(forced_bets: ForcedBets) -> PokerGame {
+    TableConfig::new()
+        .with_player(1, "Alice", 100)
+        .with_player(2, "Bob", 100)
+        .with_player(3, "Charlie", 100)
+        .with_player(4, "Dave", 100)
+        .with_stakes(forced_bets)
+        .with_rng_seed(3)
+        .build()
+        .unwrap()
+}
+
+fn play_folded_hand(game: &mut PokerGame) {
+    set_scripts(game, &[(1, &["fold"]), (2, &["fold"]), (3, &["fold"]), (4, &["fold"])]);
+    game.start_next_hand().unwrap();
+    game.play().unwrap();
+}
+
+#[test]
+fn a_short_all_in_wins_only_the_main_pot() {
+    // Alice has aces on the button, Bob kings in the small blind and Charlie queens in the big blind
+    let mut game = TableConfig::new()
+        .with_player(1, "Alice", 50)
+        .with_player(2, "Bob", 100)
+        .with_player(3, "Charlie", 100)
+        .with_rng_seed(2)
+        .with_stacked_deck(&["Ah", "Ad", "Kh", "Kd", "Qh", "Qd", "2c", "7s", "9d", "3c", "4h"])
+        .build()
+        .unwrap();
+    set_scripts(&mut game, &[(1, &["raise to 50"]), (2, &["raise to 100"]), (3, &["call"])]);
+    let event_log = EventLog::new();
+    game.subscribe(Box::new(event_log.clone()));
+
+    game.start_next_hand().unwrap();
+    game.play().unwrap();
+
+    let events = event_log.get_events();
+    assert_eq!(get_pots_awarded(&events), vec![(150, vec![(1, 150)]), (100, vec![(2, 100)])]);
+    let coins: Vec<u32> = (0..3).map(|seat| game.get_player_view(seat).unwrap().get_own_seat().unwrap().coins).collect();
+    assert_eq!(coins, vec![150, 100, 0]);
+}
+
+#[test]
+fn raises_must_be_at_least_the_last_raise() {
+    let minimum_raises = Arc::new(Mutex::new(Vec::new()));
+    let mut game = TableConfig::new()
+        .with_player(1, "Alice", 100)
+        .with_player(2, "Bob", 100)
+        .with_rng_seed(5)
+        .build()
+        .unwrap();
+    let scripts: [(u32, &[&str]); 2] = [
+        (1, &["raise to 6", "raise to 14", "fold"]),
+        // Bob's first re-raise is short, so the engine rejects it and asks again
+        (2, &["raise to 9", "raise to 10", "raise to 22"]),
+    ];
+    for (player_id, script) in scripts {
+        let scripted_agent = ScriptedAgent::from_script(script).unwrap();
+        game.set_agent(player_id, Box::new(RaiseRecorder { scripted_agent, minimum_raises: minimum_raises.clone() }));
+    }
+    let event_log = EventLog::new();
+    game.subscribe(Box::new(event_log.clone()));
+
+    game.start_next_hand().unwrap();
+    game.play().unwrap();
+
+    let events = event_log.get_events();
+    let rejections: Vec<(u32, Option<u32>)> = events.iter()
+        .filter_map(|event| match event {
+            GameEvent::ActionRejected { player_id, amount, .. } => Some((*player_id, *amount)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(rejections, vec![(2, Some(9))]);
+    // Each raise sets the size the next one has to match
+    assert_eq!(*minimum_raises.lock().unwrap(), vec![(1, 4), (2, 10), (2, 10), (1, 14), (2, 18), (1, 30)]);
+    assert_eq!(get_pots_awarded(&events), vec![(28, vec![(2, 28)])]);
+}
+
+#[test]
+fn heads_up_the_button_acts_first_before_the_flop_and_last_after_it() {
+    let mut game = TableConfig::new()
+        .with_player(1, "Alice", 100)
+        .with_player(2, "Bob", 100)
+        .with_rng_seed(5)
+        .build()
+        .unwrap();
+    set_scripts(&mut game, &[(1, &["call", "check", "check", "check"]), (2, &["check", "check", "check", "check"])]);
+    let event_log = EventLog::new();
+    game.subscribe(Box::new(event_log.clone()));
+
+    game.start_next_hand().unwrap();
+    game.play().unwrap();
+
+    let events = event_log.get_events();
+    assert!(events.iter().any(|event| matches!(event, GameEvent::HandStarted { button_seat: 0, .. })));
+    assert!(events.iter().any(|event| matches!(event, GameEvent::BlindsPosted { player_id: 1, blind_type: BlindType::SmallBlind, .. })));
+    assert_eq!(get_acting_order(&events), vec![1, 2, 2, 1, 2, 1, 2, 1]);
+}
+
+#[test]
+fn stacked_cards_the_decks_cannot_hold_are_refused() {
+    let result = TableConfig::new()
+        .with_player(1, "Alice", 100)
+        .with_player(2, "Bob", 100)
+        .with_stacked_deck(&["Ah", "Kd", "Ah"])
+        .build();
+    assert!(matches!(result, Err(GameError::InvalidConfig { .. })));
+
+    let mut game = TableConfig::new()
+        .with_player(1, "Alice", 100)
+        .with_player(2, "Bob", 100)
+        .with_deck_count(2)
+        .build()
+        .unwrap();
+    let three_aces = vec![Card::from_short_name("Ah").unwrap(); 3];
+    assert!(matches!(game.stack_deck(three_aces), Err(GameError::InvalidConfig { .. })));
+    let two_aces = vec![Card::from_short_name("Ah").unwrap(); 2];
+    assert!(game.stack_deck(two_aces).is_ok());
+}
+
+#[test]
+fn a_deck_stacked_mid_hand_is_dealt_the_next_hand() {
+    let mut game = TableConfig::new()
+        .with_player(1, "Alice", 100)
+        .with_player(2, "Bob", 100)
+        .with_rng_seed(5)
+        .build()
+        .unwrap();
+    set_scripts(&mut game, &[(1, &["fold"]), (2, &[])]);
+    game.start_next_hand().unwrap();
+    let stacked_cards: Vec<Card> = ["7c", "2d", "As", "Ks"].iter().map(|short_name| Card::from_short_name(short_name).unwrap()).collect();
+    game.stack_deck(stacked_cards.clone()).unwrap();
+    game.play().unwrap();
+
+    game.start_next_hand().unwrap();
+    let mut hole_cards: Vec<Card> = Vec::new();
+    for seat in 0..2 {
+        hole_cards.extend(game.get_player_view(seat).unwrap().hole_cards);
+    }
+    assert_eq!(hole_cards, stacked_cards);
+}
+
+#[test]
+fn every_player_dealt_in_posts_the_ante() {
+    let mut game = build_four_handed_game(ForcedBets { small_blind: 1, big_blind: 2, ante: 1, ..Default::default() });
+    let event_log = EventLog::new();
+    game.subscribe(Box::new(event_log.clone()));
+
+    let (first_to_act, pot) = deal_to_first_action(&mut game, 4);
+
+    assert_eq!(get_blinds_posted(&event_log.get_events()), vec![
+        (1, BlindType::Ante, 1), (2, BlindType::Ante, 1), (3, BlindType::Ante, 1), (4, BlindType::Ante, 1),
+        (2, BlindType::SmallBlind, 1), (3, BlindType::BigBlind, 2),
+    ]);
+    assert_eq!(pot, 7);
+    assert_eq!(first_to_act, 4);
+}
+
+#[test]
+fn the_big_blind_posts_the_ante_for_the_table() {
+    let mut game = build_four_handed_game(ForcedBets { small_blind: 1, big_blind: 2, big_blind_ante: 2, ..Default::default() });
+    let event_log = EventLog::new();
+    game.subscribe(Box::new(event_log.clone()));
+
+    let (first_to_act, pot) = deal_to_first_action(&mut game, 4);
+
+    assert_eq!(get_blinds_posted(&event_log.get_events()), vec![
+        (2, BlindType::SmallBlind, 1), (3, BlindType::BigBlind, 2), (3, BlindType::BigBlindAnte, 2),
+    ]);
+    assert_eq!(pot, 5);
+    assert_eq!(first_to_act, 4);
+}
+
+#[test]
+fn an_under_the_gun_straddle_acts_last_before_the_flop() {
+    let mut game = build_four_handed_game(ForcedBets { small_blind: 1, big_blind: 2, straddle: Some(StraddleType::UnderTheGun), ..Default::default() });
+    set_scripts(&mut game, &[(1, &["call"]), (2, &["call"]), (3, &["call"]), (4, &["check"])]);
+    let event_log = EventLog::new();
+    game.subscribe(Box::new(event_log.clone()));
+
+    let (first_to_act, pot) = deal_to_first_action(&mut game, 4);
+    while game.get_hand_phase() == HandPhase::PreFlop {
+        game.step().unwrap();
+    }
+
+    let events = event_log.get_events();
+    assert_eq!(get_blinds_posted(&events), vec![
+        (2, BlindType::SmallBlind, 1), (3, BlindType::BigBlind, 2), (4, BlindType::Straddle, 4),
+    ]);
+    assert_eq!(pot, 7);
+    assert_eq!(first_to_act, 1);
+    assert_eq!(get_acting_order(&events), vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn a_button_straddle_starts_the_action_under_the_gun_and_acts_last() {
+    let mut game = build_four_handed_game(ForcedBets { small_blind: 1, big_blind: 2, straddle: Some(StraddleType::Button), ..Default::default() });
+    set_scripts(&mut game, &[(1, &["check"]), (2, &["call"]), (3, &["call"]), (4, &["call"])]);
+    let event_log = EventLog::new();
+    game.subscribe(Box::new(event_log.clone()));
+
+    let (first_to_act, pot) = deal_to_first_action(&mut game, 4);
+    while game.get_hand_phase() == HandPhase::PreFlop {
+        game.step().unwrap();
+    }
+
+    let events = event_log.get_events();
+    assert_eq!(get_blinds_posted(&events), vec![
+        (2, BlindType::SmallBlind, 1), (3, BlindType::BigBlind, 2), (1, BlindType::Straddle, 4),
+    ]);
+    assert_eq!(pot, 7);
+    assert_eq!(first_to_act, 4);
+    // The button is skipped until the blinds have acted, then has the option
+    assert_eq!(get_acting_order(&events), vec![4, 2, 3, 1]);
+}
+
+#[test]
+fn a_player_who_missed_the_big_blind_posts_it_live_on_returning() {
+    let mut game = build_four_handed_game(ForcedBets { small_blind: 1, big_blind: 2, ..Default::default() });
+    let event_log = EventLog::new();
+    game.subscribe(Box::new(event_log.clone()));
+    play_folded_hand(&mut game);
+    // Dave sits out while the big blind passes their seat
+    game.sit_out_player(3).unwrap();
+    play_folded_hand(&mut game);
+    game.return_player_to_table(3).unwrap();
+
+    set_scripts(&mut game, &[(1, &[]), (2, &[]), (3, &[]), (4, &[])]);
+    let (first_to_act, pot) = deal_to_first_action(&mut game, 4);
+
+    assert_eq!(get_blinds_posted(&event_log.get_events()), vec![
+        (1, BlindType::SmallBlind, 1), (2, BlindType::BigBlind, 2), (4, BlindType::MissedBigBlind, 2),
+    ]);
+    assert_eq!(pot, 5);
+    assert_eq!(first_to_act, 3);
+}
+
+#[test]
+fn a_player_who_missed_both_blinds_also_posts_a_dead_small_blind() {
+    let mut game = build_four_handed_game(ForcedBets { small_blind: 1, big_blind: 2, ..Default::default() });
+    let event_log = EventLog::new();
+    game.subscribe(Box::new(event_log.clone()));
+    play_folded_hand(&mut game);
+    // Charlie sits out as the small blind reaches their seat, and stays out until the big
+    // blind has passed it too
+    game.sit_out_player(2).unwrap();
+    for _ in 0..4 {
+        play_folded_hand(&mut game);
+    }
+    game.return_player_to_table(2).unwrap();
+
+    set_scripts(&mut game, &[(1, &[]), (2, &[]), (3, &[]), (4, &[])]);
+    let (first_to_act, pot) = deal_to_first_action(&mut game, 4);
+
+    assert_eq!(get_blinds_posted(&event_log.get_events()), vec![
+        (4, BlindType::SmallBlind, 1), (1, BlindType::BigBlind, 2),
+        (3, BlindType::MissedBigBlind, 2), (3, BlindType::DeadSmallBlind, 1),
+    ]);
+    // The dead small blind goes in the pot but does not count toward Charlie's bet
+    assert_eq!(pot, 6);
+    assert_eq!(game.get_player_view(2).unwrap().get_own_seat().unwrap().current_bet, 2);
+    assert_eq!(first_to_act, 2);
+}
+
+// (button, small blind, big blind) seats of the hand in progress
+fn get_positions(game: &PokerGame, seat: usize) -> (usize, usize, usize) {
+    let player_view = game.get_player_view(seat).unwrap();
+    (player_view.button_seat, player_view.small_blind_seat, player_view.big_blind_seat)
+}
+
+#[test]
+fn the_button_and_blinds_move_one_seat_each_hand() {
+    let mut game = build_four_handed_game(ForcedBets { small_blind: 1, big_blind: 2, ..Default::default() });
+    let mut positions = Vec::new();
+    for _ in 0..5 {
+        play_folded_hand(&mut game);
+        positions.push(get_positions(&game, 0));
+    }
+    assert_eq!(positions, vec![(0, 1, 2), (1, 2, 3), (2, 3, 0), (3, 0, 1), (0, 1, 2)]);
+}
+
+#[test]
+fn a_busted_big_blind_leaves_and_the_button_is_dead_the_next_hand() {
+    // Charlie goes all in on the big blind with one chip more than the small blind and loses
+    // to Alice's aces
+    let mut game = TableConfig::new()
+        .with_player(1, "Alice", 100)
+        .with_player(2, "Bob", 100)
+        .with_player(3, "Charlie", 2)
+        .with_player(4, "Dave", 100)
+        .with_rng_seed(3)
+        .with_stacked_deck(&["Ah", "Ad", "7c", "2d", "8c", "3d", "9c", "4h", "Kh", "Qs", "Js", "5c", "6d"])
+        .build()
+        .unwrap();
+    set_scripts(&mut game, &[(1, &["call"]), (2, &["fold"]), (3, &[]), (4, &["fold"])]);
+    let event_log = EventLog::new();
+    game.subscribe(Box::new(event_log.clone()));
+    game.start_next_hand().unwrap();
+    game.play().unwrap();
+    assert_eq!(get_positions(&game, 0), (0, 1, 2));
+
+    set_scripts(&mut game, &[(1, &[]), (2, &[]), (4, &[])]);
+    game.start_next_hand().unwrap();
+
+    let events = event_log.get_events();
+    assert!(events.iter().any(|event| matches!(event, GameEvent::PlayerLeft { player_id: 3, .. })));
+    assert_eq!(game.get_agent_name(3), None);
+    assert!(game.get_player_view(2).is_none());
+    // The big blind moves on to Dave, the small blind is dead on Charlie's empty seat and
+    // the button sits on Bob, so nobody skips or repeats a blind
+    assert_eq!(get_positions(&game, 0), (1, 2, 3));
+    assert_eq!(get_blinds_posted(&events), vec![(4, BlindType::BigBlind, 2)]);
+}
+
+#[test]
+fn a_player_sitting_down_between_hands_posts_a_big_blind_to_enter() {
+    let mut game = build_four_handed_game(ForcedBets { small_blind: 1, big_blind: 2, ..Default::default() });
+    let event_log = EventLog::new();
+    game.subscribe(Box::new(event_log.clone()));
+    play_folded_hand(&mut game);
+
+    let seat = game.sit_down_player(Player::new(5, String::from("Erin"), 100)).unwrap();
+    assert_eq!(seat, 4);
+    // Erin waits out the hand being played and is dealt in from the next one
+    assert!(game.get_player_view(4).is_none());
+    set_scripts(&mut game, &[(1, &[]), (2, &[]), (3, &[]), (4, &[]), (5, &[])]);
+    game.start_next_hand().unwrap();
+
+    assert_eq!(get_positions(&game, 0), (1, 2, 3));
+    assert_eq!(game.get_player_view(4).unwrap().hole_cards.len(), 2);
+    assert_eq!(get_blinds_posted(&event_log.get_events()), vec![
+        (3, BlindType::SmallBlind, 1), (4, BlindType::BigBlind, 2), (5, BlindType::MissedBigBlind, 2),
+    ]);
+}
+
+#[test]
+fn going_from_heads_up_to_three_handed_moves_the_button_on() {
+    let mut game = TableConfig::new()
+        .with_player(1, "Alice", 100)
+        .with_player(2, "Bob", 100)
+        .with_rng_seed(5)
+        .build()
+        .unwrap();
+    set_scripts(&mut game, &[(1, &["fold"]), (2, &[])]);
+    game.start_next_hand().unwrap();
+    game.play().unwrap();
+    // Heads-up Alice's button is also the small blind
+    assert_eq!(get_positions(&game, 0), (0, 0, 1));
+
+    game.sit_down_player(Player::new(3, String::from("Charlie"), 100)).unwrap();
+    set_scripts(&mut game, &[(1, &[]), (2, &[]), (3, &[])]);
+    game.start_next_hand().unwrap();
+
+    assert_eq!(get_positions(&game, 0), (1, 2, 0));
+}
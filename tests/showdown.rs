@@ -0,0 +1,38 @@
+mod common;
+
+use common::EventLog;
+use poker::prelude::*;
+
+
+
+#[test]
+fn the_showdown_reports_the_hand_values_that_decided_the_pot() {
+    // A straight beats three aces, which a sort by hand name would get backwards
+    let mut game = TableConfig::new()
+        .with_player(1, "Alice", 100)
+        .with_player(2, "Bob", 100)
+        .with_rng_seed(5)
+        .with_stacked_deck(&["5h", "6h", "Ac", "As", "7h", "8c", "9d", "Ad", "2s"])
+        .build()
+        .unwrap();
+    game.set_agent(1, Box::new(ScriptedAgent::from_script(&["call", "check", "check", "check"]).unwrap()));
+    game.set_agent(2, Box::new(ScriptedAgent::from_script(&["check", "check", "check", "check"]).unwrap()));
+    let event_log = EventLog::new();
+    game.subscribe(Box::new(event_log.clone()));
+
+    game.start_next_hand().unwrap();
+    game.play().unwrap();
+
+    let events = event_log.get_events();
+    let hands = events.iter()
+        .find_map(|event| match event {
+            GameEvent::Showdown { hands } => Some(hands.clone()),
+            _ => None,
+        })
+        .unwrap();
+    let hand_values: Vec<(u32, u8, &str)> = hands.iter()
+        .map(|(player_id, _, hand_value)| (*player_id, hand_value.category, hand_value.get_category_name()))
+        .collect();
+    assert_eq!(hand_values, vec![(1, HandValue::STRAIGHT, "Straight"), (2, HandValue::THREE_OF_A_KIND, "Three of a Kind")]);
+    assert!(events.iter().any(|event| matches!(event, GameEvent::PotAwarded { winners, .. } if winners.len() == 1 && winners[0].0 == 1)));
+}
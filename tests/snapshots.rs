@@ -0,0 +1,99 @@
+use poker::prelude::*;
+
+
+
+fn build_heads_up_game() -> PokerGame {
+    let mut game = TableConfig::new()
+        .with_player(1, "Alice", 500)
+        .with_player(2, "Bob", 500)
+        .with_blinds(10, 20)
+        .with_rake(RakeSettings { percentage: 10.0, cap: None, no_flop_no_drop: false, player_count_caps: Vec::new() })
+        .with_rng_seed(9)
+        .build()
+        .unwrap();
+    game.start_next_hand().unwrap();
+    game.step().unwrap();
+    game
+}
+
+#[test]
+fn undo_across_the_end_of_a_hand_takes_back_the_payout_and_the_rake() {
+    let mut game = build_heads_up_game();
+    let transfers_before = game.get_chip_ledger().get_transfers().len();
+    let player_index = game.get_action_index().unwrap();
+
+    game.apply_action(player_index, PlayerAction::Call, None).unwrap();
+    let other_index = game.get_action_index().unwrap();
+    game.apply_action(other_index, PlayerAction::Raise, Some(40)).unwrap();
+    game.apply_action(player_index, PlayerAction::Fold, None).unwrap();
+    game.play().unwrap();
+    assert_eq!(game.get_hand_phase(), HandPhase::HandOver);
+    assert_eq!(game.get_house_ledger().get_total_rake(), 4);
+
+    game.undo(3).unwrap();
+    assert_eq!(game.get_hand_phase(), HandPhase::PreFlop);
+    assert_eq!(game.get_action_index(), Some(player_index));
+    assert_eq!(game.get_house_ledger().get_total_rake(), 0);
+    assert!(game.get_house_ledger().get_hands().is_empty());
+    assert_eq!(game.get_chip_ledger().get_transfers().len(), transfers_before);
+    game.audit_chips().unwrap();
+
+    // The hand can be played on from the restored point
+    game.apply_action(player_index, PlayerAction::Fold, None).unwrap();
+    game.play().unwrap();
+    assert_eq!(game.get_house_ledger().get_hands().len(), 1);
+    game.audit_chips().unwrap();
+}
+
+fn restore_edited(game: &mut PokerGame, field: &str, value: serde_json::Value) -> Result<(), GameError> {
+    let mut snapshot_json: serde_json::Value = serde_json::from_slice(&game.snapshot().to_bytes().unwrap()).unwrap();
+    snapshot_json[field] = value;
+    game.restore_from_bytes(&serde_json::to_vec(&snapshot_json).unwrap())
+}
+
+#[test]
+fn crafted_snapshots_are_rejected_instead_of_panicking() {
+    let mut game = build_heads_up_game();
+    let action_index = game.get_action_index();
+
+    for (field, value) in [
+        ("action_index", serde_json::json!(7)),
+        ("button_seat", serde_json::json!(40)),
+        ("max_seats", serde_json::json!(0)),
+        ("pot", serde_json::json!(1)),
+        ("deck", serde_json::json!([])),
+    ] {
+        match restore_edited(&mut game, field, value) {
+            Err(GameError::InvalidSnapshot { .. }) => {},
+            other => panic!("editing {} gave {:?}", field, other),
+        }
+    }
+
+    // Nothing was restored, so the hand goes on as before
+    assert_eq!(game.get_action_index(), action_index);
+    restore_edited(&mut game, "hand_number", serde_json::json!(1)).unwrap();
+    game.play().unwrap();
+    assert_eq!(game.get_hand_phase(), HandPhase::HandOver);
+}
+
+#[test]
+fn snapshots_whose_chips_do_not_add_up_are_rejected() {
+    let mut game = build_heads_up_game();
+    let snapshot_json: serde_json::Value = serde_json::from_slice(&game.snapshot().to_bytes().unwrap()).unwrap();
+    let coins = snapshot_json["players"][0]["coins"].as_u64().unwrap();
+
+    // One chip more than was bought in, and stacks so big their sum does not fit a u32
+    for stacks in [[coins + 1, snapshot_json["players"][1]["coins"].as_u64().unwrap()], [u32::MAX as u64, u32::MAX as u64]] {
+        let mut edited_json = snapshot_json.clone();
+        for (player_index, stack) in stacks.iter().enumerate() {
+            edited_json["players"][player_index]["coins"] = serde_json::json!(stack);
+        }
+        let bytes = serde_json::to_vec(&edited_json).unwrap();
+        assert!(matches!(GameSnapshot::from_bytes(&bytes), Err(GameError::InvalidSnapshot { .. })));
+        assert!(matches!(game.restore_from_bytes(&bytes), Err(GameError::InvalidSnapshot { .. })));
+    }
+
+    // A snapshot the table took itself restores cleanly
+    game.restore(game.snapshot()).unwrap();
+    game.audit_chips().unwrap();
+}
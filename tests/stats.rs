@@ -0,0 +1,126 @@
+use poker::prelude::*;
+
+
+
+fn get_cards(short_names: &[&str]) -> Vec<Card> {
+    short_names.iter().map(|short_name| Card::from_short_name(short_name).unwrap()).collect()
+}
+
+// Alice has the button in the first hand, Bob the small blind and Charlie the big blind
+fn build_three_handed_game() -> PokerGame {
+    let mut game = TableConfig::new()
+        .with_player(1, "Alice", 200)
+        .with_player(2, "Bob", 200)
+        .with_player(3, "Charlie", 200)
+        .with_rng_seed(2)
+        .build()
+        .unwrap();
+    let scripts: [(u32, &[&str]); 3] = [
+        (1, &["raise to 6", "call", "fold", "check", "check", "check", "check"]),
+        (2, &["raise to 18", "bet 10", "fold"]),
+        (3, &["fold", "call", "check", "check", "check"]),
+    ];
+    for (player_id, script) in scripts {
+        game.set_agent(player_id, Box::new(ScriptedAgent::from_script(script).unwrap()));
+    }
+    game
+}
+
+// Hand one: Alice opens, Bob 3-bets, Charlie folds, Alice calls and folds to Bob's flop bet.
+// Hand two: Bob folds, and Alice's aces beat Charlie's kings at showdown.
+fn play_two_hands(game: &mut PokerGame) {
+    game.start_next_hand().unwrap();
+    game.stack_deck(get_cards(&["Ah", "Ad", "7c", "2d", "Ks", "Kh", "3c", "8d", "9s", "Jc", "4h"])).unwrap();
+    game.play().unwrap();
+    game.start_next_hand().unwrap();
+    game.play().unwrap();
+}
+
+fn get_seen_stats(game: &PokerGame, viewer_seat: usize, player_id: u32) -> PlayerStats {
+    game.get_player_view(viewer_seat).unwrap().get_seat_view(player_id).unwrap().stats.clone()
+}
+
+#[test]
+fn a_scripted_hand_records_three_bets_continuation_bets_and_showdowns() {
+    let mut game = build_three_handed_game();
+    play_two_hands(&mut game);
+
+    // Bob's notes, as his seat sees them
+    let alice = get_seen_stats(&game, 1, 1);
+    assert_eq!(alice.hands, 2);
+    assert_eq!((alice.preflop_raises, alice.three_bet_chances), (1, 0));
+    assert_eq!((alice.continuation_bets_faced, alice.folds_to_continuation_bet), (1, 1));
+    assert_eq!((alice.saw_flop, alice.went_to_showdown, alice.won_at_showdown), (2, 1, 1));
+    assert_eq!((alice.get_wtsd(), alice.get_wsd()), (Some(50.0), Some(100.0)));
+
+    let bob = get_seen_stats(&game, 1, 2);
+    assert_eq!((bob.three_bet_chances, bob.three_bets), (1, 1));
+    assert_eq!(bob.postflop_aggressive_actions, 1);
+    assert_eq!((bob.went_to_showdown, bob.won_at_showdown), (0, 0));
+
+    // Charlie faced two raises in the first hand, which is no chance to 3-bet
+    let charlie = get_seen_stats(&game, 1, 3);
+    assert_eq!((charlie.three_bet_chances, charlie.voluntary_hands), (0, 1));
+    assert_eq!((charlie.saw_flop, charlie.went_to_showdown, charlie.won_at_showdown), (1, 1, 0));
+    assert_eq!(charlie.get_fold_to_continuation_bet(), None);
+}
+
+#[test]
+fn seats_only_see_notes_on_what_happened_while_they_were_dealt_in() {
+    let mut game = TableConfig::new()
+        .with_player_at_seat(1, "Alice", 200, 0)
+        .with_player_at_seat(2, "Bob", 200, 1)
+        .with_player_at_seat(3, "Dave", 200, 2)
+        .with_rng_seed(5)
+        .build()
+        .unwrap();
+    game.sit_out_player(2).unwrap();
+    for player_id in [1, 2] {
+        game.set_agent(player_id, Box::new(ScriptedAgent::from_script(&["fold"]).unwrap()));
+    }
+    game.start_next_hand().unwrap();
+    game.play().unwrap();
+
+    // Dave sat out, so the hand is in Alice's and Bob's notes but not his
+    assert!(game.get_stats_tracker("Dave").is_none());
+    assert_eq!(game.get_stats_tracker("Alice").unwrap().get_stats("Bob").unwrap().hands, 1);
+    assert!(game.get_stats_tracker("Alice").unwrap().get_stats("Dave").is_none());
+    let dave_view = game.get_player_view(2).unwrap();
+    assert!(dave_view.seats.iter().all(|seat_view| seat_view.stats == PlayerStats::default()));
+}
+
+#[test]
+fn a_folded_hand_does_not_count_as_a_showdown() {
+    let mut game = build_three_handed_game();
+    play_two_hands(&mut game);
+
+    // Bob folded before the showdown in the second hand, so nobody saw his cards
+    for viewer_seat in 0..3 {
+        let bob = get_seen_stats(&game, viewer_seat, 2);
+        assert_eq!(bob.hands, 2);
+        assert_eq!(bob.went_to_showdown, 0);
+        assert_eq!(bob.get_wtsd(), Some(0.0));
+    }
+}
+
+#[test]
+fn stats_are_kept_by_snapshots_and_taken_back_by_undo() {
+    let mut game = build_three_handed_game();
+    play_two_hands(&mut game);
+    let stats_after_two_hands = game.get_stats_tracker("Bob").unwrap().clone();
+
+    let restored = PokerGame::from_snapshot(GameSnapshot::from_bytes(&game.snapshot().to_bytes().unwrap()).unwrap());
+    assert_eq!(restored.get_stats_tracker("Bob"), Some(&stats_after_two_hands));
+
+    // Taking back the last check reopens the second hand, so it leaves the notes again
+    game.undo(1).unwrap();
+    assert_eq!(game.get_hand_phase(), HandPhase::River);
+    let bob_notes = game.get_stats_tracker("Bob").unwrap();
+    assert_eq!(bob_notes.get_stats("Alice").unwrap().hands, 1);
+    assert_eq!(bob_notes.get_stats("Charlie").unwrap().went_to_showdown, 0);
+
+    // Playing the hand out again counts it once
+    game.set_agent(1, Box::new(ScriptedAgent::from_script(&["check"]).unwrap()));
+    game.play().unwrap();
+    assert_eq!(game.get_stats_tracker("Bob"), Some(&stats_after_two_hands));
+}
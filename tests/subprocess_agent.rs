@@ -0,0 +1,93 @@
+mod common;
+
+use common::EventLog;
+use poker::prelude::*;
+use std::time::{Duration, Instant};
+
+
+
+const BOT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/json_lines_bot.py");
+
+// Plays hands heads-up between the example bot, run with the given arguments, and a checking
+// and calling rule-based agent, and returns what the table published
+fn play_against_bot(bot_args: &[&str], time_limits: TimeLimits, number_of_hands: u32) -> Vec<GameEvent> {
+    let mut args = vec![String::from(BOT_PATH)];
+    args.extend(bot_args.iter().map(|bot_arg| String::from(*bot_arg)));
+    let mut game = TableConfig::new()
+        .with_player(1, "Bot", 100)
+        .with_player(2, "Alice", 100)
+        .with_time_limits(time_limits)
+        .with_rng_seed(3)
+        .build()
+        .unwrap();
+    game.set_agent(1, Box::new(SubprocessAgent::new("python3", args).unwrap()));
+    game.set_agent(2, Box::new(RuleBasedAgent::new()));
+    let event_log = EventLog::new();
+    game.subscribe(Box::new(event_log.clone()));
+    game.play_session(number_of_hands).unwrap();
+    event_log.get_events()
+}
+
+fn get_problems(events: &[GameEvent]) -> Vec<String> {
+    events.iter()
+        .filter_map(|event| match event {
+            GameEvent::AgentProblem { player_id: 1, problem } => Some(problem.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn count_actions(events: &[GameEvent], player_id: u32) -> usize {
+    events.iter()
+        .filter(|event| matches!(event, GameEvent::PlayerActed { player_id: acting_player_id, .. } if *acting_player_id == player_id))
+        .count()
+}
+
+fn with_action_seconds(action_seconds: u64) -> TimeLimits {
+    TimeLimits { action_seconds: Some(action_seconds), time_bank_seconds: 0 }
+}
+
+#[test]
+fn a_well_behaved_bot_plays_its_hands() {
+    let events = play_against_bot(&[], with_action_seconds(5), 3);
+    assert_eq!(get_problems(&events), Vec::<String>::new());
+    assert!(count_actions(&events, 1) > 0);
+    assert!(!events.iter().any(|event| matches!(event, GameEvent::PlayerTimedOut { .. })));
+}
+
+#[test]
+fn a_crashed_bot_folds_from_then_on() {
+    let events = play_against_bot(&["--misbehave", "crash"], with_action_seconds(5), 3);
+    let problems = get_problems(&events);
+    // The crash is reported once, not for every hand the bot folds afterwards
+    assert_eq!(problems.len(), 1, "{:?}", problems);
+    assert!(problems[0].contains("folding its hands from now on"));
+}
+
+#[test]
+fn a_garbage_reply_is_reported() {
+    let events = play_against_bot(&["--misbehave", "garbage"], with_action_seconds(5), 1);
+    let problems = get_problems(&events);
+    assert!(!problems.is_empty());
+    assert!(problems.iter().all(|problem| problem.contains("malformed reply")), "{:?}", problems);
+}
+
+#[test]
+fn an_illegal_reply_is_reported() {
+    let events = play_against_bot(&["--misbehave", "illegal"], with_action_seconds(5), 1);
+    let problems = get_problems(&events);
+    assert!(!problems.is_empty());
+    assert!(problems.iter().all(|problem| problem.contains("Raise") && problem.contains("outside")), "{:?}", problems);
+}
+
+#[test]
+fn a_slow_bot_is_acted_for_in_time() {
+    let started_at = Instant::now();
+    let events = play_against_bot(&["--misbehave", "slow"], with_action_seconds(1), 1);
+
+    // The bot sleeps ten seconds a request, so it never gets to answer
+    assert!(started_at.elapsed() < Duration::from_secs(8));
+    let slow_problems = get_problems(&events).iter().filter(|problem| problem.contains("did not reply")).count();
+    let timeouts = events.iter().filter(|event| matches!(event, GameEvent::PlayerTimedOut { player_id: 1, .. })).count();
+    assert!(slow_problems + timeouts > 0);
+}
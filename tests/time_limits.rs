@@ -0,0 +1,202 @@
+mod common;
+
+use common::EventLog;
+use poker::prelude::*;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+
+
+// The time left and the time bank each view showed
+type TimesSeen = Arc<Mutex<Vec<(Option<u64>, u64)>>>;
+
+// Waits on input that never comes, like a person who walked away from the keyboard
+struct HungAgent {
+    input: Receiver<()>,
+    _keep_open: Sender<()>
+}
+
+impl HungAgent {
+    fn new() -> Self {
+        let (keep_open, input) = mpsc::channel();
+        HungAgent { input, _keep_open: keep_open }
+    }
+}
+
+impl PlayerAgent for HungAgent {
+    fn get_agent_name(&self) -> &str {
+        "hung"
+    }
+
+    fn decide(&mut self, _player_view: &PlayerView) -> ActionResult {
+        let _ = self.input.recv();
+        ActionResult { action: PlayerAction::Fold, success: true, amount: None }
+    }
+}
+
+// Checks or calls, and notes the time each view said was left
+struct TimedAgent {
+    times_seen: TimesSeen
+}
+
+impl PlayerAgent for TimedAgent {
+    fn get_agent_name(&self) -> &str {
+        "timed"
+    }
+
+    fn decide(&mut self, player_view: &PlayerView) -> ActionResult {
+        self.times_seen.lock().unwrap().push((player_view.time_left_ms, player_view.time_bank_left_ms));
+        let action = if player_view.is_legal(PlayerAction::Check) { PlayerAction::Check } else { PlayerAction::Call };
+        ActionResult { action, success: true, amount: None }
+    }
+}
+
+// Takes longer than it is allowed and then panics, long after its seat was acted for
+struct LatePanicAgent;
+
+impl PlayerAgent for LatePanicAgent {
+    fn get_agent_name(&self) -> &str {
+        "late panic"
+    }
+
+    fn decide(&mut self, _player_view: &PlayerView) -> ActionResult {
+        std::thread::sleep(Duration::from_millis(1500));
+        panic!("the bot crashed");
+    }
+}
+
+#[test]
+fn a_hung_agent_is_timed_out_instead_of_holding_up_the_table() {
+    let mut game = TableConfig::new()
+        .with_player(1, "Hung", 100)
+        .with_player(2, "Other", 100)
+        .with_time_limits(TimeLimits { action_seconds: Some(1), time_bank_seconds: 0 })
+        .with_rng_seed(4)
+        .build()
+        .unwrap();
+    game.set_agent(1, Box::new(HungAgent::new()));
+    game.set_agent(2, Box::new(TimedAgent { times_seen: Arc::new(Mutex::new(Vec::new())) }));
+    let event_log = EventLog::new();
+    game.subscribe(Box::new(event_log.clone()));
+
+    let started_at = Instant::now();
+    game.play_session(2).unwrap();
+
+    assert!(started_at.elapsed() < Duration::from_secs(10));
+    let timeouts = event_log.get_events().iter()
+        .filter(|event| matches!(event, GameEvent::PlayerTimedOut { player_id: 1, .. }))
+        .count();
+    // The agent never answers, so it is acted for at least once in each hand
+    assert!(timeouts >= 2);
+}
+
+#[test]
+fn the_view_shows_the_time_left_and_the_time_bank() {
+    let times_seen = Arc::new(Mutex::new(Vec::new()));
+    let mut game = TableConfig::new()
+        .with_player(1, "Alice", 100)
+        .with_player(2, "Bob", 100)
+        .with_time_limits(TimeLimits { action_seconds: Some(30), time_bank_seconds: 10 })
+        .with_rng_seed(4)
+        .build()
+        .unwrap();
+    let clock = ManualClock::new();
+    game.set_clock(Box::new(clock.clone()));
+    for player_id in [1, 2] {
+        game.set_agent(player_id, Box::new(TimedAgent { times_seen: times_seen.clone() }));
+    }
+    game.start_next_hand().unwrap();
+    while times_seen.lock().unwrap().len() < 2 {
+        game.step().unwrap();
+    }
+
+    let times_seen = times_seen.lock().unwrap().clone();
+    assert_eq!(times_seen[0], (Some(40_000), 10_000));
+    assert_eq!(times_seen[1], (Some(40_000), 10_000));
+    // Only the player to act has a clock running
+    for seat in [0, 1] {
+        let player_view = game.get_player_view(seat).unwrap();
+        if !player_view.is_to_act {
+            assert_eq!(player_view.time_left_ms, None);
+            assert_eq!(player_view.time_bank_left_ms, 10_000);
+        }
+    }
+}
+
+#[test]
+fn a_disconnected_player_sits_out_from_the_next_hand() {
+    let mut game = TableConfig::new()
+        .with_player_at_seat(1, "Alice", 100, 0)
+        .with_player_at_seat(2, "Bob", 100, 1)
+        .with_player_at_seat(3, "Charlie", 100, 2)
+        .with_rng_seed(4)
+        .build()
+        .unwrap();
+    let times_seen = Arc::new(Mutex::new(Vec::new()));
+    for player_id in [2, 3] {
+        game.set_agent(player_id, Box::new(TimedAgent { times_seen: times_seen.clone() }));
+    }
+    let event_log = EventLog::new();
+    game.subscribe(Box::new(event_log.clone()));
+    game.start_next_hand().unwrap();
+    game.disconnect_player(0).unwrap();
+
+    // Everyone dealt in stays in this hand, and the disconnected player is acted for
+    let seated_now = |game: &PokerGame| game.get_player_view(1).unwrap().seats.iter().filter(|seat_view| !seat_view.sitting_out).count();
+    assert_eq!(seated_now(&game), 3);
+    game.play().unwrap();
+    assert!(event_log.get_events().iter().any(|event| matches!(event, GameEvent::PlayerTimedOut { player_id: 1, .. })));
+
+    game.start_next_hand().unwrap();
+    assert_eq!(seated_now(&game), 2);
+    assert!(game.get_player_view(0).unwrap().seats[0].sitting_out);
+}
+
+#[test]
+fn an_agent_that_panics_after_timing_out_is_dropped_without_ending_the_session() {
+    let mut game = TableConfig::new()
+        .with_player_at_seat(1, "Crashy", 100, 0)
+        .with_player_at_seat(2, "Bob", 100, 1)
+        .with_player_at_seat(3, "Charlie", 100, 2)
+        .with_time_limits(TimeLimits { action_seconds: Some(1), time_bank_seconds: 0 })
+        .with_rng_seed(4)
+        .build()
+        .unwrap();
+    game.set_agent(1, Box::new(LatePanicAgent));
+    let times_seen = Arc::new(Mutex::new(Vec::new()));
+    for player_id in [2, 3] {
+        game.set_agent(player_id, Box::new(TimedAgent { times_seen: times_seen.clone() }));
+    }
+    let event_log = EventLog::new();
+    game.subscribe(Box::new(event_log.clone()));
+
+    game.play_session(1).unwrap();
+    // The agent panics while the table is between hands, and is found out on its next turn
+    std::thread::sleep(Duration::from_millis(1000));
+    game.play_session(2).unwrap();
+
+    let events = event_log.get_events();
+    assert!(events.iter().any(|event| matches!(event, GameEvent::AgentProblem { player_id: 1, problem } if problem.contains("the bot crashed"))));
+    assert!(events.iter().any(|event| matches!(event, GameEvent::PlayerDisconnected { player_id: 1 })));
+    assert!(game.get_player_view(0).unwrap().seats[0].sitting_out);
+}
+
+#[test]
+fn huge_time_limits_do_not_overflow() {
+    let times_seen = Arc::new(Mutex::new(Vec::new()));
+    let mut game = TableConfig::new()
+        .with_player(1, "Alice", 100)
+        .with_player(2, "Bob", 100)
+        .with_time_limits(TimeLimits { action_seconds: Some(u64::MAX), time_bank_seconds: u64::MAX })
+        .with_rng_seed(4)
+        .build()
+        .unwrap();
+    for player_id in [1, 2] {
+        game.set_agent(player_id, Box::new(TimedAgent { times_seen: times_seen.clone() }));
+    }
+
+    game.play_session(1).unwrap();
+
+    assert!(times_seen.lock().unwrap().iter().all(|(time_left_ms, time_bank_left_ms)| *time_left_ms == Some(u64::MAX) && *time_bank_left_ms == u64::MAX));
+}